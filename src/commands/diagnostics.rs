@@ -0,0 +1,104 @@
+// ABOUTME: Redacted diagnostics bundle for bug reports
+// ABOUTME: Captures tool/server versions and sanitized connection parameters as JSON
+
+use crate::{postgres, utils};
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+/// Sanitized connection details and server version for one side of a migration
+#[derive(Debug, Serialize)]
+pub struct EndpointDiagnostics {
+    /// `connection_string` run through [`utils::strip_password_from_url`] - never
+    /// the raw URL, so this struct can never carry a credential
+    pub connection_string: String,
+    pub host: String,
+    pub port: u16,
+    pub database: String,
+    pub sslmode: String,
+    /// `SHOW server_version`'s result, or `None` if the endpoint couldn't be reached
+    pub server_version: Option<String>,
+}
+
+/// Full diagnostics bundle for a bug report
+#[derive(Debug, Serialize)]
+pub struct DiagnosticsBundle {
+    pub crate_version: String,
+    pub os: String,
+    pub arch: String,
+    pub tools: Vec<utils::ToolVersionInfo>,
+    pub source: EndpointDiagnostics,
+    pub target: EndpointDiagnostics,
+}
+
+/// Collect a single copy-pasteable JSON diagnostics bundle for a bug report: detected
+/// `pg_dump`/`pg_dumpall`/`psql` versions, each endpoint's `server_version`, connection
+/// parameters with credentials removed, the resolved `sslmode`, and basic OS/crate
+/// version info.
+///
+/// Every string that could possibly carry a credential or control character is run
+/// through [`utils::strip_password_from_url`] or [`utils::sanitize_identifier`] before
+/// being placed in the bundle, so the result is always safe to paste into a public
+/// issue tracker - including when a lookup itself fails (a server_version query error
+/// is logged and recorded as `None`, never surfaced as raw error text that might echo
+/// back connection details).
+///
+/// # Errors
+///
+/// Returns an error only if a connection URL fails to parse; a reachability failure
+/// for either database is recorded as a `None` `server_version` instead of failing the
+/// whole bundle, since a bug report about a connection failure is exactly the case
+/// this is meant to help with.
+pub async fn collect_diagnostics(source_url: &str, target_url: &str) -> Result<DiagnosticsBundle> {
+    let tools = utils::detect_tool_versions();
+    let source = collect_endpoint_diagnostics(source_url).await?;
+    let target = collect_endpoint_diagnostics(target_url).await?;
+
+    Ok(DiagnosticsBundle {
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        tools,
+        source,
+        target,
+    })
+}
+
+async fn collect_endpoint_diagnostics(url: &str) -> Result<EndpointDiagnostics> {
+    let parts = utils::parse_postgres_url(url)
+        .with_context(|| format!("Failed to parse connection URL: {}", url))?;
+
+    let connection_string = utils::strip_password_from_url(url)
+        .unwrap_or_else(|_| "<connection string could not be parsed>".to_string());
+
+    let sslmode = parts
+        .query_params
+        .get("sslmode")
+        .cloned()
+        .unwrap_or_else(|| "verify-full".to_string());
+
+    let server_version = match postgres::connect(url).await {
+        Ok(client) => match client.query_one("SHOW server_version", &[]).await {
+            Ok(row) => {
+                let version: String = row.get(0);
+                Some(utils::sanitize_identifier(&version))
+            }
+            Err(e) => {
+                tracing::warn!("Failed to query server_version for diagnostics: {}", e);
+                None
+            }
+        },
+        Err(e) => {
+            tracing::warn!("Failed to connect for diagnostics: {}", e);
+            None
+        }
+    };
+
+    Ok(EndpointDiagnostics {
+        connection_string,
+        host: utils::sanitize_identifier(&parts.host),
+        port: parts.port,
+        database: utils::sanitize_identifier(&parts.database),
+        sslmode: utils::sanitize_identifier(&sslmode),
+        server_version,
+    })
+}
@@ -1,14 +1,18 @@
 // ABOUTME: Command implementations for each migration phase
-// ABOUTME: Exports validate, init, sync, status, and verify commands
+// ABOUTME: Exports validate, init, sync, status, verify, worker, and diagnostics commands
 
+pub mod diagnostics;
 pub mod init;
 pub mod status;
 pub mod sync;
 pub mod validate;
 pub mod verify;
+pub mod worker;
 
+pub use diagnostics::{collect_diagnostics, DiagnosticsBundle, EndpointDiagnostics};
 pub use init::init;
-pub use status::status;
-pub use sync::sync;
-pub use validate::validate;
-pub use verify::verify;
+pub use status::{status, watch_status, LagThresholds, StatusFormat, StatusHealth};
+pub use sync::{sync, watch};
+pub use validate::{validate, ValidateReportFormat, ValidationCheckReport, ValidationCheckStatus, ValidationReport};
+pub use verify::{verify, VerifyOutcome, VerifyReportFormat};
+pub use worker::worker;
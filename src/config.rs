@@ -0,0 +1,144 @@
+// ABOUTME: TOML config-file support for connection/filter/subscription-template defaults
+// ABOUTME: Lets a checked-in `migrator.toml` replace flags repeated on every invocation
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// One `[source]` or `[target]` section: just the connection URL today, but
+/// broken out into its own section so per-endpoint options (e.g. a CA bundle
+/// path) have somewhere to go later without a breaking config-file change
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EndpointConfig {
+    pub url: Option<String>,
+}
+
+/// Mirrors [`crate::filters::ReplicationFilter::new`]'s four arguments, so a
+/// `[filter]` section can be checked into version control instead of repeated
+/// as CLI flags on every invocation
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FilterConfig {
+    pub include_databases: Option<Vec<String>>,
+    pub exclude_databases: Option<Vec<String>>,
+    pub include_tables: Option<Vec<String>>,
+    pub exclude_tables: Option<Vec<String>>,
+}
+
+/// Top-level shape of a migrator TOML config file (e.g. `migrator.toml`)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MigratorConfig {
+    #[serde(default)]
+    pub source: EndpointConfig,
+    #[serde(default)]
+    pub target: EndpointConfig,
+    #[serde(default)]
+    pub filter: FilterConfig,
+    /// Template used to derive each database's subscription name (see
+    /// `commands::status`'s `sub_name_template` argument); `None` means the
+    /// caller should fall back to its own hardcoded default
+    pub subscription_name_template: Option<String>,
+}
+
+/// Load and parse a migrator config file
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read, or isn't valid TOML matching
+/// [`MigratorConfig`]'s shape.
+pub fn load_migrator_config(path: &Path) -> Result<MigratorConfig> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file '{}'", path.display()))?;
+    toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse config file '{}' as TOML", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_migrator_config_full() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("migrator_test_full_{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"
+subscription_name_template = "custom_sub"
+
+[source]
+url = "postgresql://source.example.com/postgres"
+
+[target]
+url = "postgresql://target.example.com/postgres"
+
+[filter]
+include_databases = ["mydb", "analytics"]
+exclude_tables = ["mydb.audit_log"]
+"#,
+        )
+        .unwrap();
+
+        let config = load_migrator_config(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(
+            config.source.url.as_deref(),
+            Some("postgresql://source.example.com/postgres")
+        );
+        assert_eq!(
+            config.target.url.as_deref(),
+            Some("postgresql://target.example.com/postgres")
+        );
+        assert_eq!(
+            config.filter.include_databases,
+            Some(vec!["mydb".to_string(), "analytics".to_string()])
+        );
+        assert_eq!(
+            config.filter.exclude_tables,
+            Some(vec!["mydb.audit_log".to_string()])
+        );
+        assert_eq!(config.subscription_name_template.as_deref(), Some("custom_sub"));
+    }
+
+    #[test]
+    fn test_load_migrator_config_partial() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!(
+            "migrator_test_partial_{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "[source]\nurl = \"postgresql://source.example.com/postgres\"\n").unwrap();
+
+        let config = load_migrator_config(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(
+            config.source.url.as_deref(),
+            Some("postgresql://source.example.com/postgres")
+        );
+        assert!(config.target.url.is_none());
+        assert!(config.filter.include_databases.is_none());
+        assert!(config.subscription_name_template.is_none());
+    }
+
+    #[test]
+    fn test_load_migrator_config_missing_file() {
+        let path = Path::new("/nonexistent/migrator.toml");
+        assert!(load_migrator_config(path).is_err());
+    }
+
+    #[test]
+    fn test_load_migrator_config_invalid_toml() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!(
+            "migrator_test_invalid_{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "this is not valid toml [[[").unwrap();
+
+        let result = load_migrator_config(&path);
+        std::fs::remove_file(&path).ok();
+
+        assert!(result.is_err());
+    }
+}
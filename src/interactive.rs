@@ -4,10 +4,84 @@
 use crate::{
     filters::ReplicationFilter,
     migration, postgres,
+    profile::{save_profile, SchemaOnlyEntry, SelectionProfile, TimeFilterEntry},
     table_rules::{QualifiedTable, TableRules},
 };
 use anyhow::{Context, Result};
-use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect, Select};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Default number of databases discovered concurrently by [`discover_databases`]
+const DEFAULT_DISCOVERY_CONCURRENCY: usize = 8;
+
+/// Schemas and tables discovered in one database by [`discover_databases`]
+struct DatabaseDiscovery {
+    schemas: Vec<String>,
+    tables: Vec<migration::TableInfo>,
+}
+
+/// A table found ineligible for logical replication, collected across all selected
+/// databases before the final confirmation prompt
+struct EligibilityViolation {
+    database: String,
+    schema: String,
+    table: String,
+    reason: String,
+}
+
+/// Connect to each of `databases` and discover its schemas and tables, bounded to
+/// `max_concurrency` connections in flight at once via a [`Semaphore`]
+///
+/// Each database's connection/discovery failure is captured in its own `Result`
+/// rather than aborting the whole run, so one unreachable database doesn't stop the
+/// caller from prompting about the rest.
+async fn discover_databases(
+    source_url: &str,
+    databases: &[String],
+    max_concurrency: usize,
+) -> HashMap<String, Result<DatabaseDiscovery>> {
+    let max_concurrency = max_concurrency.max(1).min(databases.len().max(1));
+    let semaphore = Arc::new(Semaphore::new(max_concurrency));
+
+    let discoveries = databases.iter().map(|db_name| {
+        let semaphore = Arc::clone(&semaphore);
+        let source_url = source_url.to_string();
+        let db_name = db_name.clone();
+
+        async move {
+            let _permit = semaphore
+                .acquire()
+                .await
+                .expect("semaphore is never closed while discovery is in flight");
+
+            let result = async {
+                let db_url = replace_database_in_url(&source_url, &db_name)
+                    .context(format!("Failed to build URL for database '{}'", db_name))?;
+                let db_client = postgres::connect(&db_url)
+                    .await
+                    .context(format!("Failed to connect to database '{}'", db_name))?;
+                let schemas = migration::list_schemas(&db_client)
+                    .await
+                    .context(format!("Failed to list schemas in database '{}'", db_name))?;
+                let tables = migration::list_tables(&db_client)
+                    .await
+                    .context(format!("Failed to list tables from database '{}'", db_name))?;
+                Ok(DatabaseDiscovery { schemas, tables })
+            }
+            .await;
+
+            (db_name, result)
+        }
+    });
+
+    futures::future::join_all(discoveries)
+        .await
+        .into_iter()
+        .collect()
+}
 
 /// Interactive database and table selection with advanced filtering
 ///
@@ -103,24 +177,108 @@ pub async fn select_databases_and_tables(
     }
     tracing::info!("");
 
+    // Discover schemas and tables for every selected database up front, fanned out
+    // across a bounded pool of connections rather than one at a time, so 20 databases
+    // don't serialize 20 round-trips before the user can make a single choice.
+    tracing::info!(
+        "Discovering schemas and tables across {} database(s)...",
+        selected_databases.len()
+    );
+    let mut discoveries = discover_databases(
+        source_url,
+        &selected_databases,
+        DEFAULT_DISCOVERY_CONCURRENCY,
+    )
+    .await;
+    tracing::info!("");
+
     // Step 2: For each selected database, configure table-level rules
     let mut excluded_tables: Vec<String> = Vec::new();
     let mut table_rules = TableRules::default();
+    let mut profile = SelectionProfile::new(selected_databases.clone());
+    let mut eligibility_violations: Vec<EligibilityViolation> = Vec::new();
 
     for db_name in &selected_databases {
-        // Build database-specific connection URL
+        // Build database-specific connection URL (used below to derive a
+        // search_path-scoped URL once schemas are chosen; discovery already used its
+        // own connection and doesn't need this one)
         let db_url = replace_database_in_url(source_url, db_name)
             .context(format!("Failed to build URL for database '{}'", db_name))?;
 
-        // Connect to the specific database
-        tracing::info!("Discovering tables in database '{}'...", db_name);
-        let db_client = postgres::connect(&db_url)
-            .await
-            .context(format!("Failed to connect to database '{}'", db_name))?;
+        let discovery = match discoveries.remove(db_name) {
+            Some(Ok(discovery)) => discovery,
+            Some(Err(e)) => {
+                tracing::warn!("⚠ Skipping database '{}': {}", db_name, e);
+                tracing::info!("");
+                continue;
+            }
+            None => continue,
+        };
+
+        let all_schemas = discovery.schemas;
 
-        let all_tables = migration::list_tables(&db_client)
-            .await
-            .context(format!("Failed to list tables from database '{}'", db_name))?;
+        let selected_schemas: Vec<String> = if all_schemas.len() <= 1 {
+            all_schemas
+        } else {
+            println!("Select schemas to replicate from '{}':", db_name);
+            println!("(Use arrow keys to navigate, Space to select, Enter to confirm)");
+            println!();
+
+            let schema_selections = MultiSelect::with_theme(&ColorfulTheme::default())
+                .items(&all_schemas)
+                .defaults(
+                    &all_schemas
+                        .iter()
+                        .map(|s| s == "public")
+                        .collect::<Vec<bool>>(),
+                )
+                .interact()
+                .context(format!(
+                    "Failed to get schema selection for database '{}'",
+                    db_name
+                ))?;
+
+            if schema_selections.is_empty() {
+                tracing::warn!("⚠ No schemas selected for '{}', skipping database", db_name);
+                tracing::info!("");
+                continue;
+            }
+
+            schema_selections
+                .iter()
+                .map(|&idx| all_schemas[idx].clone())
+                .collect()
+        };
+
+        tracing::info!(
+            "✓ Replicating schema(s) {} from '{}'",
+            selected_schemas.join(", "),
+            db_name
+        );
+        tracing::info!("");
+
+        // Build a search_path-scoped variant of this database's connection string
+        // for the selected schemas. It is only logged at debug level; it is not
+        // returned, and the connection strings used for replication are unchanged.
+        let scoped_db_url = crate::utils::apply_connection_params(
+            &db_url,
+            &[("search_path".to_string(), selected_schemas.join(","))],
+        )
+        .context(format!(
+            "Failed to apply search_path for database '{}'",
+            db_name
+        ))?;
+        tracing::debug!(
+            "search_path-scoped connection for '{}': {}",
+            db_name,
+            crate::utils::redact_url_for_logging(&scoped_db_url)
+        );
+
+        let all_tables: Vec<migration::TableInfo> = discovery
+            .tables
+            .into_iter()
+            .filter(|t| selected_schemas.contains(&t.schema))
+            .collect();
 
         if all_tables.is_empty() {
             tracing::info!("  No tables found in database '{}'", db_name);
@@ -143,21 +301,52 @@ pub async fn select_databases_and_tables(
             })
             .collect();
 
-        println!(
-            "Select tables to EXCLUDE from '{}' (or press Enter to include all):",
-            db_name
-        );
-        println!("(Use arrow keys to navigate, Space to select, Enter to confirm)");
-        println!();
+        if selected_schemas.len() > 1 {
+            println!("Tables in '{}', grouped by schema:", db_name);
+            for schema in &selected_schemas {
+                let count = all_tables.iter().filter(|t| &t.schema == schema).count();
+                if count > 0 {
+                    println!("  == {} ({} table(s)) ==", schema, count);
+                }
+            }
+            println!();
+        }
 
-        let table_exclusions = MultiSelect::with_theme(&ColorfulTheme::default())
-            .items(&table_display_names)
+        let selection_mode = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "How do you want to select tables from '{}'?",
+                db_name
+            ))
+            .items(&[
+                "Pick tables to exclude from a list",
+                "Match tables with glob patterns (e.g. audit_*, public.logs_*)",
+            ])
+            .default(0)
             .interact()
             .context(format!(
-                "Failed to get table exclusion selection for database '{}'",
+                "Failed to get table selection mode for database '{}'",
                 db_name
             ))?;
 
+        let table_exclusions = if selection_mode == 0 {
+            println!(
+                "Select tables to EXCLUDE from '{}' (or press Enter to include all):",
+                db_name
+            );
+            println!("(Use arrow keys to navigate, Space to select, Enter to confirm)");
+            println!();
+
+            MultiSelect::with_theme(&ColorfulTheme::default())
+                .items(&table_display_names)
+                .interact()
+                .context(format!(
+                    "Failed to get table exclusion selection for database '{}'",
+                    db_name
+                ))?
+        } else {
+            resolve_table_patterns_to_exclusions(db_name, &table_display_names)?
+        };
+
         // Track which tables are excluded
         let excluded_indices: std::collections::HashSet<usize> =
             table_exclusions.iter().copied().collect();
@@ -181,6 +370,9 @@ pub async fn select_databases_and_tables(
                 tracing::info!("  - {}", table);
             }
 
+            profile
+                .excluded_tables
+                .extend(excluded_in_db.iter().cloned());
             excluded_tables.extend(excluded_in_db);
         } else {
             tracing::info!("");
@@ -239,6 +431,11 @@ pub async fn select_databases_and_tables(
                         table_info.name.clone(),
                     );
                     table_rules.add_schema_only_table(qualified)?;
+                    profile.schema_only_tables.push(SchemaOnlyEntry {
+                        database: db_name.clone(),
+                        schema: table_info.schema.clone(),
+                        table: table_info.name.clone(),
+                    });
                 }
             }
 
@@ -257,6 +454,39 @@ pub async fn select_databases_and_tables(
                 .collect();
 
             if !tables_for_time_filter.is_empty() {
+                // Check that every table that will actually carry data has a usable
+                // replica identity before it's too late to fix - a table discovered
+                // to lack one mid-sync surfaces as a confusing apply error instead of
+                // an up-front choice.
+                let eligibility_client = postgres::connect(&db_url).await.context(format!(
+                    "Failed to connect to database '{}' for replication eligibility check",
+                    db_name
+                ))?;
+                let eligibility_tables: Vec<(String, String)> = tables_for_time_filter
+                    .iter()
+                    .map(|(original_idx, _)| {
+                        let table_info = &all_tables[*original_idx];
+                        (table_info.schema.clone(), table_info.name.clone())
+                    })
+                    .collect();
+                let issues = migration::check_replication_eligibility(
+                    &eligibility_client,
+                    &eligibility_tables,
+                )
+                .await
+                .context(format!(
+                    "Failed to check replication eligibility for database '{}'",
+                    db_name
+                ))?;
+                for issue in issues {
+                    eligibility_violations.push(EligibilityViolation {
+                        database: db_name.clone(),
+                        schema: issue.schema,
+                        table: issue.table,
+                        reason: issue.reason,
+                    });
+                }
+
                 let confirm_time_filters = Confirm::with_theme(&ColorfulTheme::default())
                     .with_prompt(format!(
                         "Configure time-based filters for tables in '{}'?",
@@ -309,7 +539,18 @@ pub async fn select_databases_and_tables(
                                 table_info.schema.clone(),
                                 table_info.name.clone(),
                             );
-                            table_rules.add_time_filter(qualified, column, window)?;
+                            table_rules.add_time_filter(
+                                qualified,
+                                column.clone(),
+                                window.clone(),
+                            )?;
+                            profile.time_filters.push(TimeFilterEntry {
+                                database: db_name.clone(),
+                                schema: table_info.schema.clone(),
+                                table: table_info.name.clone(),
+                                column,
+                                window,
+                            });
                         }
                     }
                 }
@@ -378,9 +619,62 @@ pub async fn select_databases_and_tables(
         println!();
     }
 
+    if !eligibility_violations.is_empty() {
+        println!(
+            "Tables not eligible for logical replication: {}",
+            eligibility_violations.len()
+        );
+        for violation in &eligibility_violations {
+            println!(
+                "  ⚠ {}.{}.{} {}",
+                violation.database, violation.schema, violation.table, violation.reason
+            );
+        }
+        println!();
+    }
+
     println!("========================================");
     println!();
 
+    if !eligibility_violations.is_empty() {
+        let demote = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Auto-demote these {} table(s) to schema-only, or abort?",
+                eligibility_violations.len()
+            ))
+            .default(true)
+            .interact()
+            .context("Failed to get eligibility demote confirmation")?;
+
+        if !demote {
+            tracing::warn!("⚠ User aborted due to replication eligibility issues");
+            anyhow::bail!(
+                "Aborted: {} table(s) are not eligible for logical replication",
+                eligibility_violations.len()
+            );
+        }
+
+        for violation in &eligibility_violations {
+            let qualified = QualifiedTable::new(
+                Some(violation.database.clone()),
+                violation.schema.clone(),
+                violation.table.clone(),
+            );
+            table_rules.add_schema_only_table(qualified)?;
+            profile.schema_only_tables.push(SchemaOnlyEntry {
+                database: violation.database.clone(),
+                schema: violation.schema.clone(),
+                table: violation.table.clone(),
+            });
+        }
+
+        tracing::info!(
+            "✓ Demoted {} table(s) to schema-only",
+            eligibility_violations.len()
+        );
+        tracing::info!("");
+    }
+
     let confirmed = Confirm::with_theme(&ColorfulTheme::default())
         .with_prompt("Proceed with this configuration?")
         .default(true)
@@ -396,6 +690,29 @@ pub async fn select_databases_and_tables(
     tracing::info!("✓ Configuration confirmed");
     tracing::info!("");
 
+    // Step 3a: Offer to save this configuration for non-interactive reuse
+    let save_for_reuse = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Save this configuration to a profile file for reuse?")
+        .default(false)
+        .interact()
+        .context("Failed to get profile save confirmation")?;
+
+    if save_for_reuse {
+        let path_input: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Profile path")
+            .default("migration-profile.toml".to_string())
+            .interact_text()
+            .context("Failed to get profile path")?;
+
+        save_profile(&PathBuf::from(&path_input), &profile)
+            .with_context(|| format!("Failed to save profile to '{}'", path_input))?;
+        tracing::info!("✓ Saved selection profile to '{}'", path_input);
+        tracing::info!(
+            "  Reuse it non-interactively with `select_databases_and_tables_from_profile`"
+        );
+        tracing::info!("");
+    }
+
     // Step 4: Convert selections to ReplicationFilter
     let filter = if excluded_tables.is_empty() {
         // No table exclusions - just filter by databases
@@ -408,6 +725,142 @@ pub async fn select_databases_and_tables(
     Ok((filter, table_rules))
 }
 
+/// Non-interactive counterpart to [`select_databases_and_tables`]: reconstruct the
+/// exact `(ReplicationFilter, TableRules)` recorded in a profile file saved by that
+/// function, without touching a TTY
+///
+/// Intended for CI runs and repeated migrations that have already had their selections
+/// vetted once interactively and checked into version control as a profile file.
+///
+/// # Errors
+///
+/// Returns an error if the profile file can't be read, isn't valid TOML, or encodes an
+/// invalid table rule.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use anyhow::Result;
+/// # use std::path::Path;
+/// # use postgres_seren_replicator::interactive::select_databases_and_tables_from_profile;
+/// # async fn example() -> Result<()> {
+/// let (filter, rules) = select_databases_and_tables_from_profile(Path::new("migration-profile.toml"))?;
+/// # let _ = (filter, rules);
+/// # Ok(())
+/// # }
+/// ```
+pub fn select_databases_and_tables_from_profile(
+    path: &std::path::Path,
+) -> Result<(ReplicationFilter, TableRules)> {
+    tracing::info!("Loading selection profile from '{}'...", path.display());
+    let (filter, rules) = crate::profile::load_profile(path)
+        .with_context(|| format!("Failed to load selection profile from '{}'", path.display()))?;
+    tracing::info!("✓ Loaded selection profile");
+    Ok((filter, rules))
+}
+
+/// Prompt for glob patterns and resolve them against `table_display_names`, returning
+/// the indices that should end up excluded
+///
+/// Patterns are applied either as an include-list ("replicate only matches") or an
+/// ignore-list ("replicate everything except matches") - the two are mutually
+/// exclusive, mirroring `--replication-tables`/`--replication-tables-ignore`. The
+/// expanded concrete set is shown back to the user for confirmation before it's used.
+fn resolve_table_patterns_to_exclusions(
+    db_name: &str,
+    table_display_names: &[String],
+) -> Result<Vec<usize>> {
+    let mode = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Apply patterns as an include-list or an ignore-list?")
+        .items(&[
+            "Include-list: replicate only tables matching a pattern",
+            "Ignore-list: replicate everything except tables matching a pattern",
+        ])
+        .default(1)
+        .interact()
+        .context("Failed to get include/ignore mode")?;
+    let is_include_list = mode == 0;
+
+    let patterns_input: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Comma-separated glob patterns (e.g. audit_*, public.logs_*)")
+        .interact_text()
+        .context("Failed to get glob patterns")?;
+
+    let patterns: Vec<String> = patterns_input
+        .split(',')
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    let matched_indices: std::collections::HashSet<usize> = table_display_names
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| patterns.iter().any(|pattern| glob_match(pattern, name)))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    let matched_names: Vec<&String> = table_display_names
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| matched_indices.contains(idx))
+        .map(|(_, name)| name)
+        .collect();
+
+    println!();
+    println!(
+        "{} pattern(s) matched {} table(s) in '{}':",
+        patterns.len(),
+        matched_names.len(),
+        db_name
+    );
+    for name in &matched_names {
+        println!("  - {}", name);
+    }
+    println!();
+
+    let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(if is_include_list {
+            "Replicate only these tables?"
+        } else {
+            "Exclude these tables?"
+        })
+        .default(true)
+        .interact()
+        .context("Failed to confirm pattern match")?;
+
+    if !confirmed {
+        anyhow::bail!("Table pattern selection cancelled by user");
+    }
+
+    let exclusions = if is_include_list {
+        (0..table_display_names.len())
+            .filter(|idx| !matched_indices.contains(idx))
+            .collect()
+    } else {
+        let mut indices: Vec<usize> = matched_indices.into_iter().collect();
+        indices.sort_unstable();
+        indices
+    };
+
+    Ok(exclusions)
+}
+
+/// Match `text` against a simple glob `pattern` where `*` matches any run of
+/// characters (including none) and every other character must match literally
+fn glob_match(pattern: &str, text: &str) -> bool {
+    fn matches(pattern: &[u8], text: &[u8]) -> bool {
+        match pattern.first() {
+            None => text.is_empty(),
+            Some(b'*') => {
+                matches(&pattern[1..], text) || (!text.is_empty() && matches(pattern, &text[1..]))
+            }
+            Some(&c) => !text.is_empty() && text[0] == c && matches(&pattern[1..], &text[1..]),
+        }
+    }
+
+    matches(pattern.as_bytes(), text.as_bytes())
+}
+
 /// Replace the database name in a PostgreSQL connection URL
 ///
 /// # Arguments
@@ -445,6 +898,31 @@ fn replace_database_in_url(url: &str, new_db_name: &str) -> Result<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_glob_match_wildcard_suffix() {
+        assert!(glob_match("audit_*", "audit_log"));
+        assert!(glob_match("audit_*", "audit_"));
+        assert!(!glob_match("audit_*", "other_audit_log"));
+    }
+
+    #[test]
+    fn test_glob_match_wildcard_across_schema_dot() {
+        assert!(glob_match("public.logs_*", "public.logs_2024"));
+        assert!(!glob_match("public.logs_*", "private.logs_2024"));
+    }
+
+    #[test]
+    fn test_glob_match_without_wildcard_requires_exact_match() {
+        assert!(glob_match("users", "users"));
+        assert!(!glob_match("users", "users_archive"));
+    }
+
+    #[test]
+    fn test_glob_match_leading_and_trailing_wildcards() {
+        assert!(glob_match("*_archive", "orders_archive"));
+        assert!(glob_match("*log*", "audit_log_2024"));
+    }
+
     #[test]
     fn test_replace_database_in_url() {
         // Basic URL
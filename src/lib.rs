@@ -1,6 +1,11 @@
 // ABOUTME: Library module for neon-seren-replicator
 // ABOUTME: Exports all core functionality for use in binary and tests
 
+// Each database backend lives behind its own Cargo feature so a consumer
+// that only needs, say, SQLite-to-Neon migration isn't forced to pull in
+// tokio-postgres, mysql_async, and a MongoDB driver. `postgres` and `sqlite`
+// are part of the default feature set, since they're this crate's primary
+// targets; `mongodb` and `mysql` are opt-in.
 pub mod checkpoint;
 pub mod commands;
 pub mod config;
@@ -8,16 +13,35 @@ pub mod filters;
 pub mod interactive;
 pub mod jsonb;
 pub mod migration;
+#[cfg(feature = "mongodb")]
+pub mod mongodb;
+#[cfg(feature = "mysql")]
+pub mod mysql;
+pub mod neon_http;
+#[cfg(feature = "postgres")]
 pub mod postgres;
+pub mod profile;
+pub mod queue;
 pub mod remote;
 pub mod replication;
+pub mod results;
+pub mod signals;
+pub mod source;
+#[cfg(feature = "sqlite")]
 pub mod sqlite;
+pub mod ssh_tunnel;
 pub mod table_rules;
+pub mod test_support;
 pub mod utils;
 
 use anyhow::{bail, Result};
 
 /// Database source types supported for replication
+///
+/// Every variant exists regardless of which backend features are compiled
+/// in, so a connection string can always be classified; [`detect_source_type`]
+/// is what turns "classified as X" into "X was not compiled into this build"
+/// when the matching feature is off.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SourceType {
     /// PostgreSQL database (postgresql:// or postgres:// URL)
@@ -26,7 +50,7 @@ pub enum SourceType {
     SQLite,
     /// MongoDB database (mongodb:// URL) - Future support
     MongoDB,
-    /// MySQL database (mysql:// URL) - Future support
+    /// MySQL database (mysql:// URL)
     MySQL,
 }
 
@@ -34,9 +58,18 @@ pub enum SourceType {
 ///
 /// Detection rules:
 /// - PostgreSQL: Starts with `postgresql://` or `postgres://`
-/// - SQLite: Ends with `.db`, `.sqlite`, or `.sqlite3`
-/// - MongoDB: Starts with `mongodb://` (future support)
-/// - MySQL: Starts with `mysql://` (future support)
+/// - SQLite: Ends with `.db`, `.sqlite`, or `.sqlite3`, or a `file:` URL
+/// - MongoDB: Starts with `mongodb://` or `mongodb+srv://`
+/// - MySQL: Starts with `mysql://`
+///
+/// Delegates the actual scheme/suffix classification to [`source::parse_source`],
+/// so a connection string is parsed into structured components exactly once
+/// regardless of whether the caller wants the full [`source::ParsedSource`] or
+/// just the [`SourceType`] this function returns. Classification always
+/// succeeds for a recognized scheme, even if the matching backend feature
+/// isn't compiled in - that's checked separately below, so the error tells
+/// the caller which feature to enable rather than implying the backend
+/// doesn't exist yet.
 ///
 /// # Arguments
 ///
@@ -44,7 +77,8 @@ pub enum SourceType {
 ///
 /// # Returns
 ///
-/// Detected source type or error if type cannot be determined
+/// Detected source type, or an error if the type can't be determined or its
+/// backend feature isn't compiled into this build
 ///
 /// # Examples
 ///
@@ -56,27 +90,24 @@ pub enum SourceType {
 /// assert!(detect_source_type("invalid").is_err());
 /// ```
 pub fn detect_source_type(source: &str) -> Result<SourceType> {
-    if source.starts_with("postgresql://") || source.starts_with("postgres://") {
-        Ok(SourceType::PostgreSQL)
-    } else if source.starts_with("mongodb://") {
-        // Future support
-        bail!("MongoDB sources are not yet supported. Coming in Phase 2.")
-    } else if source.starts_with("mysql://") {
-        // Future support
-        bail!("MySQL sources are not yet supported. Coming in Phase 3.")
-    } else if source.ends_with(".db") || source.ends_with(".sqlite") || source.ends_with(".sqlite3")
-    {
-        Ok(SourceType::SQLite)
-    } else {
-        bail!(
-            "Could not detect source database type from '{}'.\n\
-             Supported sources:\n\
-             - PostgreSQL: postgresql://... or postgres://...\n\
-             - SQLite: path ending with .db, .sqlite, or .sqlite3\n\
-             - MongoDB: (coming soon)\n\
-             - MySQL: (coming soon)",
-            source
-        )
+    match source::parse_source(source)?.kind {
+        #[cfg(not(feature = "postgres"))]
+        SourceType::PostgreSQL => {
+            bail!("PostgreSQL support was not compiled in; enable the `postgres` feature.")
+        }
+        #[cfg(not(feature = "sqlite"))]
+        SourceType::SQLite => {
+            bail!("SQLite support was not compiled in; enable the `sqlite` feature.")
+        }
+        #[cfg(not(feature = "mongodb"))]
+        SourceType::MongoDB => {
+            bail!("MongoDB support was not compiled in; enable the `mongodb` feature.")
+        }
+        #[cfg(not(feature = "mysql"))]
+        SourceType::MySQL => {
+            bail!("MySQL support was not compiled in; enable the `mysql` feature.")
+        }
+        kind => Ok(kind),
     }
 }
 
@@ -112,6 +143,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_sqlite_file_url() {
+        assert_eq!(
+            detect_source_type("file:///tmp/database.sqlite").unwrap(),
+            SourceType::SQLite
+        );
+    }
+
     #[test]
     fn test_detect_mongodb_not_supported() {
         let result = detect_source_type("mongodb://localhost/db");
@@ -119,7 +158,7 @@ mod tests {
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("not yet supported"));
+            .contains("not compiled in"));
     }
 
     #[test]
@@ -129,7 +168,7 @@ mod tests {
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("not yet supported"));
+            .contains("not compiled in"));
     }
 
     #[test]
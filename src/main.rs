@@ -1,6 +1,7 @@
 // ABOUTME: CLI entry point for postgres-seren-replicator
 // ABOUTME: Parses commands and routes to appropriate handlers
 
+use anyhow::Context;
 use clap::{Args, Parser, Subcommand};
 use postgres_seren_replicator::commands;
 
@@ -11,6 +12,92 @@ use postgres_seren_replicator::commands;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Log output format: human-readable text, or newline-delimited JSON
+    /// (bunyan-style: timestamp, level, span name/fields) for log aggregators
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Pretty)]
+    log_format: LogFormat,
+}
+
+/// Output format for the tracing subscriber, selected via `--log-format`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
+enum LogFormat {
+    /// Human-readable text, colorized when stdout is a terminal
+    #[default]
+    Pretty,
+    /// Newline-delimited JSON, one record per log line, for machine consumption
+    Json,
+}
+
+#[derive(Args, Clone, Default)]
+struct ConnectionParamArgs {
+    /// Extra libpq connection parameter applied only to the source connection
+    /// (key=value, repeatable - e.g. `--source-param application_name=seren-migrator`).
+    /// Keys with no dedicated libpq keyword (e.g. `statement_timeout`) are set as
+    /// session GUCs via `options=-c key=value`. `host`/`port`/`dbname`/`user`/
+    /// `password`/`replication` are rejected since they're already derived from `--source`.
+    #[arg(long = "source-param")]
+    source_params: Vec<String>,
+    /// Extra libpq connection parameter applied only to the target connection
+    /// (key=value, repeatable). Same rules as `--source-param`.
+    #[arg(long = "target-param")]
+    target_params: Vec<String>,
+}
+
+#[derive(Args, Clone, Default)]
+struct SshTunnelArgs {
+    /// Reach --source through an SSH tunnel to this bastion (user@host); the
+    /// tunnel forwards a local port to --source's own host/port for the
+    /// duration of the command
+    #[arg(long = "source-ssh-tunnel")]
+    source_ssh_tunnel: Option<String>,
+    /// Port sshd listens on at --source-ssh-tunnel's host (default: 22)
+    #[arg(long = "source-ssh-port")]
+    source_ssh_port: Option<u16>,
+    /// Private key for --source-ssh-tunnel, if not the default identity
+    #[arg(long = "source-ssh-identity-file")]
+    source_ssh_identity_file: Option<std::path::PathBuf>,
+    /// `-J` jump host for --source-ssh-tunnel, for a bastion only reachable
+    /// through another bastion
+    #[arg(long = "source-ssh-jump-host")]
+    source_ssh_jump_host: Option<String>,
+    /// Reach --target through an SSH tunnel to this bastion (user@host). Same
+    /// rules as --source-ssh-tunnel.
+    #[arg(long = "target-ssh-tunnel")]
+    target_ssh_tunnel: Option<String>,
+    /// Port sshd listens on at --target-ssh-tunnel's host (default: 22)
+    #[arg(long = "target-ssh-port")]
+    target_ssh_port: Option<u16>,
+    /// Private key for --target-ssh-tunnel, if not the default identity
+    #[arg(long = "target-ssh-identity-file")]
+    target_ssh_identity_file: Option<std::path::PathBuf>,
+    /// `-J` jump host for --target-ssh-tunnel
+    #[arg(long = "target-ssh-jump-host")]
+    target_ssh_jump_host: Option<String>,
+}
+
+impl SshTunnelArgs {
+    fn source_config(&self) -> Option<postgres_seren_replicator::ssh_tunnel::SshTunnelConfig> {
+        self.source_ssh_tunnel.clone().map(|user_host| {
+            postgres_seren_replicator::ssh_tunnel::SshTunnelConfig::new(
+                user_host,
+                self.source_ssh_port,
+                self.source_ssh_identity_file.clone(),
+                self.source_ssh_jump_host.clone(),
+            )
+        })
+    }
+
+    fn target_config(&self) -> Option<postgres_seren_replicator::ssh_tunnel::SshTunnelConfig> {
+        self.target_ssh_tunnel.clone().map(|user_host| {
+            postgres_seren_replicator::ssh_tunnel::SshTunnelConfig::new(
+                user_host,
+                self.target_ssh_port,
+                self.target_ssh_identity_file.clone(),
+                self.target_ssh_jump_host.clone(),
+            )
+        })
+    }
 }
 
 #[derive(Args, Clone, Default)]
@@ -52,6 +139,21 @@ enum Commands {
         /// Disable interactive mode (use CLI filter flags instead)
         #[arg(long)]
         no_interactive: bool,
+        /// Stream an NDJSON result record per check (plus a final summary) to
+        /// stdout, for CI pipelines
+        #[arg(long)]
+        emit_results: bool,
+        /// Output format for the validation report: human-readable log lines, or
+        /// a single pretty-printed JSON object covering every check
+        #[arg(long, value_enum, default_value_t)]
+        format: commands::ValidateReportFormat,
+        /// Dump/restore implementation to validate against: `cli` requires
+        /// pg_dump/pg_dumpall/psql on PATH, `native` uses a pure tokio-postgres
+        /// path with no client tools
+        #[arg(long, value_enum, default_value_t)]
+        backend: postgres_seren_replicator::migration::MigrationBackend,
+        #[command(flatten)]
+        connection_params: ConnectionParamArgs,
     },
     /// Initialize replication with snapshot copy of schema and data
     Init {
@@ -82,6 +184,20 @@ enum Commands {
         /// Drop existing databases on target before copying
         #[arg(long)]
         drop_existing: bool,
+        /// When an existing target database would be dropped via --drop-existing
+        /// (or an interactive confirmation), rename it to a timestamped sidecar
+        /// instead so it can be rolled back with a single `ALTER DATABASE ...
+        /// RENAME TO` if the restore fails. The sidecar is dropped once that
+        /// database's restore completes successfully
+        #[arg(long)]
+        snapshot_before_drop: bool,
+        /// When an existing target database would be dropped via --drop-existing (or an
+        /// interactive confirmation), reset only the schemas the active filter is
+        /// replicating into it instead of the whole database, leaving unrelated schemas
+        /// and the database itself untouched. Takes priority over --snapshot-before-drop
+        /// if both are given
+        #[arg(long)]
+        reset_schemas: bool,
         /// Disable automatic continuous replication setup after snapshot (default: false, meaning sync IS enabled)
         #[arg(long)]
         no_sync: bool,
@@ -101,6 +217,77 @@ enum Commands {
         /// Maximum job duration in seconds before timeout (default: 28800 = 8 hours)
         #[arg(long, default_value_t = 28800)]
         job_timeout: u64,
+        /// Resume a previous `--remote` job that was interrupted, continuing each
+        /// database from its last checkpointed WAL position instead of restarting
+        /// from scratch (only meaningful together with --remote)
+        #[arg(long)]
+        resume_from: Option<String>,
+        /// Parallel jobs for each database's data dump/restore (defaults to CPU count, capped at 8)
+        #[arg(long)]
+        jobs: Option<usize>,
+        /// Concurrent workers for copying predicate-filtered tables (tables with a
+        /// time-based replication filter, which stream via COPY since pg_dump can't
+        /// apply a row predicate). Defaults to the CPU count, capped at 8 (the same
+        /// default --jobs uses; a value passed to --jobs is not carried over).
+        #[arg(long)]
+        parallelism: Option<usize>,
+        /// Byte budget per filtered-copy worker batch when bin-packing tables (default: 512 MiB)
+        #[arg(long)]
+        batch_bytes: Option<i64>,
+        /// Compression for each database's data dump, as `<method>` or
+        /// `<method>:<level>` (method: gzip, lz4, zstd; default: gzip:9).
+        /// Falls back to gzip if the installed pg_dump predates method support (v16+)
+        #[arg(long)]
+        dump_compression: Option<String>,
+        /// Directory of versioned .sql migration files to apply to each database's
+        /// target after its data is restored (tracked in a _seren_migrations table)
+        #[arg(long)]
+        migrations_dir: Option<std::path::PathBuf>,
+        /// Restore each database's schema and data inside a single transaction, so a
+        /// mid-restore failure leaves the target untouched instead of half-populated
+        /// (forces data restore to a single job; implies --jobs=1)
+        #[arg(long)]
+        single_transaction: bool,
+        /// Stream changes committed on the source during the dump/restore window to
+        /// the target via a logical replication slot opened just before the data
+        /// dump, then wait for the target to drain them before tearing the slot
+        /// down - a near-zero-downtime alternative to the plain one-shot snapshot.
+        /// Tables without a replica identity (primary key, or REPLICA IDENTITY
+        /// FULL) won't replicate UPDATE/DELETE statements made during the window.
+        #[arg(long)]
+        cutover: bool,
+        /// Pipe each database's data dump directly from pg_dump into pg_restore
+        /// instead of staging it on local disk first, for environments where local
+        /// disk can't hold the full dataset. Custom format is a single, non-parallel
+        /// stream, so this forfeits the directory-format path's parallel jobs; not
+        /// compatible with --cutover, which needs the dump to finish before
+        /// subscribing so a consistent snapshot can be handed off
+        #[arg(long)]
+        stream: bool,
+        /// Time budget (seconds) for retrying a transient failure - a dropped
+        /// connection, a brief `57P03 cannot_connect_now`, a pooler hiccup - in any
+        /// dump/restore/connect step, with jittered exponential backoff before
+        /// giving up (default: 300 = 5 minutes)
+        #[arg(long)]
+        max_retry_elapsed: Option<u64>,
+        /// Replicate this many databases concurrently instead of one at a time, so a
+        /// slow database's dump/restore no longer blocks the rest (default: 4)
+        #[arg(long)]
+        max_parallel_databases: Option<usize>,
+        /// Skip the source/target compatibility check (server version, installed
+        /// extensions, roles) run right after database discovery and before any
+        /// database is touched
+        #[arg(long)]
+        skip_compat_check: bool,
+        /// Dump/restore implementation: `cli` requires pg_dump/pg_dumpall/psql on
+        /// PATH, `native` uses a pure tokio-postgres path with no client tools
+        /// (narrower DDL coverage - see `migration::native`)
+        #[arg(long, value_enum, default_value_t)]
+        backend: postgres_seren_replicator::migration::MigrationBackend,
+        #[command(flatten)]
+        connection_params: ConnectionParamArgs,
+        #[command(flatten)]
+        ssh_tunnel: SshTunnelArgs,
     },
     /// Set up continuous logical replication from source to target
     Sync {
@@ -128,9 +315,88 @@ enum Commands {
         /// Force recreate subscriptions even if they already exist
         #[arg(long)]
         force: bool,
+        /// Stay running after initial sync, polling status until Ctrl+C/SIGTERM
+        #[arg(long)]
+        watch: bool,
+        /// How often to poll subscription status while --watch is active (seconds)
+        #[arg(long, default_value_t = 30)]
+        watch_interval: u64,
+        /// Leave subscriptions/publications in place on --watch shutdown instead of dropping them
+        #[arg(long)]
+        no_teardown: bool,
+        /// Abort a database instead of warning if its source and target schema have
+        /// drifted apart (missing tables or mismatched columns), before the publication
+        /// is created
+        #[arg(long)]
+        require_schema_match: bool,
+        /// Set up replication for this many databases concurrently, instead of one at
+        /// a time; a slow database's initial sync no longer blocks the rest
+        #[arg(long)]
+        max_parallel_dbs: Option<usize>,
+        #[command(flatten)]
+        connection_params: ConnectionParamArgs,
     },
     /// Check replication status and lag in real-time
     Status {
+        /// Source connection URL; falls back to the config file's `[source]`
+        /// section if omitted
+        #[arg(long)]
+        source: Option<String>,
+        /// Target connection URL; falls back to the config file's `[target]`
+        /// section if omitted
+        #[arg(long)]
+        target: Option<String>,
+        /// Include only these databases (comma-separated); falls back to the
+        /// config file's `[filter]` section if omitted
+        #[arg(long, value_delimiter = ',')]
+        include_databases: Option<Vec<String>>,
+        /// Exclude these databases (comma-separated); falls back to the config
+        /// file's `[filter]` section if omitted
+        #[arg(long, value_delimiter = ',')]
+        exclude_databases: Option<Vec<String>>,
+        /// Stream an NDJSON result record per check (plus a final summary) to
+        /// stdout, for CI pipelines
+        #[arg(long)]
+        emit_results: bool,
+        /// Output format: human-readable text, a single pretty-printed JSON
+        /// bundle, or one compact JSON object per line (NDJSON) for dashboards
+        #[arg(long, value_enum, default_value_t = commands::StatusFormat::Human)]
+        format: commands::StatusFormat,
+        /// Keep polling and estimate a catch-up ETA per database from observed
+        /// replay throughput, exiting automatically once every database is
+        /// caught up, instead of checking once and exiting
+        #[arg(long)]
+        watch: bool,
+        /// How often to poll while --watch is active (seconds)
+        #[arg(long, default_value_t = 10)]
+        watch_interval: u64,
+        /// Maximum replay lag (milliseconds) a database may have and still
+        /// count as caught up
+        #[arg(long, default_value_t = 1000)]
+        max_lag_ms: i64,
+        /// Maximum write lag (milliseconds), in addition to --max-lag-ms; unset
+        /// means write lag isn't checked separately
+        #[arg(long)]
+        max_write_lag_ms: Option<i64>,
+        /// Maximum flush lag (milliseconds), in addition to --max-lag-ms; unset
+        /// means flush lag isn't checked separately
+        #[arg(long)]
+        max_flush_lag_ms: Option<i64>,
+        /// Base subscription name each database's replication is checked
+        /// against; falls back to the config file's `subscription_name_template`,
+        /// then to `"seren_migration_sub"`
+        #[arg(long)]
+        subscription_name_template: Option<String>,
+        /// Path to a migrator.toml config file providing defaults for --source,
+        /// --target, and the database/table filter flags above; CLI flags take
+        /// precedence over values in the file
+        #[arg(long = "config")]
+        config_path: Option<String>,
+        #[command(flatten)]
+        connection_params: ConnectionParamArgs,
+    },
+    /// Verify data integrity between source and target
+    Verify {
         #[arg(long)]
         source: String,
         #[arg(long)]
@@ -141,9 +407,42 @@ enum Commands {
         /// Exclude these databases (comma-separated)
         #[arg(long, value_delimiter = ',')]
         exclude_databases: Option<Vec<String>>,
+        /// Include only these tables (format: database.table, comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        include_tables: Option<Vec<String>>,
+        /// Exclude these tables (format: database.table, comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        exclude_tables: Option<Vec<String>>,
+        /// Concurrent table checks against a shared connection pool (default: 4)
+        #[arg(long, default_value_t = 4)]
+        jobs: usize,
+        /// Stream an NDJSON result record per table check (plus a final summary)
+        /// to stdout, for CI pipelines
+        #[arg(long)]
+        emit_results: bool,
+        /// Pin every source table to one exported snapshot, wait for the target's
+        /// subscription to catch up to it, then compare - avoids false-positive
+        /// mismatches from writes landing mid-comparison
+        #[arg(long)]
+        consistent: bool,
+        /// Subscription name to wait on in --consistent mode; falls back to
+        /// "seren_migration_sub" (suffixed with "_<database>" for multiple
+        /// databases, matching how 'sync' names subscriptions)
+        #[arg(long)]
+        subscription_name_template: Option<String>,
+        /// Emit a structured per-database/per-table report in addition to the
+        /// human log output (default: human output only)
+        #[arg(long, value_enum, default_value_t = commands::VerifyReportFormat::Human)]
+        report_format: commands::VerifyReportFormat,
+        /// Write the --report-format output to this file instead of stdout
+        #[arg(long)]
+        report_file: Option<std::path::PathBuf>,
+        #[command(flatten)]
+        connection_params: ConnectionParamArgs,
     },
-    /// Verify data integrity between source and target
-    Verify {
+    /// Drain the shared replication task queue; run several for a horizontally
+    /// scalable fleet instead of one long-lived process per run
+    Worker {
         #[arg(long)]
         source: String,
         #[arg(long)]
@@ -160,18 +459,91 @@ enum Commands {
         /// Exclude these tables (format: database.table, comma-separated)
         #[arg(long, value_delimiter = ',')]
         exclude_tables: Option<Vec<String>>,
+        /// Disable interactive mode (use CLI filter flags instead)
+        #[arg(long)]
+        no_interactive: bool,
+        #[command(flatten)]
+        table_rules: TableRuleArgs,
+        /// Identifier recorded on claimed tasks for observability (defaults to
+        /// `worker-<pid>`)
+        #[arg(long)]
+        worker_id: Option<String>,
+        /// Tasks claimed and executed concurrently within this process
+        #[arg(long, default_value_t = 2)]
+        concurrency: usize,
+        /// How often an idle worker slot checks for new work (seconds)
+        #[arg(long, default_value_t = 5)]
+        poll_interval: u64,
+        /// Attempts before a failing task is left in a `failed` state for good
+        #[arg(long, default_value_t = postgres_seren_replicator::queue::DEFAULT_MAX_ATTEMPTS)]
+        max_attempts: i32,
+        /// Keep polling for new work instead of exiting once the queue is drained
+        #[arg(long)]
+        follow: bool,
+        #[command(flatten)]
+        connection_params: ConnectionParamArgs,
+    },
+    /// Observe and control remote replication jobs submitted via `init --remote`
+    Jobs {
+        #[command(subcommand)]
+        action: JobsAction,
+        /// API endpoint for remote execution (defaults to Seren's API)
+        #[arg(
+            long,
+            global = true,
+            default_value_t = std::env::var("SEREN_REMOTE_API")
+                .unwrap_or_else(|_| "https://api.seren.cloud/replication".to_string())
+        )]
+        remote_api: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum JobsAction {
+    /// List remote replication jobs
+    List,
+    /// Show the current status of a remote job
+    Status {
+        /// Job ID returned by `init --remote` or `jobs list`
+        job_id: String,
+    },
+    /// Stream stdout/progress output from a remote job's worker
+    Logs {
+        /// Job ID returned by `init --remote` or `jobs list`
+        job_id: String,
+    },
+    /// Request cancellation of a running remote job
+    Cancel {
+        /// Job ID returned by `init --remote` or `jobs list`
+        job_id: String,
+    },
+    /// Re-attach to an already-submitted job and poll it until completion
+    Attach {
+        /// Job ID returned by `init --remote` or `jobs list`
+        job_id: String,
     },
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize logging - default to INFO level if RUST_LOG not set
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
-        )
-        .init();
+    let cli = Cli::parse();
+
+    // Initialize logging - default to INFO level if RUST_LOG not set.
+    // --log-format selects the subscriber: pretty text for a terminal, or
+    // newline-delimited JSON so a long init/sync run can be correlated in a
+    // log aggregator (RUST_LOG still controls verbosity in both formats).
+    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    match cli.log_format {
+        LogFormat::Json => tracing_subscriber::fmt()
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .with_env_filter(env_filter)
+            .init(),
+        LogFormat::Pretty => tracing_subscriber::fmt().with_env_filter(env_filter).init(),
+    }
 
     // Clean up stale temp directories from previous runs (older than 24 hours)
     // This handles temp files left behind by processes killed with SIGKILL
@@ -180,7 +552,10 @@ async fn main() -> anyhow::Result<()> {
         // Don't fail startup if cleanup fails
     }
 
-    let cli = Cli::parse();
+    // Catch SIGINT/SIGTERM so an interrupted run cleans up its temp
+    // directories and .pgpass files immediately instead of leaving them for
+    // the next startup's cleanup_stale_temp_dirs() above
+    postgres_seren_replicator::signals::install_signal_handlers();
 
     match cli.command {
         Commands::Validate {
@@ -191,7 +566,13 @@ async fn main() -> anyhow::Result<()> {
             include_tables,
             exclude_tables,
             no_interactive,
+            emit_results,
+            format,
+            backend,
+            connection_params,
         } => {
+            let source = resolve_connection_url(&source, &connection_params.source_params)?;
+            let target = resolve_connection_url(&target, &connection_params.target_params)?;
             let filter = if !no_interactive {
                 // Interactive mode (default) - prompt user to select databases and tables
                 let (filter, rules) =
@@ -207,7 +588,7 @@ async fn main() -> anyhow::Result<()> {
                     exclude_tables,
                 )?
             };
-            commands::validate(&source, &target, filter).await
+            commands::validate(&source, &target, filter, emit_results, format, backend).await
         }
         Commands::Init {
             source,
@@ -220,14 +601,110 @@ async fn main() -> anyhow::Result<()> {
             no_interactive,
             table_rules,
             drop_existing,
+            snapshot_before_drop,
+            reset_schemas,
             no_sync,
             no_resume,
             remote,
             remote_api,
             job_timeout,
+            resume_from,
+            jobs,
+            parallelism,
+            batch_bytes,
+            migrations_dir,
+            dump_compression,
+            single_transaction,
+            cutover,
+            stream,
+            max_retry_elapsed,
+            max_parallel_databases,
+            skip_compat_check,
+            backend,
+            connection_params,
+            ssh_tunnel,
         } => {
+            let source = resolve_connection_url(&source, &connection_params.source_params)?;
+            let target = resolve_connection_url(&target, &connection_params.target_params)?;
+
             // Remote execution path
             if remote {
+                if migrations_dir.is_some() {
+                    anyhow::bail!(
+                        "--migrations-dir is not yet supported with --remote; run without \
+                         --remote, or apply migrations separately once the remote job completes"
+                    );
+                }
+                if parallelism.is_some() || batch_bytes.is_some() {
+                    anyhow::bail!(
+                        "--parallelism/--batch-bytes are not supported with --remote; they tune \
+                         local COPY workers, which don't run when replication executes remotely"
+                    );
+                }
+                if single_transaction {
+                    anyhow::bail!(
+                        "--single-transaction is not yet supported with --remote; run without \
+                         --remote to restore atomically"
+                    );
+                }
+                if cutover {
+                    anyhow::bail!(
+                        "--cutover is not yet supported with --remote; run without --remote for \
+                         a near-zero-downtime migration"
+                    );
+                }
+                if stream {
+                    anyhow::bail!(
+                        "--stream is not yet supported with --remote; run without --remote to \
+                         stream the dump directly into pg_restore"
+                    );
+                }
+                if ssh_tunnel.source_ssh_tunnel.is_some() || ssh_tunnel.target_ssh_tunnel.is_some() {
+                    anyhow::bail!(
+                        "--source-ssh-tunnel/--target-ssh-tunnel are not supported with --remote; \
+                         they open a local SSH process, which has nothing to connect through \
+                         when replication executes remotely"
+                    );
+                }
+                if max_retry_elapsed.is_some() {
+                    anyhow::bail!(
+                        "--max-retry-elapsed is not supported with --remote; it tunes local retry \
+                         backoff, which doesn't apply when replication executes remotely"
+                    );
+                }
+                if snapshot_before_drop {
+                    anyhow::bail!(
+                        "--snapshot-before-drop is not supported with --remote; the sidecar \
+                         rename/cleanup it performs happens on the local connection, which \
+                         doesn't exist when replication executes remotely"
+                    );
+                }
+                if max_parallel_databases.is_some() {
+                    anyhow::bail!(
+                        "--max-parallel-databases is not supported with --remote; database \
+                         fan-out there is controlled by the remote job, not this process"
+                    );
+                }
+                if reset_schemas {
+                    anyhow::bail!(
+                        "--reset-schemas is not supported with --remote; the schema introspection \
+                         and reset it performs happens on the local connection, which doesn't \
+                         exist when replication executes remotely"
+                    );
+                }
+                if skip_compat_check {
+                    anyhow::bail!(
+                        "--skip-compat-check is not supported with --remote; the compatibility \
+                         check it skips runs on the local connection, which doesn't exist when \
+                         replication executes remotely"
+                    );
+                }
+                if backend.is_native() {
+                    anyhow::bail!(
+                        "--backend native is not supported with --remote; the remote job always \
+                         uses the CLI dump/restore path"
+                    );
+                }
                 return init_remote(
                     source,
                     target,
@@ -240,9 +717,25 @@ async fn main() -> anyhow::Result<()> {
                     no_sync,
                     remote_api,
                     job_timeout,
+                    resume_from,
                 )
                 .await;
             }
+            if resume_from.is_some() {
+                anyhow::bail!("--resume-from requires --remote");
+            }
+            if cutover && stream {
+                anyhow::bail!(
+                    "--cutover and --stream can't be combined; --cutover needs the dump to \
+                     finish before subscribing so it can hand off a consistent snapshot, which \
+                     --stream's pipe never produces"
+                );
+            }
+
+            let dump_compression = dump_compression
+                .as_deref()
+                .map(postgres_seren_replicator::migration::DumpCompression::parse)
+                .transpose()?;
 
             // Local execution path (existing code continues below)
             // Interactive mode is default unless --no-interactive or --yes is specified
@@ -271,8 +764,25 @@ async fn main() -> anyhow::Result<()> {
                 yes,
                 filter,
                 drop_existing,
+                snapshot_before_drop,
+                reset_schemas,
                 enable_sync,
                 !no_resume,
+                jobs,
+                parallelism,
+                batch_bytes,
+                migrations_dir,
+                single_transaction,
+                dump_compression,
+                cutover,
+                stream,
+                ssh_tunnel.source_config(),
+                ssh_tunnel.target_config(),
+                max_retry_elapsed.map(std::time::Duration::from_secs),
+                max_parallel_databases,
+                None, // Use the default terminal progress bar
+                skip_compat_check,
+                backend,
             )
             .await
         }
@@ -285,8 +795,16 @@ async fn main() -> anyhow::Result<()> {
             exclude_tables,
             no_interactive,
             table_rules,
-            force,
+            force: _force,
+            watch,
+            watch_interval,
+            no_teardown,
+            require_schema_match,
+            max_parallel_dbs,
+            connection_params,
         } => {
+            let source = resolve_connection_url(&source, &connection_params.source_params)?;
+            let target = resolve_connection_url(&target, &connection_params.target_params)?;
             let filter = if !no_interactive {
                 // Interactive mode (default) - prompt user to select databases and tables
                 let (filter, rules) =
@@ -304,21 +822,110 @@ async fn main() -> anyhow::Result<()> {
                 let table_rule_data = build_table_rules(&table_rules)?;
                 filter.with_table_rules(table_rule_data)
             };
-            commands::sync(&source, &target, Some(filter), None, None, None, force).await
+            if watch {
+                commands::watch(
+                    &source,
+                    &target,
+                    Some(filter),
+                    None,
+                    None,
+                    None,
+                    watch_interval,
+                    !no_teardown,
+                    require_schema_match,
+                    max_parallel_dbs,
+                )
+                .await
+            } else {
+                commands::sync(
+                    &source,
+                    &target,
+                    Some(filter),
+                    None,
+                    None,
+                    None,
+                    require_schema_match,
+                    max_parallel_dbs,
+                )
+                .await
+            }
         }
         Commands::Status {
             source,
             target,
             include_databases,
             exclude_databases,
+            emit_results,
+            format,
+            watch,
+            watch_interval,
+            max_lag_ms,
+            max_write_lag_ms,
+            max_flush_lag_ms,
+            subscription_name_template,
+            config_path,
+            connection_params,
         } => {
+            let file_config = config_path
+                .as_deref()
+                .map(|path| {
+                    postgres_seren_replicator::config::load_migrator_config(std::path::Path::new(path))
+                })
+                .transpose()?
+                .unwrap_or_default();
+
+            let source = source
+                .or(file_config.source.url)
+                .context("Source is required: pass --source or set [source].url in --config")?;
+            let target = target
+                .or(file_config.target.url)
+                .context("Target is required: pass --target or set [target].url in --config")?;
+            let source = resolve_connection_url(&source, &connection_params.source_params)?;
+            let target = resolve_connection_url(&target, &connection_params.target_params)?;
             let filter = postgres_seren_replicator::filters::ReplicationFilter::new(
-                include_databases,
-                exclude_databases,
+                include_databases.or(file_config.filter.include_databases),
+                exclude_databases.or(file_config.filter.exclude_databases),
                 None,
                 None,
             )?;
-            commands::status(&source, &target, Some(filter)).await
+            let sub_name_template = subscription_name_template
+                .or(file_config.subscription_name_template)
+                .unwrap_or_else(|| "seren_migration_sub".to_string());
+            if watch {
+                commands::watch_status(
+                    &source,
+                    &target,
+                    Some(filter),
+                    std::time::Duration::from_secs(watch_interval),
+                    format,
+                    &sub_name_template,
+                )
+                .await
+            } else {
+                let thresholds = commands::LagThresholds {
+                    max_lag_ms,
+                    max_write_lag_ms,
+                    max_flush_lag_ms,
+                };
+                let health = commands::status(
+                    &source,
+                    &target,
+                    Some(filter),
+                    emit_results,
+                    format,
+                    thresholds,
+                    &sub_name_template,
+                )
+                .await?;
+                // Exit with a distinct code per health verdict, so a deploy
+                // pipeline can gate a cutover on `status`'s exit code instead of
+                // scraping its output
+                std::process::exit(match health {
+                    commands::StatusHealth::Healthy => 0,
+                    commands::StatusHealth::Lagging => 1,
+                    commands::StatusHealth::NotActive => 2,
+                });
+            }
         }
         Commands::Verify {
             source,
@@ -327,14 +934,178 @@ async fn main() -> anyhow::Result<()> {
             exclude_databases,
             include_tables,
             exclude_tables,
+            jobs,
+            emit_results,
+            consistent,
+            subscription_name_template,
+            report_format,
+            report_file,
+            connection_params,
         } => {
+            let source = resolve_connection_url(&source, &connection_params.source_params)?;
+            let target = resolve_connection_url(&target, &connection_params.target_params)?;
             let filter = postgres_seren_replicator::filters::ReplicationFilter::new(
                 include_databases,
                 exclude_databases,
                 include_tables,
                 exclude_tables,
             )?;
-            commands::verify(&source, &target, Some(filter)).await
+            // Three distinct exit codes so a CI pipeline can branch reliably: 0
+            // for a clean match, 1 for confirmed mismatches, 2 for a connection
+            // or operational failure (instead of all three collapsing into
+            // whatever exit code anyhow's default `Err` handling picks)
+            match commands::verify(
+                &source,
+                &target,
+                Some(filter),
+                Some(jobs),
+                emit_results,
+                consistent,
+                subscription_name_template.as_deref(),
+                report_format,
+                report_file.as_deref(),
+            )
+            .await
+            {
+                Ok(outcome) => std::process::exit(match outcome {
+                    commands::VerifyOutcome::AllMatch => 0,
+                    commands::VerifyOutcome::MismatchesFound => 1,
+                }),
+                Err(e) => {
+                    tracing::error!("Verification failed: {:#}", e);
+                    std::process::exit(2);
+                }
+            }
+        }
+        Commands::Worker {
+            source,
+            target,
+            include_databases,
+            exclude_databases,
+            include_tables,
+            exclude_tables,
+            no_interactive,
+            table_rules,
+            worker_id,
+            concurrency,
+            poll_interval,
+            max_attempts,
+            follow,
+            connection_params,
+        } => {
+            let source = resolve_connection_url(&source, &connection_params.source_params)?;
+            let target = resolve_connection_url(&target, &connection_params.target_params)?;
+            let filter = if !no_interactive {
+                // Interactive mode (default) - prompt user to select databases and tables
+                let (filter, rules) =
+                    postgres_seren_replicator::interactive::select_databases_and_tables(&source)
+                        .await?;
+                filter.with_table_rules(rules)
+            } else {
+                // CLI mode - use provided filter arguments
+                let filter = postgres_seren_replicator::filters::ReplicationFilter::new(
+                    include_databases,
+                    exclude_databases,
+                    include_tables,
+                    exclude_tables,
+                )?;
+                let table_rule_data = build_table_rules(&table_rules)?;
+                filter.with_table_rules(table_rule_data)
+            };
+            let worker_id = worker_id.unwrap_or_else(|| format!("worker-{}", std::process::id()));
+            commands::worker(
+                &source,
+                &target,
+                Some(filter),
+                &worker_id,
+                concurrency,
+                poll_interval,
+                max_attempts,
+                follow,
+            )
+            .await
+        }
+        Commands::Jobs { action, remote_api } => jobs_command(action, remote_api).await,
+    }
+}
+
+/// Handle the `jobs` subcommand: list, inspect, stream logs for, cancel, or
+/// re-attach to a remote replication job submitted via `init --remote`
+async fn jobs_command(action: JobsAction, remote_api: String) -> anyhow::Result<()> {
+    use postgres_seren_replicator::remote::RemoteClient;
+
+    let client = RemoteClient::new(remote_api)?;
+
+    match action {
+        JobsAction::List => {
+            let jobs = client.list_jobs().await?;
+            if jobs.is_empty() {
+                println!("No remote jobs found");
+                return Ok(());
+            }
+            for job in jobs {
+                println!("{}  {}", job.job_id, job.status);
+            }
+            Ok(())
+        }
+        JobsAction::Status { job_id } => {
+            let status = client.get_job_status(&job_id).await?;
+            println!("Job ID: {}", status.job_id);
+            println!("Status: {}", status.status);
+            if let Some(progress) = &status.progress {
+                println!(
+                    "Progress: {}/{} databases{}",
+                    progress.databases_completed,
+                    progress.databases_total,
+                    progress
+                        .current_database
+                        .as_deref()
+                        .map(|db| format!(" (current: {})", db))
+                        .unwrap_or_default()
+                );
+                if !progress.checkpoints.is_empty() {
+                    println!("Checkpoints (resume points if interrupted):");
+                    for (database, lsn) in &progress.checkpoints {
+                        println!("  {}: {}", database, lsn);
+                    }
+                }
+            }
+            if let Some(error) = &status.error {
+                println!("Error: {}", error);
+            }
+            Ok(())
+        }
+        JobsAction::Logs { job_id } => {
+            let mut printed = 0usize;
+            loop {
+                let lines = client.get_job_logs(&job_id).await?;
+                for line in &lines[printed.min(lines.len())..] {
+                    println!("{}", line);
+                }
+                printed = lines.len();
+
+                let status = client.get_job_status(&job_id).await?;
+                if matches!(status.status.as_str(), "completed" | "failed") {
+                    // Fetch once more in case the worker appended its final
+                    // lines between the log fetch and status check above.
+                    let final_lines = client.get_job_logs(&job_id).await?;
+                    for line in &final_lines[printed.min(final_lines.len())..] {
+                        println!("{}", line);
+                    }
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            }
+            Ok(())
+        }
+        JobsAction::Cancel { job_id } => {
+            client.cancel_job(&job_id).await?;
+            println!("✓ Cancellation requested for job {}", job_id);
+            Ok(())
+        }
+        JobsAction::Attach { job_id } => {
+            println!("Attaching to job {}...", job_id);
+            poll_job_to_completion(&client, &job_id).await
         }
     }
 }
@@ -352,7 +1123,9 @@ async fn init_remote(
     no_sync: bool,
     remote_api: String,
     job_timeout: u64,
+    resume_from: Option<String>,
 ) -> anyhow::Result<()> {
+    use anyhow::Context;
     use postgres_seren_replicator::migration;
     use postgres_seren_replicator::postgres;
     use postgres_seren_replicator::remote::{FilterSpec, JobSpec, RemoteClient};
@@ -385,13 +1158,8 @@ async fn init_remote(
             0i64
         } else {
             // Estimate total size
-            let size_estimates = migration::estimate_database_sizes(
-                &source,
-                &source_client,
-                &databases,
-                &filter_for_sizing,
-            )
-            .await?;
+            let size_estimates =
+                migration::estimate_database_sizes(&source, &source_client, &databases).await?;
 
             let total_bytes: i64 = size_estimates.iter().map(|s| s.size_bytes).sum();
             println!(
@@ -432,6 +1200,30 @@ async fn init_remote(
         serde_json::Value::Number(serde_json::Number::from(job_timeout)),
     );
 
+    let client = RemoteClient::new(remote_api)?;
+
+    // A --resume-from job picks up each database from its last checkpointed WAL
+    // position (see JobSpec::resume_from) instead of restarting its snapshot/stream
+    // from scratch.
+    let resume_checkpoints = match &resume_from {
+        Some(job_id) => {
+            let status = client.get_job_status(job_id).await.with_context(|| {
+                format!("Failed to look up checkpoints for resumed job '{}'", job_id)
+            })?;
+            let checkpoints = status
+                .progress
+                .map(|progress| progress.checkpoints)
+                .unwrap_or_default();
+            println!(
+                "Resuming job '{}' from {} checkpointed database(s)",
+                job_id,
+                checkpoints.len()
+            );
+            Some(checkpoints)
+        }
+        None => None,
+    };
+
     let job_spec = JobSpec {
         version: "1".to_string(),
         command: "init".to_string(),
@@ -439,10 +1231,10 @@ async fn init_remote(
         target_url: target,
         filter,
         options,
+        resume_from: resume_checkpoints,
     };
 
     // Submit job
-    let client = RemoteClient::new(remote_api)?;
     println!("Submitting replication job...");
 
     let response = client.submit_job(&job_spec).await?;
@@ -450,9 +1242,19 @@ async fn init_remote(
     println!("Job ID: {}", response.job_id);
     println!("\nPolling for status...");
 
-    // Poll until complete
+    poll_job_to_completion(&client, &response.job_id).await
+}
+
+/// Poll a remote job until it reaches a terminal state, printing progress as
+/// it goes, and translate the final status into an `Ok`/`Err` result. Shared
+/// by `init --remote` (polling the job it just submitted) and `jobs attach`
+/// (re-attaching to a job submitted in a previous invocation).
+async fn poll_job_to_completion(
+    client: &postgres_seren_replicator::remote::RemoteClient,
+    job_id: &str,
+) -> anyhow::Result<()> {
     let final_status = client
-        .poll_until_complete(&response.job_id, |status| match status.status.as_str() {
+        .poll_until_complete(job_id, |status| match status.status.as_str() {
             "provisioning" => println!("Status: provisioning EC2 instance..."),
             "running" => {
                 if let Some(ref progress) = status.progress {
@@ -462,6 +1264,11 @@ async fn init_remote(
                         progress.databases_total,
                         progress.current_database.as_deref().unwrap_or("unknown")
                     );
+                    if let Some(db) = &progress.current_database {
+                        if let Some(lsn) = progress.checkpoints.get(db) {
+                            println!("  Checkpointed at {} (resume point if interrupted)", lsn);
+                        }
+                    }
                 } else {
                     println!("Status: running...");
                 }
@@ -470,7 +1277,6 @@ async fn init_remote(
         })
         .await?;
 
-    // Display result
     match final_status.status.as_str() {
         "completed" => {
             println!("\n‚úì Replication completed successfully");
@@ -502,3 +1308,12 @@ fn build_table_rules(
     rules.apply_time_filter_cli(&args.time_filters)?;
     Ok(rules)
 }
+
+/// Validate `--source-param`/`--target-param` entries and fold them into `url`'s
+/// query string, so every downstream connection (and `pg_dump`/`pg_restore` via
+/// [`postgres_seren_replicator::utils::PostgresUrlParts::to_pg_env_vars`]) picks
+/// them up without any further plumbing
+fn resolve_connection_url(url: &str, raw_params: &[String]) -> anyhow::Result<String> {
+    use postgres_seren_replicator::utils::{apply_connection_params, parse_connection_params};
+    apply_connection_params(url, &parse_connection_params(raw_params)?)
+}
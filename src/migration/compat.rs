@@ -0,0 +1,218 @@
+// ABOUTME: Pre-flight source/target compatibility checks for init
+// ABOUTME: Surfaces version, extension, and role mismatches before anything is mutated
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use tokio_postgres::Client;
+
+use super::extensions::NEON_SUPPORTED_EXTENSIONS;
+
+/// Whether a [`CompatibilityIssue`] should block `init` outright or just be
+/// surfaced for the user to judge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompatibilitySeverity {
+    /// Surfaced in the report but doesn't block proceeding
+    Warning,
+    /// Blocks `init` unless `--skip-compat-check` is passed
+    Blocking,
+}
+
+/// A single compatibility problem found between source and target by
+/// [`check_compatibility`]
+#[derive(Debug, Clone)]
+pub struct CompatibilityIssue {
+    pub severity: CompatibilitySeverity,
+    pub message: String,
+}
+
+/// Result of comparing a source and target cluster's server version, installed
+/// extensions, and roles ahead of [`crate::commands::init::init`] mutating anything,
+/// so an unsupported extension or a role `pg_restore` can't find surfaces as one
+/// actionable report instead of a confusing failure mid-restore.
+#[derive(Debug, Clone, Default)]
+pub struct CompatibilityReport {
+    pub issues: Vec<CompatibilityIssue>,
+}
+
+impl CompatibilityReport {
+    /// True if any issue in this report is [`CompatibilitySeverity::Blocking`]
+    pub fn has_blocking_issues(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == CompatibilitySeverity::Blocking)
+    }
+}
+
+/// Major version of the server behind `client` (e.g. `16` for `16.3`), read from
+/// `server_version_num` (`160003`) rather than `server_version`, since the latter's
+/// format varies across forks (`16.3`, `16.3 (Ubuntu ...)`, `16beta1`)
+async fn server_major_version(client: &Client) -> Result<i32> {
+    let row = client
+        .query_one("SHOW server_version_num", &[])
+        .await
+        .context("Failed to read server_version_num")?;
+    let version_num: String = row.get(0);
+    let version_num: i32 = version_num
+        .parse()
+        .context("server_version_num was not an integer")?;
+    Ok(version_num / 10000)
+}
+
+/// Names of every non-`plpgsql` extension installed on `client`'s current database
+async fn installed_extension_names(client: &Client) -> Result<HashSet<String>> {
+    let rows = client
+        .query(
+            "SELECT extname FROM pg_catalog.pg_extension WHERE extname != 'plpgsql'",
+            &[],
+        )
+        .await
+        .context("Failed to list installed extensions")?;
+    Ok(rows.iter().map(|row| row.get::<_, String>(0)).collect())
+}
+
+/// Names of every role visible to `client`
+async fn role_names(client: &Client) -> Result<HashSet<String>> {
+    let rows = client
+        .query("SELECT rolname FROM pg_catalog.pg_roles", &[])
+        .await
+        .context("Failed to list roles")?;
+    Ok(rows.iter().map(|row| row.get::<_, String>(0)).collect())
+}
+
+/// Compare `source_client` and `target_client`'s server version, installed
+/// extensions, and roles, returning a [`CompatibilityReport`] of what's mismatched.
+///
+/// Checks performed:
+/// - **Server version**: a target whose major version is older than the source's is
+///   flagged [`CompatibilitySeverity::Blocking`], since a dump taken with a newer
+///   `pg_dump` can use syntax or catalog features the older target's `pg_restore`
+///   doesn't understand. A newer target is only a [`CompatibilitySeverity::Warning`].
+/// - **Extensions**: any extension installed on the source that's neither already on
+///   the target nor in [`NEON_SUPPORTED_EXTENSIONS`] (and so can't be created by
+///   [`super::apply_extensions`] either) is [`CompatibilitySeverity::Blocking`], since
+///   schema objects depending on it will fail to restore.
+/// - **Roles**: any role listed in the source's `pg_roles` that doesn't exist on the
+///   target is [`CompatibilitySeverity::Warning`], whether or not it owns anything - `pg_restore --no-owner` is used
+///   elsewhere in this crate's schema restore, so a missing role doesn't block
+///   restoring the schema itself, but is still worth flagging since it means
+///   ownership/grants silently fell back to the connecting user.
+///
+/// Available disk space on the target isn't checked: Postgres has no portable
+/// SQL-level "free bytes on this filesystem" query, and managed Postgres (including
+/// Neon) typically blocks the few functions that touch the filesystem directly for an
+/// unprivileged role.
+///
+/// # Errors
+///
+/// Returns an error if any of the underlying catalog queries against either
+/// `source_client` or `target_client` fail.
+pub async fn check_compatibility(
+    source_client: &Client,
+    target_client: &Client,
+) -> Result<CompatibilityReport> {
+    let mut issues = Vec::new();
+
+    let source_version = server_major_version(source_client)
+        .await
+        .context("Failed to read source server version")?;
+    let target_version = server_major_version(target_client)
+        .await
+        .context("Failed to read target server version")?;
+
+    match target_version.cmp(&source_version) {
+        std::cmp::Ordering::Less => issues.push(CompatibilityIssue {
+            severity: CompatibilitySeverity::Blocking,
+            message: format!(
+                "Target server (v{}) is older than source (v{}); a dump taken from \
+                 the newer source may not restore cleanly onto the older target",
+                target_version, source_version
+            ),
+        }),
+        std::cmp::Ordering::Greater => issues.push(CompatibilityIssue {
+            severity: CompatibilitySeverity::Warning,
+            message: format!(
+                "Target server (v{}) is newer than source (v{}); this is a supported \
+                 upgrade path but worth confirming intentional",
+                target_version, source_version
+            ),
+        }),
+        std::cmp::Ordering::Equal => {}
+    }
+
+    let source_extensions = installed_extension_names(source_client)
+        .await
+        .context("Failed to list source extensions")?;
+    let target_extensions = installed_extension_names(target_client)
+        .await
+        .context("Failed to list target extensions")?;
+    let mut missing_extensions: Vec<&String> = source_extensions
+        .difference(&target_extensions)
+        .filter(|name| !NEON_SUPPORTED_EXTENSIONS.contains(&name.as_str()))
+        .collect();
+    missing_extensions.sort();
+    for name in missing_extensions {
+        issues.push(CompatibilityIssue {
+            severity: CompatibilitySeverity::Blocking,
+            message: format!(
+                "Extension '{}' is installed on the source, missing on the target, \
+                 and has no Neon-supported equivalent; schema objects depending on \
+                 it will fail to restore",
+                name
+            ),
+        });
+    }
+
+    let source_roles = role_names(source_client)
+        .await
+        .context("Failed to list source roles")?;
+    let target_roles = role_names(target_client)
+        .await
+        .context("Failed to list target roles")?;
+    let mut missing_roles: Vec<&String> = source_roles.difference(&target_roles).collect();
+    missing_roles.sort();
+    for name in missing_roles {
+        issues.push(CompatibilityIssue {
+            severity: CompatibilitySeverity::Warning,
+            message: format!(
+                "Role '{}' exists on the source but not the target; ownership and \
+                 grants referencing it will fall back to the connecting role",
+                name
+            ),
+        });
+    }
+
+    Ok(CompatibilityReport { issues })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_blocking_issues_false_when_empty() {
+        let report = CompatibilityReport::default();
+        assert!(!report.has_blocking_issues());
+    }
+
+    #[test]
+    fn test_has_blocking_issues_true_with_blocking_issue() {
+        let report = CompatibilityReport {
+            issues: vec![CompatibilityIssue {
+                severity: CompatibilitySeverity::Blocking,
+                message: "test".to_string(),
+            }],
+        };
+        assert!(report.has_blocking_issues());
+    }
+
+    #[test]
+    fn test_has_blocking_issues_false_with_only_warnings() {
+        let report = CompatibilityReport {
+            issues: vec![CompatibilityIssue {
+                severity: CompatibilitySeverity::Warning,
+                message: "test".to_string(),
+            }],
+        };
+        assert!(!report.has_blocking_issues());
+    }
+}
@@ -0,0 +1,233 @@
+// ABOUTME: Near-zero-downtime cutover via logical replication after the bulk dump/restore
+// ABOUTME: Opens a replication slot before dump_data so no change is missed or double-applied
+
+use crate::filters::ReplicationFilter;
+use crate::replication::{
+    create_publication, create_subscription, drop_publication, drop_subscription,
+    wait_for_catchup, wait_for_sync, SubscriptionOptions,
+};
+use anyhow::{Context, Result};
+use tokio_postgres::Client;
+
+/// A logical replication slot opened on the source before the bulk dump, holding
+/// a snapshot exported at the slot's starting position
+///
+/// Handing [`snapshot_name`](Self::snapshot_name) to `pg_dump --snapshot=<name>`
+/// makes the dump's view of the data consistent with exactly what the slot starts
+/// streaming changes *after* - nothing committed before the dump's snapshot is
+/// missed, and nothing the dump already captured is replayed again. Without this,
+/// a subscription started only after restore finishes would miss every change
+/// committed on the source during the (potentially long) dump/restore window.
+pub struct CutoverSlot {
+    pub slot_name: String,
+    pub snapshot_name: String,
+}
+
+/// Open a logical replication slot on `source_client` and export its snapshot
+///
+/// `source_client`'s transaction is left open on return; nothing else may run on
+/// this connection until [`close_cutover_slot`] commits it; the exported snapshot
+/// name becomes invalid the moment the transaction ends. Hand `snapshot_name` to
+/// `dump_data`'s `snapshot_name` argument before calling [`close_cutover_slot`].
+///
+/// # Errors
+///
+/// Returns an error if the snapshot transaction can't be started, or if slot
+/// creation or snapshot export fails - most commonly because the source isn't
+/// configured with `wal_level = logical`, or the connecting role lacks the
+/// `REPLICATION` privilege.
+pub async fn open_cutover_slot(source_client: &Client, slot_name: &str) -> Result<CutoverSlot> {
+    source_client
+        .batch_execute("BEGIN ISOLATION LEVEL REPEATABLE READ")
+        .await
+        .context("Failed to start snapshot transaction for cutover slot")?;
+
+    let row = source_client
+        .query_one(
+            "SELECT slot_name FROM pg_create_logical_replication_slot($1, 'pgoutput')",
+            &[&slot_name],
+        )
+        .await
+        .context("Failed to create logical replication slot for cutover")?;
+    let created_slot_name: String = row.get(0);
+
+    let row = source_client
+        .query_one("SELECT pg_export_snapshot()", &[])
+        .await
+        .context("Failed to export snapshot for cutover slot")?;
+    let snapshot_name: String = row.get(0);
+
+    tracing::info!(
+        "✓ Opened cutover slot '{}' with snapshot '{}'",
+        created_slot_name,
+        snapshot_name
+    );
+
+    Ok(CutoverSlot {
+        slot_name: created_slot_name,
+        snapshot_name,
+    })
+}
+
+/// Release the snapshot transaction opened by [`open_cutover_slot`]
+///
+/// Call this as soon as `dump_data` returns. The slot itself keeps accumulating
+/// WAL independently of this transaction, so committing here doesn't affect it -
+/// only the exported snapshot, which is no longer needed once the dump is done.
+pub async fn close_cutover_slot(source_client: &Client) -> Result<()> {
+    source_client
+        .batch_execute("COMMIT")
+        .await
+        .context("Failed to commit cutover snapshot transaction")
+}
+
+/// Attach the target to the already-accumulating cutover slot, skipping the
+/// initial table copy a plain subscription would otherwise perform - the
+/// dump/restore already seeded that data, consistent with the slot's start
+/// position, so copying it again would be redundant (and for non-INSERT
+/// statements, which logical decoding requires a replica identity to apply,
+/// create duplicate rows).
+///
+/// Tables replicated this way need a replica identity (`REPLICA IDENTITY
+/// DEFAULT` via a primary key, or `FULL`) for `UPDATE`/`DELETE` statements to
+/// replicate; a table with neither raises a `pg_subscription_rel` error that
+/// only surfaces once a matching write actually happens on the source.
+///
+/// # Errors
+///
+/// Returns an error if publication or subscription creation fails, e.g. because
+/// `filter` excludes every table in `db_name`.
+pub async fn start_streaming(
+    source_client: &Client,
+    target_client: &Client,
+    db_name: &str,
+    source_connection_string: &str,
+    publication_name: &str,
+    subscription_name: &str,
+    slot: &CutoverSlot,
+    filter: &ReplicationFilter,
+) -> Result<()> {
+    create_publication(source_client, db_name, publication_name, filter).await?;
+
+    let options = SubscriptionOptions {
+        copy_data: false,
+        create_slot: false,
+        slot_name: Some(slot.slot_name.clone()),
+        ..SubscriptionOptions::default()
+    };
+
+    create_subscription(
+        target_client,
+        subscription_name,
+        source_connection_string,
+        publication_name,
+        &options,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Wait for the target to fully drain the changes accumulated during the dump,
+/// then drop the subscription and publication, completing the cutover
+///
+/// First waits for the subscription's initial per-table sync to finish (cheap,
+/// since `copy_data = false` means there's no data to copy), then for the
+/// target to flush every change committed on the source as of the moment this
+/// is called - call it only once writes to the source have actually stopped,
+/// so "caught up" means "caught up for good" rather than a moving target.
+///
+/// `DROP SUBSCRIPTION` already drops the replication slot it's attached to on
+/// the source by default, so there's no separate slot cleanup step here even
+/// though [`start_streaming`] attached to a slot it didn't create.
+///
+/// # Errors
+///
+/// Returns an error if the subscription never finishes its initial sync, never
+/// catches up within `timeout_secs`, or if tearing down the subscription or
+/// publication fails.
+pub async fn wait_and_cutover(
+    source_client: &Client,
+    target_client: &Client,
+    subscription_name: &str,
+    publication_name: &str,
+    timeout_secs: u64,
+) -> Result<()> {
+    wait_for_sync(target_client, subscription_name, timeout_secs)
+        .await
+        .context("Subscription never completed initial sync")?;
+
+    wait_for_catchup(
+        source_client,
+        target_client,
+        subscription_name,
+        timeout_secs,
+    )
+    .await
+    .context("Subscription never caught up to the cutover point")?;
+
+    drop_subscription(target_client, subscription_name)
+        .await
+        .context("Failed to drop subscription during cutover")?;
+    drop_publication(source_client, publication_name)
+        .await
+        .context("Failed to drop publication during cutover")?;
+
+    tracing::info!(
+        "✓ Cutover complete: '{}' drained and torn down",
+        subscription_name
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::postgres::connect;
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_full_cutover_cycle() {
+        // This test requires two databases: source and target, with
+        // wal_level = logical on the source and a REPLICATION-privileged role.
+        let source_url = std::env::var("TEST_SOURCE_URL").unwrap();
+        let target_url = std::env::var("TEST_TARGET_URL").unwrap();
+
+        let slot_name = "test_cutover_slot";
+        let pub_name = "test_cutover_pub";
+        let sub_name = "test_cutover_sub";
+
+        let source_client = connect(&source_url).await.unwrap();
+        let slot = open_cutover_slot(&source_client, slot_name).await.unwrap();
+        assert!(!slot.snapshot_name.is_empty());
+
+        // In a real migration, dump_data(..., Some(&slot.snapshot_name), ...)
+        // and restore_data run here while the slot accumulates WAL.
+        close_cutover_slot(&source_client).await.unwrap();
+
+        let target_client = connect(&target_url).await.unwrap();
+        let filter = crate::filters::ReplicationFilter::new(
+            Some(vec!["postgres".to_string()]),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        start_streaming(
+            &source_client,
+            &target_client,
+            "postgres",
+            &source_url,
+            pub_name,
+            sub_name,
+            &slot,
+            &filter,
+        )
+        .await
+        .unwrap();
+
+        let result = wait_and_cutover(&source_client, &target_client, sub_name, pub_name, 60).await;
+
+        assert!(result.is_ok(), "Cutover failed: {:?}", result);
+    }
+}
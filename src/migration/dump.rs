@@ -2,12 +2,135 @@
 // ABOUTME: Handles global objects, schema, and data export
 
 use crate::filters::ReplicationFilter;
+use crate::ssh_tunnel::SshTunnelConfig;
 use anyhow::{bail, Context, Result};
 use std::collections::BTreeSet;
 use std::process::{Command, Stdio};
 
+/// A `pg_dump --compress` method
+///
+/// `Lz4` and `Zstd` require pg_dump 16+; `dump_data` falls back to
+/// [`CompressionMethod::Gzip`] on an older client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionMethod {
+    Gzip,
+    Lz4,
+    Zstd,
+}
+
+impl CompressionMethod {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Lz4 => "lz4",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+/// Compression method and level for `dump_data`'s directory-format output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DumpCompression {
+    pub method: CompressionMethod,
+    pub level: Option<u8>,
+}
+
+impl Default for DumpCompression {
+    /// The previous hardcoded behavior: gzip at level 9
+    fn default() -> Self {
+        Self {
+            method: CompressionMethod::Gzip,
+            level: Some(9),
+        }
+    }
+}
+
+impl DumpCompression {
+    /// Parse a `--dump-compression` value, e.g. `zstd:3`, `lz4`, or `gzip:9`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the method isn't `gzip`, `lz4`, or `zstd`, or if
+    /// the optional `:<level>` suffix isn't a valid number.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (method_str, level_str) = match spec.split_once(':') {
+            Some((method, level)) => (method, Some(level)),
+            None => (spec, None),
+        };
+
+        let method = match method_str {
+            "gzip" => CompressionMethod::Gzip,
+            "lz4" => CompressionMethod::Lz4,
+            "zstd" => CompressionMethod::Zstd,
+            other => bail!(
+                "Unknown compression method '{}'; expected one of: gzip, lz4, zstd",
+                other
+            ),
+        };
+
+        let level = level_str
+            .map(|level| {
+                level
+                    .parse::<u8>()
+                    .with_context(|| format!("Invalid compression level '{}'", level))
+            })
+            .transpose()?;
+
+        Ok(Self { method, level })
+    }
+
+    /// The `--compress=<value>` argument for `pg_dump`, falling back to
+    /// [`CompressionMethod::Gzip`] when `pg_dump`'s detected version doesn't
+    /// support `--compress=<method>:<level>` syntax (added in pg_dump 16; older
+    /// versions only accept a bare numeric gzip level). The fallback is that bare
+    /// level: the requested one for gzip, otherwise gzip's default of 6, with a
+    /// warning that lz4/zstd was replaced.
+    fn resolve_compress_arg(self) -> String {
+        if !pg_dump_supports_method() {
+            if self.method != CompressionMethod::Gzip {
+                tracing::warn!(
+                    "⚠ Installed pg_dump does not support --compress=<method>:<level> (needs v16+); \
+                     falling back to gzip instead of {}",
+                    self.method.as_str()
+                );
+            }
+            let gzip_level = self.level.filter(|_| self.method == CompressionMethod::Gzip);
+            return format!("--compress={}", gzip_level.unwrap_or(6));
+        }
+
+        match self.level {
+            Some(level) => format!("--compress={}:{}", self.method.as_str(), level),
+            None => format!("--compress={}", self.method.as_str()),
+        }
+    }
+}
+
+/// Whether the installed `pg_dump` is new enough (16+) to accept
+/// `--compress=<method>:<level>` rather than just a bare gzip level
+fn pg_dump_supports_method() -> bool {
+    crate::utils::detect_tool_version("pg_dump")
+        .and_then(|version| pg_dump_major_version(&version))
+        .is_some_and(|major| major >= 16)
+}
+
+/// Extract the major version number from a `pg_dump --version` first line,
+/// e.g. "pg_dump (PostgreSQL) 16.2" -> Some(16)
+fn pg_dump_major_version(version: &str) -> Option<u32> {
+    version
+        .split_whitespace()
+        .find_map(|token| token.split('.').next()?.parse::<u32>().ok())
+}
+
 /// Dump global objects (roles, tablespaces) using pg_dumpall
-pub async fn dump_globals(source_url: &str, output_path: &str) -> Result<()> {
+///
+/// When `ssh_tunnel` is set, the connection is routed through an `ssh -L`
+/// tunnel to `source_url`'s host instead of connecting to it directly (see
+/// [`crate::ssh_tunnel`]).
+pub async fn dump_globals(
+    source_url: &str,
+    output_path: &str,
+    ssh_tunnel: Option<&SshTunnelConfig>,
+) -> Result<()> {
     tracing::info!("Dumping global objects to {}", output_path);
 
     // Parse URL and create .pgpass file for secure authentication
@@ -15,18 +138,20 @@ pub async fn dump_globals(source_url: &str, output_path: &str) -> Result<()> {
         .with_context(|| format!("Failed to parse source URL: {}", source_url))?;
     let pgpass = crate::utils::PgPassFile::new(&parts)
         .context("Failed to create .pgpass file for authentication")?;
+    let (host, port, _tunnel_guard) = crate::ssh_tunnel::resolve_connect_target(ssh_tunnel, &parts)?;
 
     let mut cmd = Command::new("pg_dumpall");
     cmd.arg("--globals-only")
         .arg("--no-role-passwords") // Don't dump passwords
         .arg("--host")
-        .arg(&parts.host)
+        .arg(&host)
         .arg("--port")
-        .arg(parts.port.to_string())
+        .arg(port.to_string())
         .arg("--dbname")
         .arg(&parts.database)
         .arg(format!("--file={}", output_path))
         .env("PGPASSFILE", pgpass.path())
+        .envs(parts.to_pg_env_vars())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit());
 
@@ -60,11 +185,16 @@ pub async fn dump_globals(source_url: &str, output_path: &str) -> Result<()> {
 }
 
 /// Dump schema (DDL) for a specific database
+///
+/// When `ssh_tunnel` is set, the connection is routed through an `ssh -L`
+/// tunnel instead of connecting to `source_url`'s host directly (see
+/// [`crate::ssh_tunnel`]).
 pub async fn dump_schema(
     source_url: &str,
     database: &str,
     output_path: &str,
     filter: &ReplicationFilter,
+    ssh_tunnel: Option<&SshTunnelConfig>,
 ) -> Result<()> {
     tracing::info!(
         "Dumping schema for database '{}' to {}",
@@ -77,6 +207,7 @@ pub async fn dump_schema(
         .with_context(|| format!("Failed to parse source URL: {}", source_url))?;
     let pgpass = crate::utils::PgPassFile::new(&parts)
         .context("Failed to create .pgpass file for authentication")?;
+    let (host, port, _tunnel_guard) = crate::ssh_tunnel::resolve_connect_target(ssh_tunnel, &parts)?;
 
     let mut cmd = Command::new("pg_dump");
     cmd.arg("--schema-only")
@@ -102,13 +233,14 @@ pub async fn dump_schema(
     }
 
     cmd.arg("--host")
-        .arg(&parts.host)
+        .arg(&host)
         .arg("--port")
-        .arg(parts.port.to_string())
+        .arg(port.to_string())
         .arg("--dbname")
         .arg(&parts.database)
         .arg(format!("--file={}", output_path))
         .env("PGPASSFILE", pgpass.path())
+        .envs(parts.to_pg_env_vars())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit());
 
@@ -146,27 +278,48 @@ pub async fn dump_schema(
 ///
 /// Uses PostgreSQL directory format dump with:
 /// - Parallel dumps for faster performance
-/// - Maximum compression (level 9)
+/// - Configurable compression (defaults to gzip level 9, see [`DumpCompression`])
 /// - Large object (blob) support
 /// - Directory output for efficient parallel restore
 ///
-/// The number of parallel jobs is automatically determined based on available CPU cores.
+/// The number of parallel jobs defaults to the available CPU cores (capped at 8)
+/// unless `jobs` overrides it, e.g. via the `init --jobs` flag. `compression`
+/// defaults to gzip level 9 (the previous hardcoded behavior) when `None`,
+/// e.g. via the `init --dump-compression` flag.
+///
+/// When `snapshot_name` is set, it's passed as `pg_dump --snapshot=<name>` so
+/// the dump reads a specific already-exported snapshot instead of taking its
+/// own at start time - see [`crate::migration::cutover::open_cutover_slot`],
+/// which exports one consistent with a replication slot's starting position
+/// so no change is missed or double-applied when streaming replication picks
+/// up where this dump left off.
+///
+/// When `ssh_tunnel` is set, the connection is routed through an `ssh -L`
+/// tunnel instead of connecting to `source_url`'s host directly (see
+/// [`crate::ssh_tunnel`]).
 pub async fn dump_data(
     source_url: &str,
     database: &str,
     output_path: &str,
     filter: &ReplicationFilter,
+    jobs: Option<usize>,
+    compression: Option<DumpCompression>,
+    snapshot_name: Option<&str>,
+    ssh_tunnel: Option<&SshTunnelConfig>,
 ) -> Result<()> {
-    // Determine optimal number of parallel jobs (number of CPUs, capped at 8)
-    let num_cpus = std::thread::available_parallelism()
-        .map(|n| n.get().min(8))
-        .unwrap_or(4);
+    let num_cpus = jobs.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get().min(8))
+            .unwrap_or(4)
+    });
+    let compress_arg = compression.unwrap_or_default().resolve_compress_arg();
 
     tracing::info!(
-        "Dumping data for database '{}' to {} (parallel={}, compression=9, format=directory)",
+        "Dumping data for database '{}' to {} (parallel={}, {}, format=directory)",
         database,
         output_path,
-        num_cpus
+        num_cpus,
+        compress_arg
     );
 
     // Parse URL and create .pgpass file for secure authentication
@@ -174,15 +327,20 @@ pub async fn dump_data(
         .with_context(|| format!("Failed to parse source URL: {}", source_url))?;
     let pgpass = crate::utils::PgPassFile::new(&parts)
         .context("Failed to create .pgpass file for authentication")?;
+    let (host, port, _tunnel_guard) = crate::ssh_tunnel::resolve_connect_target(ssh_tunnel, &parts)?;
 
     let mut cmd = Command::new("pg_dump");
     cmd.arg("--data-only")
         .arg("--no-owner")
         .arg("--format=directory") // Directory format enables parallel operations
         .arg("--blobs") // Include large objects (blobs)
-        .arg("--compress=9") // Maximum compression for smaller dump size
+        .arg(&compress_arg)
         .arg(format!("--jobs={}", num_cpus)); // Parallel dump jobs
 
+    if let Some(snapshot_name) = snapshot_name {
+        cmd.arg(format!("--snapshot={}", snapshot_name));
+    }
+
     // Add table filtering if specified
     if let Some(exclude_tables) = get_excluded_tables_for_db(filter, database) {
         if !exclude_tables.is_empty() {
@@ -202,13 +360,14 @@ pub async fn dump_data(
     }
 
     cmd.arg("--host")
-        .arg(&parts.host)
+        .arg(&host)
         .arg("--port")
-        .arg(parts.port.to_string())
+        .arg(port.to_string())
         .arg("--dbname")
         .arg(&parts.database)
         .arg(format!("--file={}", output_path))
         .env("PGPASSFILE", pgpass.path())
+        .envs(parts.to_pg_env_vars())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit());
 
@@ -249,7 +408,10 @@ pub async fn dump_data(
 
 /// Extract table names for a specific database from exclude_tables filter
 /// Returns schema-qualified names in format: "schema"."table"
-fn get_excluded_tables_for_db(filter: &ReplicationFilter, db_name: &str) -> Option<Vec<String>> {
+pub(super) fn get_excluded_tables_for_db(
+    filter: &ReplicationFilter,
+    db_name: &str,
+) -> Option<Vec<String>> {
     let mut tables = BTreeSet::new();
 
     // Handle explicit exclude_tables (format: "database.table")
@@ -282,7 +444,10 @@ fn get_excluded_tables_for_db(filter: &ReplicationFilter, db_name: &str) -> Opti
 
 /// Extract table names for a specific database from include_tables filter
 /// Returns schema-qualified names in format: "schema"."table"
-fn get_included_tables_for_db(filter: &ReplicationFilter, db_name: &str) -> Option<Vec<String>> {
+pub(super) fn get_included_tables_for_db(
+    filter: &ReplicationFilter,
+    db_name: &str,
+) -> Option<Vec<String>> {
     filter.include_tables().map(|tables| {
         tables
             .iter()
@@ -311,7 +476,7 @@ mod tests {
         let dir = tempdir().unwrap();
         let output = dir.path().join("globals.sql");
 
-        let result = dump_globals(&url, output.to_str().unwrap()).await;
+        let result = dump_globals(&url, output.to_str().unwrap(), None).await;
 
         assert!(result.is_ok());
         assert!(output.exists());
@@ -332,7 +497,7 @@ mod tests {
         let db = url.split('/').next_back().unwrap_or("postgres");
 
         let filter = crate::filters::ReplicationFilter::empty();
-        let result = dump_schema(&url, db, output.to_str().unwrap(), &filter).await;
+        let result = dump_schema(&url, db, output.to_str().unwrap(), &filter, None).await;
 
         assert!(result.is_ok());
         assert!(output.exists());
@@ -407,4 +572,42 @@ mod tests {
         let tables = get_included_tables_for_db(&filter, "db1");
         assert!(tables.is_none());
     }
+
+    #[test]
+    fn test_dump_compression_parse() {
+        assert_eq!(
+            DumpCompression::parse("zstd:3").unwrap(),
+            DumpCompression {
+                method: CompressionMethod::Zstd,
+                level: Some(3),
+            }
+        );
+        assert_eq!(
+            DumpCompression::parse("lz4").unwrap(),
+            DumpCompression {
+                method: CompressionMethod::Lz4,
+                level: None,
+            }
+        );
+        assert!(DumpCompression::parse("brotli:3").is_err());
+        assert!(DumpCompression::parse("zstd:not-a-number").is_err());
+    }
+
+    #[test]
+    fn test_dump_compression_default_matches_old_hardcoded_behavior() {
+        assert_eq!(
+            DumpCompression::default(),
+            DumpCompression {
+                method: CompressionMethod::Gzip,
+                level: Some(9),
+            }
+        );
+    }
+
+    #[test]
+    fn test_pg_dump_major_version() {
+        assert_eq!(pg_dump_major_version("pg_dump (PostgreSQL) 16.2"), Some(16));
+        assert_eq!(pg_dump_major_version("pg_dump (PostgreSQL) 9.6.24"), Some(9));
+        assert_eq!(pg_dump_major_version("garbage output"), None);
+    }
 }
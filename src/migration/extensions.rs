@@ -0,0 +1,185 @@
+// ABOUTME: Extension-aware schema restore support for Neon-supported extensions
+// ABOUTME: Plans ordered CREATE EXTENSION statements to run ahead of schema restore
+
+use anyhow::{Context, Result};
+use tokio_postgres::Client;
+
+/// An installed extension and its version, as reported by `pg_extension`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionInfo {
+    pub name: String,
+    pub version: String,
+}
+
+/// Extensions Neon's managed Postgres supports today; anything else
+/// installed on the source has no target equivalent, so it's reported via
+/// [`ExtensionPlan::unsupported`] instead of restored
+pub const NEON_SUPPORTED_EXTENSIONS: &[&str] = &[
+    "vector",
+    "postgis",
+    "pg_trgm",
+    "btree_gin",
+    "btree_gist",
+    "citext",
+    "hstore",
+    "uuid-ossp",
+    "pgcrypto",
+    "pg_stat_statements",
+    "tablefunc",
+    "unaccent",
+    "fuzzystrmatch",
+    "intarray",
+    "ltree",
+];
+
+/// Extensions that must be created before the rest of [`NEON_SUPPORTED_EXTENSIONS`]
+///
+/// Currently just `vector`: the `vector` type, `vector_ops` operator classes,
+/// and HNSW/IVFFlat indexes all need `CREATE EXTENSION vector` to have
+/// already run before any table using them is created, and a plain
+/// alphabetical or pg_dump-order replay doesn't guarantee that.
+const HIGH_PRIORITY_EXTENSIONS: &[&str] = &["vector"];
+
+/// Installed extensions on the source, partitioned into what can be restored
+/// on a Neon target and what can't
+#[derive(Debug, Clone, Default)]
+pub struct ExtensionPlan {
+    /// Extensions to `CREATE EXTENSION IF NOT EXISTS` on the target, ordered
+    /// so [`HIGH_PRIORITY_EXTENSIONS`] run first
+    pub supported: Vec<ExtensionInfo>,
+    /// Extensions installed on the source with no Neon equivalent; schema
+    /// objects depending on these will fail to restore unless dropped first
+    pub unsupported: Vec<ExtensionInfo>,
+}
+
+impl ExtensionPlan {
+    /// Ordered `CREATE EXTENSION IF NOT EXISTS` statements for `self.supported`
+    pub fn create_statements(&self) -> Vec<String> {
+        self.supported
+            .iter()
+            .map(|ext| format!("CREATE EXTENSION IF NOT EXISTS \"{}\";", ext.name))
+            .collect()
+    }
+}
+
+/// Query the source's installed extensions (excluding `plpgsql`, which every
+/// Postgres database - including Neon's - already has) and split them into
+/// [`ExtensionPlan::supported`]/[`ExtensionPlan::unsupported`] against
+/// [`NEON_SUPPORTED_EXTENSIONS`], logging a warning for each unsupported one
+///
+/// # Errors
+///
+/// Returns an error if querying `pg_extension` on `client` fails.
+pub async fn plan_extensions(client: &Client) -> Result<ExtensionPlan> {
+    let rows = client
+        .query(
+            "SELECT extname, extversion
+             FROM pg_catalog.pg_extension
+             WHERE extname != 'plpgsql'
+             ORDER BY extname",
+            &[],
+        )
+        .await
+        .context("Failed to list source extensions")?;
+
+    let mut supported = Vec::new();
+    let mut unsupported = Vec::new();
+
+    for row in &rows {
+        let ext = ExtensionInfo {
+            name: row.get(0),
+            version: row.get(1),
+        };
+
+        if NEON_SUPPORTED_EXTENSIONS.contains(&ext.name.as_str()) {
+            supported.push(ext);
+        } else {
+            tracing::warn!(
+                "⚠ Extension '{}' (v{}) is installed on the source but has no Neon equivalent; \
+                 schema objects depending on it will fail to restore unless dropped first",
+                ext.name,
+                ext.version
+            );
+            unsupported.push(ext);
+        }
+    }
+
+    Ok(ExtensionPlan {
+        supported: order_supported(supported),
+        unsupported,
+    })
+}
+
+/// Sort `supported` so [`HIGH_PRIORITY_EXTENSIONS`] come first, alphabetically
+/// within each group
+fn order_supported(mut supported: Vec<ExtensionInfo>) -> Vec<ExtensionInfo> {
+    supported.sort_by_key(|ext| {
+        (
+            !HIGH_PRIORITY_EXTENSIONS.contains(&ext.name.as_str()),
+            ext.name.clone(),
+        )
+    });
+    supported
+}
+
+/// Run `plan`'s `CREATE EXTENSION IF NOT EXISTS` statements against `client`,
+/// ahead of restoring the schema file that depends on them
+///
+/// # Errors
+///
+/// Returns an error if any `CREATE EXTENSION` statement fails, e.g. because
+/// the extension isn't actually installed on the target despite being on
+/// [`NEON_SUPPORTED_EXTENSIONS`].
+pub async fn apply_extensions(client: &Client, plan: &ExtensionPlan) -> Result<()> {
+    for statement in plan.create_statements() {
+        tracing::info!("  {}", statement);
+        client
+            .batch_execute(&statement)
+            .await
+            .with_context(|| format!("Failed to execute: {}", statement))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_supported_puts_high_priority_first() {
+        let unordered = vec![
+            ExtensionInfo {
+                name: "zebra_ext".to_string(),
+                version: "1.0".to_string(),
+            },
+            ExtensionInfo {
+                name: "vector".to_string(),
+                version: "0.7.0".to_string(),
+            },
+            ExtensionInfo {
+                name: "pg_trgm".to_string(),
+                version: "1.6".to_string(),
+            },
+        ];
+
+        let ordered = order_supported(unordered);
+        let names: Vec<&str> = ordered.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["vector", "pg_trgm", "zebra_ext"]);
+    }
+
+    #[test]
+    fn test_create_statements_shape() {
+        let plan = ExtensionPlan {
+            supported: vec![ExtensionInfo {
+                name: "vector".to_string(),
+                version: "0.7.0".to_string(),
+            }],
+            unsupported: Vec::new(),
+        };
+
+        assert_eq!(
+            plan.create_statements(),
+            vec!["CREATE EXTENSION IF NOT EXISTS \"vector\";".to_string()]
+        );
+    }
+}
@@ -0,0 +1,317 @@
+// ABOUTME: Predicate-filtered table data copy for time-windowed replication
+// ABOUTME: Streams rows directly via COPY, since pg_dump can't apply a per-table row filter
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use futures::{SinkExt, StreamExt};
+use tokio_postgres::Client;
+
+use crate::postgres::{self, ConnectionPool};
+use crate::utils::quote_ident;
+
+/// Default per-worker batch byte budget when the caller doesn't set `--batch-bytes`:
+/// small enough that a handful of oversized tables don't starve the other workers,
+/// large enough that most small tables bin-pack several-to-a-batch
+pub const DEFAULT_BATCH_BYTES: i64 = 512 * 1024 * 1024; // 512 MiB
+
+/// A predicate-filtered table queued for copy, with the byte estimate used for bin-packing
+struct SizedTable {
+    qualified_name: String,
+    predicate: String,
+    size_bytes: i64,
+}
+
+/// Copies rows for predicate-filtered tables from source to target using `COPY` streams
+///
+/// Tables are greedily bin-packed across `parallelism` workers: visited largest-first,
+/// each table is assigned to the worker whose current (open) batch would stay under
+/// `batch_bytes`, preferring the least-loaded such worker; when no worker's open batch
+/// has room, the least-loaded worker starts a fresh batch (so a single table bigger
+/// than `batch_bytes` still copies on its own rather than blocking behind a full
+/// batch). Each worker then streams its assigned tables through one connection, one
+/// table at a time, and all workers run concurrently - this keeps a handful of huge
+/// tables from serializing the whole snapshot while avoiding a connection per tiny
+/// table.
+///
+/// `already_completed` (schema-qualified table names, see [`crate::migration::TableInfo::qualified_name`])
+/// is skipped entirely, so a resumed run doesn't re-copy tables a previous attempt
+/// already finished. `on_table_complete` is invoked once per table as it finishes, in
+/// whatever order workers complete them, so the caller can persist per-table
+/// checkpoint progress incrementally instead of only at the very end.
+///
+/// # Errors
+///
+/// Returns an error if a connection can't be established to either database, or if
+/// any table's `COPY OUT`/`COPY IN` stream fails.
+pub async fn copy_filtered_tables(
+    source_url: &str,
+    target_url: &str,
+    filtered_tables: &[(String, String)],
+    parallelism: usize,
+    batch_bytes: i64,
+    already_completed: &HashSet<String>,
+    on_table_complete: &mut dyn FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    let pending: Vec<&(String, String)> = filtered_tables
+        .iter()
+        .filter(|(name, _)| !already_completed.contains(name))
+        .collect();
+
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let parallelism = parallelism.max(1);
+    let batch_bytes = batch_bytes.max(1);
+
+    let source_client = postgres::connect(source_url).await?;
+    let mut sized_tables = Vec::with_capacity(pending.len());
+    for (qualified_name, predicate) in pending {
+        let size_bytes = estimate_table_size(&source_client, qualified_name).await?;
+        sized_tables.push(SizedTable {
+            qualified_name: qualified_name.clone(),
+            predicate: predicate.clone(),
+            size_bytes,
+        });
+    }
+    sized_tables.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+
+    let worker_queues = bin_pack(sized_tables, parallelism, batch_bytes);
+    let active_workers = worker_queues
+        .iter()
+        .filter(|q| !q.is_empty())
+        .count()
+        .max(1);
+
+    tracing::info!(
+        "Copying {} filtered table(s) using {} worker(s), batch budget {} bytes",
+        worker_queues.iter().map(|q| q.len()).sum::<usize>(),
+        active_workers,
+        batch_bytes
+    );
+
+    let source_pool = ConnectionPool::new(source_url, active_workers).await?;
+    let target_pool = ConnectionPool::new(target_url, active_workers).await?;
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+
+    let mut worker_futures = Vec::with_capacity(active_workers);
+    let mut worker_idx = 0;
+    for queue in worker_queues {
+        if queue.is_empty() {
+            continue;
+        }
+        let source_client = source_pool.client(worker_idx);
+        let target_client = target_pool.client(worker_idx);
+        worker_idx += 1;
+        let tx = tx.clone();
+        worker_futures.push(async move {
+            for table in queue {
+                copy_one_table(
+                    source_client,
+                    target_client,
+                    &table.qualified_name,
+                    &table.predicate,
+                )
+                .await?;
+                // Only the receiver loop below can fail to keep up; the channel
+                // itself never closes before all workers finish
+                let _ = tx.send(table.qualified_name);
+            }
+            Ok::<(), anyhow::Error>(())
+        });
+    }
+    drop(tx);
+
+    let workers_done = futures::future::try_join_all(worker_futures);
+    let drain_completions = async {
+        while let Some(name) = rx.recv().await {
+            on_table_complete(&name)?;
+        }
+        Ok::<(), anyhow::Error>(())
+    };
+
+    tokio::try_join!(workers_done, drain_completions)?;
+
+    Ok(())
+}
+
+/// Greedily bin-packs `tables` (assumed sorted largest-first) into `parallelism`
+/// worker queues so each worker's cumulative byte load stays as balanced as
+/// possible given the `batch_bytes` budget. See [`copy_filtered_tables`] for the
+/// assignment rule.
+fn bin_pack(tables: Vec<SizedTable>, parallelism: usize, batch_bytes: i64) -> Vec<Vec<SizedTable>> {
+    let mut queues: Vec<Vec<SizedTable>> = (0..parallelism).map(|_| Vec::new()).collect();
+    let mut worker_totals = vec![0i64; parallelism];
+    let mut open_batch_totals = vec![0i64; parallelism];
+
+    for table in tables {
+        let fits = (0..parallelism)
+            .filter(|&w| {
+                open_batch_totals[w] == 0 || open_batch_totals[w] + table.size_bytes <= batch_bytes
+            })
+            .min_by_key(|&w| worker_totals[w]);
+
+        let worker = match fits {
+            Some(w) => w,
+            None => {
+                // Every worker's open batch would overflow - start a fresh batch on
+                // whichever worker is carrying the least total so far
+                let w = (0..parallelism).min_by_key(|&w| worker_totals[w]).unwrap();
+                open_batch_totals[w] = 0;
+                w
+            }
+        };
+
+        open_batch_totals[worker] += table.size_bytes;
+        worker_totals[worker] += table.size_bytes;
+        queues[worker].push(table);
+    }
+
+    queues
+}
+
+/// Splits a name in [`crate::migration::TableInfo::qualified_name`] format back
+/// into `(schema, table)`, defaulting to `public` when unqualified
+fn split_qualified(qualified_name: &str) -> (&str, &str) {
+    match qualified_name.split_once('.') {
+        Some((schema, table)) => (schema, table),
+        None => ("public", qualified_name),
+    }
+}
+
+/// Estimates a table's on-disk size (including indexes and TOAST) to drive bin-packing
+async fn estimate_table_size(client: &Client, qualified_name: &str) -> Result<i64> {
+    let (schema, table) = split_qualified(qualified_name);
+    let row = client
+        .query_one(
+            "SELECT pg_total_relation_size(format('%I.%I', $1::text, $2::text)::regclass)",
+            &[&schema, &table],
+        )
+        .await
+        .with_context(|| format!("Failed to estimate size for table '{}'", qualified_name))?;
+    Ok(row.get(0))
+}
+
+/// Copies one predicate-filtered table's rows from source to target, opening a
+/// dedicated connection to each side.
+///
+/// Used by `worker` to execute a single queued per-table task; prefer
+/// [`copy_filtered_tables`] when copying many tables at once, since it bin-packs
+/// and parallelizes across workers instead of opening one connection per table.
+///
+/// # Errors
+///
+/// Returns an error if a connection can't be established to either database, or
+/// if the table's `COPY OUT`/`COPY IN` stream fails.
+pub async fn copy_single_table(
+    source_url: &str,
+    target_url: &str,
+    qualified_name: &str,
+    predicate: &str,
+) -> Result<()> {
+    let source_client = postgres::connect(source_url).await?;
+    let target_client = postgres::connect(target_url).await?;
+    copy_one_table(&source_client, &target_client, qualified_name, predicate).await
+}
+
+/// Streams one table's rows from source to target via binary `COPY`, applying
+/// `predicate` as a `WHERE` clause if non-empty or copying the whole table otherwise
+///
+/// `pub(crate)` so [`crate::migration::parallel_copy`] can reuse it for plain
+/// (non-predicate-filtered) parallel copies instead of re-implementing the same
+/// `COPY OUT`/`COPY IN` streaming.
+pub(crate) async fn copy_one_table(
+    source_client: &Client,
+    target_client: &Client,
+    qualified_name: &str,
+    predicate: &str,
+) -> Result<()> {
+    let (schema, name) = split_qualified(qualified_name);
+    let qualified = format!("{}.{}", quote_ident(schema), quote_ident(name));
+
+    let copy_out_sql = if predicate.trim().is_empty() {
+        format!("COPY {} TO STDOUT (FORMAT binary)", qualified)
+    } else {
+        format!(
+            "COPY (SELECT * FROM {} WHERE {}) TO STDOUT (FORMAT binary)",
+            qualified, predicate
+        )
+    };
+
+    let mut out_stream = source_client
+        .copy_out(&copy_out_sql)
+        .await
+        .with_context(|| format!("Failed to start COPY OUT for '{}'", qualified_name))?;
+
+    let mut in_sink = target_client
+        .copy_in(&format!("COPY {} FROM STDIN (FORMAT binary)", qualified))
+        .await
+        .with_context(|| format!("Failed to start COPY IN for '{}'", qualified_name))?;
+
+    in_sink
+        .send_all(&mut out_stream)
+        .await
+        .with_context(|| format!("Failed to stream rows for '{}'", qualified_name))?;
+
+    in_sink
+        .close()
+        .await
+        .with_context(|| format!("Failed to finalize COPY IN for '{}'", qualified_name))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sized(name: &str, size_bytes: i64) -> SizedTable {
+        SizedTable {
+            qualified_name: name.to_string(),
+            predicate: String::new(),
+            size_bytes,
+        }
+    }
+
+    #[test]
+    fn test_bin_pack_balances_across_workers() {
+        let tables = vec![sized("a", 100), sized("b", 90), sized("c", 10)];
+        let queues = bin_pack(tables, 2, 1000);
+        let totals: Vec<i64> = queues
+            .iter()
+            .map(|q| q.iter().map(|t| t.size_bytes).sum())
+            .collect();
+        // Largest-first assignment to the least-loaded worker: a->w0, b->w1, c->w1
+        assert_eq!(totals, vec![100, 100]);
+    }
+
+    #[test]
+    fn test_bin_pack_gives_oversized_table_its_own_batch() {
+        let tables = vec![sized("huge", 5000), sized("small", 10)];
+        let queues = bin_pack(tables, 2, 1000);
+        // "huge" alone exceeds the budget but still gets placed (its own batch)
+        // rather than blocking "small" from running on the other worker
+        assert_eq!(queues.iter().map(|q| q.len()).sum::<usize>(), 2);
+        assert!(queues
+            .iter()
+            .any(|q| q.iter().any(|t| t.qualified_name == "huge")));
+    }
+
+    #[test]
+    fn test_bin_pack_starts_new_batch_when_budget_exceeded() {
+        // Three 60-byte tables with a 100-byte budget and a single worker: each
+        // table after the first can't join the open batch (60+60 > 100), so b and
+        // c each start a new batch on the same (only) worker
+        let tables = vec![sized("a", 60), sized("b", 60), sized("c", 60)];
+        let queues = bin_pack(tables, 1, 100);
+        assert_eq!(queues[0].len(), 3);
+    }
+
+    #[test]
+    fn test_split_qualified() {
+        assert_eq!(split_qualified("orders"), ("public", "orders"));
+        assert_eq!(split_qualified("billing.invoices"), ("billing", "invoices"));
+    }
+}
@@ -0,0 +1,331 @@
+// ABOUTME: Schema fingerprinting to detect column-level drift between source and target
+// ABOUTME: Records a versioned fingerprint per table on the target, checked before resume/sync/verify
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use tokio_postgres::Client;
+
+/// Name of the bookkeeping table created on the target to track schema fingerprints
+const FINGERPRINT_TABLE: &str = "_seren_schema_fingerprints";
+
+/// Fingerprint of a single column: name, type, and ordinal position - the
+/// three properties that must stay stable for a replicated table to remain
+/// compatible with the plan that was used to copy it
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ColumnFingerprint {
+    pub name: String,
+    pub data_type: String,
+    pub ordinal_position: i32,
+}
+
+/// Fingerprint of a table's full column set, in ordinal order
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TableFingerprint {
+    pub schema: String,
+    pub table: String,
+    pub columns: Vec<ColumnFingerprint>,
+}
+
+/// Compute the fingerprint of `schema.table` as it currently exists on `client`
+///
+/// # Errors
+///
+/// Returns an error if the table can't be queried, or has no columns (which
+/// usually means the table doesn't exist).
+pub async fn compute_fingerprint(
+    client: &Client,
+    schema: &str,
+    table: &str,
+) -> Result<TableFingerprint> {
+    let rows = client
+        .query(
+            "SELECT column_name, data_type, ordinal_position
+             FROM information_schema.columns
+             WHERE table_schema = $1 AND table_name = $2
+             ORDER BY ordinal_position",
+            &[&schema, &table],
+        )
+        .await
+        .with_context(|| format!("Failed to read column info for '{}.{}'", schema, table))?;
+
+    if rows.is_empty() {
+        bail!("Table '{}.{}' not found or has no columns", schema, table);
+    }
+
+    let columns = rows
+        .iter()
+        .map(|row| ColumnFingerprint {
+            name: row.get(0),
+            data_type: row.get(1),
+            ordinal_position: row.get(2),
+        })
+        .collect();
+
+    Ok(TableFingerprint {
+        schema: schema.to_string(),
+        table: table.to_string(),
+        columns,
+    })
+}
+
+/// Compute fingerprints for a set of `(schema, table)` pairs
+pub async fn compute_fingerprints(
+    client: &Client,
+    tables: &[(String, String)],
+) -> Result<Vec<TableFingerprint>> {
+    let mut fingerprints = Vec::with_capacity(tables.len());
+    for (schema, table) in tables {
+        fingerprints.push(compute_fingerprint(client, schema, table).await?);
+    }
+    Ok(fingerprints)
+}
+
+/// Create the `_seren_schema_fingerprints` bookkeeping table if it doesn't already exist
+async fn ensure_fingerprint_table(client: &Client) -> Result<()> {
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                schema_name TEXT NOT NULL,
+                table_name TEXT NOT NULL,
+                version BIGINT NOT NULL,
+                columns_json TEXT NOT NULL,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                PRIMARY KEY (schema_name, table_name)
+            )",
+            FINGERPRINT_TABLE
+        ))
+        .await
+        .context("Failed to create _seren_schema_fingerprints tracking table")?;
+
+    Ok(())
+}
+
+/// Record fingerprints on the target, bumping each table's `version` whenever
+/// its column set differs from what's currently recorded (a no-op if nothing
+/// changed since the last recording)
+///
+/// # Errors
+///
+/// Returns an error if the tracking table can't be created or updated.
+pub async fn record_fingerprints(client: &Client, fingerprints: &[TableFingerprint]) -> Result<()> {
+    ensure_fingerprint_table(client).await?;
+
+    for fp in fingerprints {
+        let columns_json = serde_json::to_string(&fp.columns).with_context(|| {
+            format!(
+                "Failed to serialize fingerprint for '{}.{}'",
+                fp.schema, fp.table
+            )
+        })?;
+
+        let existing = client
+            .query_opt(
+                &format!(
+                    "SELECT version, columns_json FROM {} WHERE schema_name = $1 AND table_name = $2",
+                    FINGERPRINT_TABLE
+                ),
+                &[&fp.schema, &fp.table],
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to read existing fingerprint for '{}.{}'",
+                    fp.schema, fp.table
+                )
+            })?;
+
+        let next_version: i64 = match &existing {
+            Some(row) => {
+                let recorded_json: String = row.get(1);
+                if recorded_json == columns_json {
+                    continue;
+                }
+                let current_version: i64 = row.get(0);
+                current_version + 1
+            }
+            None => 1,
+        };
+
+        client
+            .execute(
+                &format!(
+                    "INSERT INTO {} (schema_name, table_name, version, columns_json, updated_at)
+                     VALUES ($1, $2, $3, $4, now())
+                     ON CONFLICT (schema_name, table_name)
+                     DO UPDATE SET version = EXCLUDED.version, columns_json = EXCLUDED.columns_json, updated_at = now()",
+                    FINGERPRINT_TABLE
+                ),
+                &[&fp.schema, &fp.table, &next_version, &columns_json],
+            )
+            .await
+            .with_context(|| format!("Failed to record fingerprint for '{}.{}'", fp.schema, fp.table))?;
+    }
+
+    Ok(())
+}
+
+/// Load all recorded fingerprints from the target, keyed by `(schema, table)`
+///
+/// # Errors
+///
+/// Returns an error if the tracking table can't be created or read, or a
+/// recorded fingerprint can't be parsed.
+pub async fn load_recorded_fingerprints(
+    client: &Client,
+) -> Result<HashMap<(String, String), TableFingerprint>> {
+    ensure_fingerprint_table(client).await?;
+
+    let rows = client
+        .query(
+            &format!(
+                "SELECT schema_name, table_name, columns_json FROM {}",
+                FINGERPRINT_TABLE
+            ),
+            &[],
+        )
+        .await
+        .context("Failed to read recorded schema fingerprints")?;
+
+    let mut recorded = HashMap::new();
+    for row in rows {
+        let schema: String = row.get(0);
+        let table: String = row.get(1);
+        let columns_json: String = row.get(2);
+        let columns: Vec<ColumnFingerprint> = serde_json::from_str(&columns_json)
+            .with_context(|| format!("Failed to parse recorded fingerprint for '{}.{}'", schema, table))?;
+        recorded.insert(
+            (schema.clone(), table.clone()),
+            TableFingerprint {
+                schema,
+                table,
+                columns,
+            },
+        );
+    }
+
+    Ok(recorded)
+}
+
+/// Require that every fingerprint in `source` exactly matches what's recorded
+/// for the same table in `recorded` - same columns, same types, same order.
+///
+/// # Errors
+///
+/// Returns an error naming the first diverging table and column found: an
+/// extra/missing/renamed/retyped/reordered column, or a table the target has
+/// no recorded fingerprint for at all.
+pub fn check_fingerprints_match(
+    source: &[TableFingerprint],
+    recorded: &HashMap<(String, String), TableFingerprint>,
+) -> Result<()> {
+    for source_fp in source {
+        let key = (source_fp.schema.clone(), source_fp.table.clone());
+        let recorded_fp = recorded.get(&key).with_context(|| {
+            format!(
+                "No schema fingerprint recorded on target for '{}.{}' - run `init` again to \
+                 re-fingerprint before resuming or syncing",
+                source_fp.schema, source_fp.table
+            )
+        })?;
+
+        if source_fp.columns.len() != recorded_fp.columns.len() {
+            bail!(
+                "Schema drift detected in '{}.{}': source has {} column(s), target recorded {} - \
+                 the target has gained or lost columns since it was last replicated",
+                source_fp.schema,
+                source_fp.table,
+                source_fp.columns.len(),
+                recorded_fp.columns.len()
+            );
+        }
+
+        for (source_col, recorded_col) in source_fp.columns.iter().zip(recorded_fp.columns.iter()) {
+            if source_col != recorded_col {
+                bail!(
+                    "Schema drift detected in '{}.{}' at column position {}: source column is \
+                     '{}' ({}), target recorded '{}' ({})",
+                    source_fp.schema,
+                    source_fp.table,
+                    source_col.ordinal_position,
+                    source_col.name,
+                    source_col.data_type,
+                    recorded_col.name,
+                    recorded_col.data_type
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fp(schema: &str, table: &str, columns: Vec<(&str, &str, i32)>) -> TableFingerprint {
+        TableFingerprint {
+            schema: schema.to_string(),
+            table: table.to_string(),
+            columns: columns
+                .into_iter()
+                .map(|(name, data_type, ordinal_position)| ColumnFingerprint {
+                    name: name.to_string(),
+                    data_type: data_type.to_string(),
+                    ordinal_position,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_check_fingerprints_match_identical() {
+        let source = vec![fp("public", "users", vec![("id", "integer", 1), ("name", "text", 2)])];
+        let mut recorded = HashMap::new();
+        recorded.insert(
+            ("public".to_string(), "users".to_string()),
+            fp("public", "users", vec![("id", "integer", 1), ("name", "text", 2)]),
+        );
+
+        assert!(check_fingerprints_match(&source, &recorded).is_ok());
+    }
+
+    #[test]
+    fn test_check_fingerprints_match_missing_recorded() {
+        let source = vec![fp("public", "users", vec![("id", "integer", 1)])];
+        let recorded = HashMap::new();
+
+        let err = check_fingerprints_match(&source, &recorded).unwrap_err();
+        assert!(err.to_string().contains("No schema fingerprint recorded"));
+    }
+
+    #[test]
+    fn test_check_fingerprints_match_extra_column() {
+        let source = vec![fp(
+            "public",
+            "users",
+            vec![("id", "integer", 1), ("name", "text", 2)],
+        )];
+        let mut recorded = HashMap::new();
+        recorded.insert(
+            ("public".to_string(), "users".to_string()),
+            fp("public", "users", vec![("id", "integer", 1)]),
+        );
+
+        let err = check_fingerprints_match(&source, &recorded).unwrap_err();
+        assert!(err.to_string().contains("Schema drift detected in 'public.users'"));
+    }
+
+    #[test]
+    fn test_check_fingerprints_match_renamed_column() {
+        let source = vec![fp("public", "users", vec![("id", "integer", 1), ("full_name", "text", 2)])];
+        let mut recorded = HashMap::new();
+        recorded.insert(
+            ("public".to_string(), "users".to_string()),
+            fp("public", "users", vec![("id", "integer", 1), ("name", "text", 2)]),
+        );
+
+        let err = check_fingerprints_match(&source, &recorded).unwrap_err();
+        assert!(err.to_string().contains("column position 2"));
+    }
+}
@@ -0,0 +1,308 @@
+// ABOUTME: Embedded schema migration runner for bringing a target up to a known state
+// ABOUTME: Applies a directory of versioned .sql files, tracked in a _seren_migrations table
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio_postgres::Client;
+
+/// Name of the bookkeeping table created on the target to track applied migrations
+const MIGRATIONS_TABLE: &str = "_seren_migrations";
+
+/// A single versioned SQL migration file discovered on disk
+#[derive(Debug, Clone)]
+pub struct MigrationFile {
+    /// Leading version token from the filename, zero-padded when numeric (e.g.
+    /// "0001" becomes "0000000001"), used for ordering
+    pub version: String,
+    /// Filename, for logging and error messages
+    pub filename: String,
+    /// Full path to the migration file
+    pub path: PathBuf,
+    /// Raw SQL contents of the file
+    pub sql: String,
+}
+
+/// Outcome of applying a directory of migrations
+#[derive(Debug, Clone, Default)]
+pub struct MigrationSummary {
+    /// Versions newly applied during this run, in order
+    pub applied: Vec<String>,
+    /// Versions already applied (and unchanged) that were skipped
+    pub skipped: Vec<String>,
+}
+
+/// Discover and apply all pending migrations in `dir` against `client`
+///
+/// Migrations are applied in ascending version order. For each file:
+/// - If its version has never been applied, its SQL is executed and recorded
+///   in `_seren_migrations` along with a checksum of its contents.
+/// - If its version was already applied and the checksum matches, it's skipped.
+/// - If its version was already applied but the checksum differs, this is
+///   treated as drift in a previously-applied migration and returns an error
+///   rather than silently re-applying or ignoring it.
+///
+/// Idempotent: re-running against a target that's already up to date applies
+/// nothing and returns a summary with an empty `applied` list.
+///
+/// Each migration's SQL and its bookkeeping row are applied in a single
+/// transaction, so migration files must not contain their own `BEGIN`/`COMMIT`
+/// statements - doing so would end the transaction early and could leave a
+/// migration's DDL applied without being recorded.
+///
+/// # Errors
+///
+/// Returns an error if the migrations table can't be created, a file can't be
+/// read or parsed, a previously-applied migration's checksum has drifted, or
+/// applying a migration's SQL fails.
+pub async fn apply_migrations(client: &mut Client, dir: &Path) -> Result<MigrationSummary> {
+    ensure_migrations_table(client).await?;
+
+    let files = discover_migration_files(dir)?;
+    let applied_checksums = applied_migrations(client).await?;
+
+    let mut summary = MigrationSummary::default();
+
+    for file in &files {
+        let checksum = checksum_sql(client, &file.sql).await?;
+
+        if let Some(applied_checksum) = applied_checksums.get(&file.version) {
+            if applied_checksum != &checksum {
+                bail!(
+                    "Migration '{}' (version {}) has already been applied but its contents \
+                     have changed since then (checksum mismatch). Migrations must be \
+                     immutable once applied - add a new migration instead of editing this one.",
+                    file.filename,
+                    file.version
+                );
+            }
+            tracing::debug!(
+                version = %file.version,
+                file = %file.filename,
+                "Migration already applied, skipping"
+            );
+            summary.skipped.push(file.version.clone());
+            continue;
+        }
+
+        tracing::info!("Applying migration {} ({})...", file.version, file.filename);
+
+        // Run the migration's SQL and its _seren_migrations bookkeeping in one
+        // transaction, so a crash between the two never leaves a migration
+        // applied but unrecorded (which would otherwise re-run non-idempotent
+        // DDL on the next attempt).
+        let txn = client
+            .transaction()
+            .await
+            .context("Failed to start migration transaction")?;
+
+        txn.batch_execute(&file.sql)
+            .await
+            .with_context(|| format!("Failed to apply migration '{}'", file.filename))?;
+
+        txn.execute(
+            &format!(
+                "INSERT INTO {} (version, filename, checksum, applied_at) \
+                 VALUES ($1, $2, $3, now())",
+                MIGRATIONS_TABLE
+            ),
+            &[&file.version, &file.filename, &checksum],
+        )
+        .await
+        .with_context(|| format!("Failed to record migration '{}' as applied", file.filename))?;
+
+        txn.commit()
+            .await
+            .with_context(|| format!("Failed to commit migration '{}'", file.filename))?;
+
+        tracing::info!("✓ Migration {} applied", file.version);
+        summary.applied.push(file.version.clone());
+    }
+
+    Ok(summary)
+}
+
+/// Create the `_seren_migrations` tracking table if it doesn't already exist
+async fn ensure_migrations_table(client: &Client) -> Result<()> {
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                version TEXT PRIMARY KEY,
+                filename TEXT NOT NULL,
+                checksum TEXT NOT NULL,
+                applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+            )",
+            MIGRATIONS_TABLE
+        ))
+        .await
+        .context("Failed to create _seren_migrations tracking table")?;
+
+    Ok(())
+}
+
+/// Fetch the version -> checksum map of migrations already recorded as applied
+async fn applied_migrations(client: &Client) -> Result<HashMap<String, String>> {
+    let rows = client
+        .query(
+            &format!("SELECT version, checksum FROM {}", MIGRATIONS_TABLE),
+            &[],
+        )
+        .await
+        .context("Failed to read applied migrations")?;
+
+    Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+}
+
+/// Compute a checksum of `sql` using the target's own `md5()` function, so
+/// drift detection matches byte-for-byte without pulling in a hashing crate
+async fn checksum_sql(client: &Client, sql: &str) -> Result<String> {
+    let row = client
+        .query_one("SELECT md5($1)", &[&sql])
+        .await
+        .context("Failed to compute migration checksum")?;
+    Ok(row.get(0))
+}
+
+/// Discover `.sql` files in `dir`, sorted in ascending version order
+///
+/// Each filename must start with a version token followed by `_` or `-`
+/// (e.g. `0001_create_users.sql`, `2-add-index.sql`). Files not matching this
+/// pattern are ignored.
+fn discover_migration_files(dir: &Path) -> Result<Vec<MigrationFile>> {
+    if !dir.exists() {
+        bail!("Migrations directory '{}' does not exist", dir.display());
+    }
+
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read migrations directory '{}'", dir.display()))?
+    {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("sql") {
+            continue;
+        }
+
+        let filename = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .with_context(|| format!("Non-UTF8 migration filename: {}", path.display()))?
+            .to_string();
+
+        let version = parse_version(&filename)
+            .with_context(|| format!("Failed to parse version from '{}'", filename))?;
+
+        let sql = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read migration file '{}'", filename))?;
+
+        files.push(MigrationFile {
+            version,
+            filename,
+            path,
+            sql,
+        });
+    }
+
+    files.sort_by(|a, b| a.version.cmp(&b.version));
+
+    for pair in files.windows(2) {
+        if pair[0].version == pair[1].version {
+            bail!(
+                "Migrations '{}' and '{}' both resolve to version '{}' - versions must be unique",
+                pair[0].filename,
+                pair[1].filename,
+                pair[0].version
+            );
+        }
+    }
+
+    Ok(files)
+}
+
+/// Extract the leading version token from a migration filename
+///
+/// Versions are compared as zero-padded numeric strings when possible, so
+/// `10_x.sql` is treated as coming after `2_x.sql` even though `"10" <
+/// "2"` as plain strings; non-numeric prefixes sort lexically.
+fn parse_version(filename: &str) -> Result<String> {
+    let stem = filename
+        .strip_suffix(".sql")
+        .context("Migration filename must end in .sql")?;
+
+    let separator = stem
+        .find(['_', '-'])
+        .context("Migration filename must start with a version, followed by '_' or '-'")?;
+
+    let version = &stem[..separator];
+    if version.is_empty() {
+        bail!("Migration filename must start with a non-empty version");
+    }
+
+    // Zero-pad purely numeric versions so string comparison sorts numerically
+    if let Ok(n) = version.parse::<u64>() {
+        Ok(format!("{:010}", n))
+    } else {
+        Ok(version.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_version_numeric() {
+        assert_eq!(
+            parse_version("0001_create_users.sql").unwrap(),
+            "0000000001"
+        );
+        assert_eq!(parse_version("2-add-index.sql").unwrap(), "0000000002");
+        assert_eq!(parse_version("10_later.sql").unwrap(), "0000000010");
+    }
+
+    #[test]
+    fn test_parse_version_sorts_numerically_not_lexically() {
+        let mut versions = vec![
+            parse_version("10_later.sql").unwrap(),
+            parse_version("2_earlier.sql").unwrap(),
+        ];
+        versions.sort();
+        assert_eq!(versions, vec!["0000000002", "0000000010"]);
+    }
+
+    #[test]
+    fn test_parse_version_non_numeric() {
+        assert_eq!(parse_version("a_initial.sql").unwrap(), "a");
+    }
+
+    #[test]
+    fn test_parse_version_missing_separator() {
+        assert!(parse_version("nosep.sql").is_err());
+    }
+
+    #[test]
+    fn test_parse_version_not_sql() {
+        assert!(parse_version("0001_create_users.txt").is_err());
+    }
+
+    #[test]
+    fn test_discover_migration_files_orders_and_filters() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("0002_second.sql"), "SELECT 2;").unwrap();
+        std::fs::write(dir.path().join("0001_first.sql"), "SELECT 1;").unwrap();
+        std::fs::write(dir.path().join("README.md"), "not a migration").unwrap();
+
+        let files = discover_migration_files(dir.path()).unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].filename, "0001_first.sql");
+        assert_eq!(files[1].filename, "0002_second.sql");
+    }
+
+    #[test]
+    fn test_discover_migration_files_missing_dir() {
+        let result = discover_migration_files(Path::new("/nonexistent/migrations/dir"));
+        assert!(result.is_err());
+    }
+}
@@ -2,11 +2,66 @@
 // ABOUTME: Handles schema introspection, dump/restore, and data migration
 
 pub mod checksum;
+pub mod compat;
+pub mod cutover;
 pub mod dump;
+pub mod estimation;
+pub mod extensions;
+pub mod filtered;
+pub mod fingerprint;
+pub mod migrations;
+pub mod native;
+pub mod parallel_copy;
+pub mod progress;
 pub mod restore;
 pub mod schema;
+pub mod snapshot;
+pub mod state;
+pub mod stream;
 
-pub use checksum::{compare_tables, compute_table_checksum, ChecksumResult};
-pub use dump::{dump_data, dump_globals, dump_schema};
+pub use checksum::{
+    compare_schema, compare_tables, compare_tables_chunked, compare_tables_incremental,
+    compare_tables_merkle, compute_table_checksum, load_watermark, record_watermark,
+    ChecksumAlgorithm, ChecksumResult, ChunkedChecksumResult, ChunkedVerifyConfig,
+    IncrementalChecksumResult, MerkleChecksumResult, MerkleVerifyConfig, MismatchedBucket,
+    MismatchedRange, RowDifference, RowDifferenceKind, SchemaComparisonSummary,
+};
+pub use compat::{check_compatibility, CompatibilityIssue, CompatibilityReport, CompatibilitySeverity};
+pub use cutover::{
+    close_cutover_slot, open_cutover_slot, start_streaming, wait_and_cutover, CutoverSlot,
+};
+pub use dump::{dump_data, dump_globals, dump_schema, CompressionMethod, DumpCompression};
+pub use estimation::{
+    estimate_database_sizes, estimate_mongo_collection_sizes, format_bytes, format_duration,
+    CollectionSizeInfo, DatabaseSizeInfo, ThroughputEstimator,
+};
+pub use extensions::{
+    apply_extensions, plan_extensions, ExtensionInfo, ExtensionPlan, NEON_SUPPORTED_EXTENSIONS,
+};
+pub use filtered::{copy_filtered_tables, copy_single_table, DEFAULT_BATCH_BYTES};
+pub use fingerprint::{
+    check_fingerprints_match, compute_fingerprint, compute_fingerprints,
+    load_recorded_fingerprints, record_fingerprints, ColumnFingerprint, TableFingerprint,
+};
+pub use migrations::{apply_migrations, MigrationFile, MigrationSummary};
+pub use native::{
+    dump_roles_native, dump_schema_native, dump_table_ddl, dump_table_grants_native,
+    restore_roles_native, restore_schema_native, MigrationBackend,
+};
+pub use parallel_copy::{copy_tables_parallel, CopySummary, TableCopyOutcome};
+pub use progress::{terminal_progress_callback, ProgressCallback, ReplicationPhase, ReplicationProgress};
 pub use restore::{restore_data, restore_globals, restore_schema};
-pub use schema::{list_databases, list_tables, DatabaseInfo, TableInfo};
+pub use schema::{
+    check_replication_eligibility, diff_schema, list_databases, list_schemas, list_tables,
+    scan_replication_gaps, DatabaseInfo, ReplicationEligibilityIssue, ReplicationGapIssue,
+    ReplicationGapReport, ReplicationGapSeverity, SchemaDiff, SchemaIssue, TableInfo,
+};
+pub use snapshot::{
+    end_consistent_snapshot, export_consistent_snapshot, join_consistent_snapshot,
+    ConsistentSnapshot,
+};
+pub use state::{
+    check_schema_drift, compute_schema_fingerprint, load_migration_state, record_migration_state,
+    record_table_progress, RecordedMigrationState, SchemaFingerprint,
+};
+pub use stream::stream_dump_to_restore;
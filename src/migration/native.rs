@@ -0,0 +1,336 @@
+// ABOUTME: Pure-Rust dump/restore backend using catalog introspection and tokio-postgres COPY
+// ABOUTME: Alternative to the pg_dump/pg_dumpall/psql/pg_restore subprocess path for hosts without those tools
+
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use tokio_postgres::Client;
+
+use crate::replication::{quote_identifier, quote_qualified};
+
+/// Which implementation `validate`/`init` use to move schema, roles, and data between
+/// source and target.
+///
+/// [`Self::Cli`] is the original path (see [`crate::migration::dump`] and
+/// [`crate::migration::restore`]): it shells out to `pg_dump`/`pg_dumpall`/`psql`/
+/// `pg_restore`, which gives full `pg_dump` DDL coverage (views, triggers, custom
+/// types, extensions, ...) but requires those binaries on `PATH`. [`Self::Native`]
+/// (this module) introspects the catalogs directly and streams data with
+/// `tokio-postgres`'s binary `COPY` - no child processes, so it works in minimal
+/// containers and sandboxed environments that only have a Postgres wire connection,
+/// at the cost of a narrower slice of DDL: tables, columns, constraints, and
+/// non-constraint indexes, not yet views, triggers, custom types, or extensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum MigrationBackend {
+    #[default]
+    Cli,
+    Native,
+}
+
+impl MigrationBackend {
+    pub fn is_native(self) -> bool {
+        matches!(self, MigrationBackend::Native)
+    }
+}
+
+/// One column of a table, as it needs to appear in a `CREATE TABLE` statement
+struct ColumnDef {
+    name: String,
+    data_type: String,
+    not_null: bool,
+    default: Option<String>,
+}
+
+/// Read `schema.table`'s live columns (name, full type via `format_type`, `NOT NULL`,
+/// and `DEFAULT` expression), in ordinal order
+async fn table_columns(client: &Client, schema: &str, table: &str) -> Result<Vec<ColumnDef>> {
+    let rows = client
+        .query(
+            "SELECT a.attname,
+                    pg_catalog.format_type(a.atttypid, a.atttypmod),
+                    a.attnotnull,
+                    pg_catalog.pg_get_expr(d.adbin, d.adrelid)
+             FROM pg_catalog.pg_attribute a
+             JOIN pg_catalog.pg_class c ON c.oid = a.attrelid
+             JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+             LEFT JOIN pg_catalog.pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+             WHERE n.nspname = $1 AND c.relname = $2 AND a.attnum > 0 AND NOT a.attisdropped
+             ORDER BY a.attnum",
+            &[&schema, &table],
+        )
+        .await
+        .with_context(|| format!("Failed to read column definitions for '{}.{}'", schema, table))?;
+
+    Ok(rows
+        .iter()
+        .map(|row| ColumnDef {
+            name: row.get(0),
+            data_type: row.get(1),
+            not_null: row.get(2),
+            default: row.get(3),
+        })
+        .collect())
+}
+
+/// Read `schema.table`'s constraints (primary key, unique, foreign key, check,
+/// exclude) as `(name, definition)` pairs via `pg_get_constraintdef`, which already
+/// renders the full `CONSTRAINT`-body text (e.g. `PRIMARY KEY (id)`,
+/// `FOREIGN KEY (customer_id) REFERENCES customers(id)`)
+async fn table_constraints(client: &Client, schema: &str, table: &str) -> Result<Vec<(String, String)>> {
+    let rows = client
+        .query(
+            "SELECT conname, pg_catalog.pg_get_constraintdef(oid, true)
+             FROM pg_catalog.pg_constraint
+             WHERE conrelid = pg_catalog.format('%I.%I', $1::text, $2::text)::regclass
+             ORDER BY conname",
+            &[&schema, &table],
+        )
+        .await
+        .with_context(|| format!("Failed to read constraints for '{}.{}'", schema, table))?;
+
+    Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+}
+
+/// Read `schema.table`'s indexes as standalone `CREATE INDEX` statements, skipping
+/// any index already covered by a `constraint_names` entry (a primary key or unique
+/// constraint creates its own backing index under the same name, which
+/// [`table_constraints`] already emits as part of the `CREATE TABLE` statement)
+async fn table_indexes(
+    client: &Client,
+    schema: &str,
+    table: &str,
+    constraint_names: &[String],
+) -> Result<Vec<String>> {
+    let rows = client
+        .query(
+            "SELECT indexname, indexdef
+             FROM pg_catalog.pg_indexes
+             WHERE schemaname = $1 AND tablename = $2
+             ORDER BY indexname",
+            &[&schema, &table],
+        )
+        .await
+        .with_context(|| format!("Failed to read indexes for '{}.{}'", schema, table))?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let name: String = row.get(0);
+            if constraint_names.iter().any(|c| c == &name) {
+                return None;
+            }
+            let def: String = row.get(1);
+            Some(format!("{};", def))
+        })
+        .collect())
+}
+
+/// Build the `CREATE TABLE` statement (plus any standalone index statements) for
+/// `schema.table` - the portion of `pg_dump --schema-only`'s output
+/// [`MigrationBackend::Native`] replaces for one table.
+///
+/// # Errors
+///
+/// Returns an error if the catalog queries fail, or the table has no columns
+/// (usually meaning it doesn't exist).
+pub async fn dump_table_ddl(client: &Client, schema: &str, table: &str) -> Result<String> {
+    let columns = table_columns(client, schema, table).await?;
+    if columns.is_empty() {
+        bail!("Table '{}.{}' not found or has no columns", schema, table);
+    }
+    let constraints = table_constraints(client, schema, table).await?;
+    let constraint_names: Vec<String> = constraints.iter().map(|(name, _)| name.clone()).collect();
+    let indexes = table_indexes(client, schema, table, &constraint_names).await?;
+
+    let mut lines: Vec<String> = columns
+        .iter()
+        .map(|c| {
+            let mut line = format!("    {} {}", quote_identifier(&c.name), c.data_type);
+            if let Some(default) = &c.default {
+                line.push_str(&format!(" DEFAULT {}", default));
+            }
+            if c.not_null {
+                line.push_str(" NOT NULL");
+            }
+            line
+        })
+        .collect();
+    lines.extend(
+        constraints
+            .iter()
+            .map(|(name, def)| format!("    CONSTRAINT {} {}", quote_identifier(name), def)),
+    );
+
+    let mut ddl = format!(
+        "CREATE TABLE {} (\n{}\n);",
+        quote_qualified(schema, table),
+        lines.join(",\n")
+    );
+    for index_stmt in indexes {
+        ddl.push('\n');
+        ddl.push_str(&index_stmt);
+    }
+    Ok(ddl)
+}
+
+/// Build the concatenated `CREATE TABLE` script for every `(schema, table)` pair in
+/// `tables`, in the order given - the native-backend equivalent of
+/// [`crate::migration::dump_schema`]'s output file.
+///
+/// # Errors
+///
+/// Returns an error if introspecting any table fails.
+pub async fn dump_schema_native(client: &Client, tables: &[(String, String)]) -> Result<String> {
+    let mut statements = Vec::with_capacity(tables.len());
+    for (schema, table) in tables {
+        statements.push(dump_table_ddl(client, schema, table).await?);
+    }
+    Ok(statements.join("\n\n"))
+}
+
+/// Apply a script built by [`dump_schema_native`] to `client` in one batch - the
+/// native-backend equivalent of [`crate::migration::restore_schema`]
+///
+/// # Errors
+///
+/// Returns an error, with the same remediation hints `restore_schema`'s `psql`
+/// failure gives, if any statement in `ddl` fails to apply.
+pub async fn restore_schema_native(client: &Client, ddl: &str) -> Result<()> {
+    if ddl.trim().is_empty() {
+        return Ok(());
+    }
+
+    client.batch_execute(ddl).await.context(
+        "Schema restoration failed.\n\
+         \n\
+         Common causes:\n\
+         - Target database does not exist\n\
+         - User lacks CREATE privileges on target\n\
+         - Schema objects already exist (try dropping them first)\n\
+         - A referenced type, extension, or sequence isn't present on the target yet",
+    )
+}
+
+/// Read the source's non-system roles and render them as `CREATE ROLE` statements,
+/// the native-backend equivalent of `pg_dumpall --globals-only --no-role-passwords`
+/// (see [`crate::migration::dump_globals`]) - passwords are never read or emitted,
+/// matching that `--no-role-passwords` flag.
+///
+/// # Errors
+///
+/// Returns an error if `pg_roles` can't be queried.
+pub async fn dump_roles_native(client: &Client) -> Result<Vec<String>> {
+    let rows = client
+        .query(
+            "SELECT rolname, rolsuper, rolinherit, rolcreaterole, rolcreatedb, rolcanlogin, \
+                    rolreplication, rolconnlimit
+             FROM pg_catalog.pg_roles
+             WHERE rolname NOT LIKE 'pg\\_%'
+             ORDER BY rolname",
+            &[],
+        )
+        .await
+        .context("Failed to list source roles")?;
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let name: String = row.get(0);
+            let opts = [
+                if row.get::<_, bool>(1) { "SUPERUSER" } else { "NOSUPERUSER" },
+                if row.get::<_, bool>(2) { "INHERIT" } else { "NOINHERIT" },
+                if row.get::<_, bool>(3) { "CREATEROLE" } else { "NOCREATEROLE" },
+                if row.get::<_, bool>(4) { "CREATEDB" } else { "NOCREATEDB" },
+                if row.get::<_, bool>(5) { "LOGIN" } else { "NOLOGIN" },
+                if row.get::<_, bool>(6) { "REPLICATION" } else { "NOREPLICATION" },
+            ];
+            let conn_limit: i32 = row.get(7);
+            format!(
+                "CREATE ROLE {} WITH {} CONNECTION LIMIT {};",
+                quote_identifier(&name),
+                opts.join(" "),
+                conn_limit
+            )
+        })
+        .collect())
+}
+
+/// Apply the `CREATE ROLE` statements from [`dump_roles_native`] to `client`, the
+/// native-backend equivalent of [`crate::migration::restore_globals`] - each
+/// statement runs independently and a failure (most commonly the role already
+/// existing on the target) is logged and skipped rather than aborting the rest,
+/// matching `restore_globals`'s tolerance of "some errors are expected".
+///
+/// # Errors
+///
+/// Never returns an error itself; per-role failures are only logged.
+pub async fn restore_roles_native(client: &Client, statements: &[String]) -> Result<()> {
+    for statement in statements {
+        if let Err(err) = client.batch_execute(statement).await {
+            tracing::warn!("⚠ Failed to create role ({}): {}", statement, err);
+        }
+    }
+    Ok(())
+}
+
+/// Read `schema.table`'s non-owner grants and render them as `GRANT` statements,
+/// grouped by grantee - the native-backend equivalent of the privilege replication
+/// `dump_schema`'s `--no-privileges` flag otherwise defers (see its doc comment)
+///
+/// # Errors
+///
+/// Returns an error if `information_schema.role_table_grants` can't be queried.
+pub async fn dump_table_grants_native(client: &Client, schema: &str, table: &str) -> Result<Vec<String>> {
+    let rows = client
+        .query(
+            "SELECT grantee, privilege_type
+             FROM information_schema.role_table_grants
+             WHERE table_schema = $1 AND table_name = $2
+               AND grantee != (
+                   SELECT pg_catalog.pg_get_userbyid(c.relowner)
+                   FROM pg_catalog.pg_class c
+                   JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                   WHERE n.nspname = $1 AND c.relname = $2
+               )
+             ORDER BY grantee, privilege_type",
+            &[&schema, &table],
+        )
+        .await
+        .with_context(|| format!("Failed to read grants for '{}.{}'", schema, table))?;
+
+    let mut by_grantee: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for row in rows {
+        let grantee: String = row.get(0);
+        let privilege: String = row.get(1);
+        by_grantee.entry(grantee).or_default().push(privilege);
+    }
+
+    let qualified = quote_qualified(schema, table);
+    Ok(by_grantee
+        .into_iter()
+        .map(|(grantee, privileges)| {
+            let grantee_sql = if grantee == "PUBLIC" {
+                "PUBLIC".to_string()
+            } else {
+                quote_identifier(&grantee)
+            };
+            format!("GRANT {} ON {} TO {};", privileges.join(", "), qualified, grantee_sql)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_backend_default_is_cli() {
+        assert_eq!(MigrationBackend::default(), MigrationBackend::Cli);
+        assert!(!MigrationBackend::default().is_native());
+    }
+
+    #[test]
+    fn test_migration_backend_is_native() {
+        assert!(MigrationBackend::Native.is_native());
+        assert!(!MigrationBackend::Cli.is_native());
+    }
+}
@@ -0,0 +1,171 @@
+// ABOUTME: Concurrency-bounded whole-table copy driver with per-table retry and progress
+// ABOUTME: Complements copy_filtered_tables for migrations that don't need byte-based batching or row predicates
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use tokio::sync::Semaphore;
+
+use crate::migration::filtered::copy_one_table;
+use crate::postgres::ConnectionPool;
+use crate::utils::retry_with_backoff;
+
+/// Outcome of a single table in a [`copy_tables_parallel`] run
+pub struct TableCopyOutcome {
+    pub qualified_name: String,
+    /// `None` on success; the last retry's error if the table never copied
+    pub error: Option<anyhow::Error>,
+}
+
+/// Summary returned by [`copy_tables_parallel`]
+pub struct CopySummary {
+    pub results: Vec<TableCopyOutcome>,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub elapsed: Duration,
+}
+
+/// Copies `tables` (schema-qualified names, see
+/// [`crate::migration::TableInfo::qualified_name`]) from source to target
+/// concurrently, bounded to `concurrency` connections on each side via a
+/// [`ConnectionPool`] pair and a matching [`Semaphore`].
+///
+/// Each table's `COPY` is wrapped in [`retry_with_backoff`] so a transient failure -
+/// a dropped connection, a brief server hiccup - retries only that table, rather than
+/// failing (or needlessly re-copying) the whole migration. A table that still fails
+/// after retries is recorded in the returned [`CopySummary`] instead of aborting the
+/// run, so a handful of problem tables don't block the rest of a large migration from
+/// completing; `Self::results` gives callers the granular per-table detail needed to
+/// retry just the failures afterward.
+///
+/// Unlike [`crate::migration::copy_filtered_tables`], this doesn't bin-pack by
+/// estimated table size or support per-table row predicates - tables are handed out
+/// from a plain queue, one per free worker slot, which is enough when every table is
+/// copied whole.
+///
+/// # Errors
+///
+/// Returns an error if a connection pool can't be established to either database.
+/// Per-table `COPY` failures are reported in the returned summary instead.
+pub async fn copy_tables_parallel(
+    source_url: &str,
+    target_url: &str,
+    tables: &[String],
+    concurrency: usize,
+) -> Result<CopySummary> {
+    let started = Instant::now();
+
+    if tables.is_empty() {
+        return Ok(CopySummary {
+            results: Vec::new(),
+            succeeded: 0,
+            failed: 0,
+            elapsed: started.elapsed(),
+        });
+    }
+
+    let concurrency = concurrency.max(1).min(tables.len());
+    let total = tables.len();
+
+    tracing::info!(
+        "Copying {} table(s) using {} worker(s)",
+        total,
+        concurrency
+    );
+
+    let source_pool = ConnectionPool::new(source_url, concurrency).await?;
+    let target_pool = ConnectionPool::new(target_url, concurrency).await?;
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let completed = Arc::new(AtomicUsize::new(0));
+
+    let copies = tables.iter().enumerate().map(|(idx, qualified_name)| {
+        let semaphore = Arc::clone(&semaphore);
+        let completed = Arc::clone(&completed);
+        let source_client = source_pool.client(idx);
+        let target_client = target_pool.client(idx);
+
+        async move {
+            let _permit = semaphore
+                .acquire()
+                .await
+                .expect("semaphore is never closed while copies are in flight");
+
+            let result = retry_with_backoff(
+                || copy_one_table(source_client, target_client, qualified_name, ""),
+                3,
+                Duration::from_secs(1),
+                is_transient_copy_error,
+            )
+            .await;
+
+            let done = completed.fetch_add(1, Ordering::SeqCst) + 1;
+            match &result {
+                Ok(()) => tracing::info!("[{}/{}] copied '{}'", done, total, qualified_name),
+                Err(e) => {
+                    tracing::warn!("[{}/{}] failed to copy '{}': {}", done, total, qualified_name, e)
+                }
+            }
+
+            TableCopyOutcome {
+                qualified_name: qualified_name.clone(),
+                error: result.err(),
+            }
+        }
+    });
+
+    let results = futures::future::join_all(copies).await;
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    let succeeded = results.len() - failed;
+
+    tracing::info!(
+        "Copied {}/{} table(s) successfully in {:.1}s ({} failed)",
+        succeeded,
+        total,
+        started.elapsed().as_secs_f64(),
+        failed
+    );
+
+    Ok(CopySummary {
+        results,
+        succeeded,
+        failed,
+        elapsed: started.elapsed(),
+    })
+}
+
+/// Whether a [`copy_one_table`] failure is worth retrying: a dropped or reset
+/// connection and timeouts are transient; anything else (permission denied, a
+/// missing table, a type mismatch) fails the same way on every attempt
+fn is_transient_copy_error(err: &anyhow::Error) -> bool {
+    let message = err
+        .chain()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join(": ")
+        .to_lowercase();
+
+    message.contains("connection closed")
+        || message.contains("broken pipe")
+        || message.contains("reset by peer")
+        || message.contains("timed out")
+        || message.contains("server closed the connection unexpectedly")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_transient_copy_error_matches_connection_failures() {
+        let err = anyhow::anyhow!("Failed to stream rows for 'orders': connection closed");
+        assert!(is_transient_copy_error(&err));
+    }
+
+    #[test]
+    fn test_is_transient_copy_error_rejects_permanent_failures() {
+        let err = anyhow::anyhow!("Failed to start COPY OUT for 'orders': permission denied");
+        assert!(!is_transient_copy_error(&err));
+    }
+}
@@ -0,0 +1,83 @@
+// ABOUTME: Progress reporting types for init's data-copy phase
+// ABOUTME: Lets callers observe running bytes/tables totals instead of a single static log line
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Which step of a single database's data copy a [`ReplicationProgress`] snapshot
+/// describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicationPhase {
+    /// Running `pg_dump --data-only` against the source
+    DumpingData,
+    /// Running `pg_restore` of the data-only dump into the target
+    RestoringData,
+    /// Copying filtered tables directly via `COPY`, bypassing pg_dump/pg_restore
+    CopyingTables,
+}
+
+impl std::fmt::Display for ReplicationPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ReplicationPhase::DumpingData => "dumping data",
+            ReplicationPhase::RestoringData => "restoring data",
+            ReplicationPhase::CopyingTables => "copying filtered tables",
+        })
+    }
+}
+
+/// A running snapshot of how far one database's data copy has gotten, passed to a
+/// [`ProgressCallback`] on a timer so a caller can refine its own ETA instead of
+/// getting one static "Dumping data for 'x'..." line for a multi-hundred-GB database.
+///
+/// `bytes_total` is `None` when no size estimate is available to compare against
+/// (e.g. [`super::estimate_database_sizes`] wasn't run, or the phase is
+/// [`ReplicationPhase::CopyingTables`], which has no byte total of its own).
+#[derive(Debug, Clone)]
+pub struct ReplicationProgress {
+    pub database: String,
+    pub phase: ReplicationPhase,
+    pub bytes_done: u64,
+    pub bytes_total: Option<u64>,
+    pub tables_done: usize,
+    pub tables_total: usize,
+}
+
+/// Callback invoked with a [`ReplicationProgress`] snapshot as a database's data copy
+/// advances. Wrapped in `Arc` rather than `Box` so the same callback can be cloned
+/// into every database's concurrent replication task (see `max_parallel_databases`).
+pub type ProgressCallback = Arc<dyn Fn(&ReplicationProgress) + Send + Sync>;
+
+/// Default [`ProgressCallback`] used when [`crate::commands::init::init`] isn't given
+/// one: a terminal bar per database, grouped under one [`MultiProgress`] so several
+/// databases replicating concurrently each get their own line instead of clobbering
+/// one another.
+pub fn terminal_progress_callback() -> ProgressCallback {
+    let multi = MultiProgress::new();
+    let bars: Mutex<HashMap<String, ProgressBar>> = Mutex::new(HashMap::new());
+
+    Arc::new(move |progress: &ReplicationProgress| {
+        let mut bars = bars.lock().unwrap();
+        let bar = bars.entry(progress.database.clone()).or_insert_with(|| {
+            let bar = multi.add(ProgressBar::new(progress.bytes_total.unwrap_or(0)));
+            bar.set_style(
+                ProgressStyle::default_bar()
+                    .template("[{elapsed_precise}] {prefix:.bold} {bar:30.cyan/blue} {bytes}/{total_bytes} {msg}")
+                    .unwrap()
+                    .progress_chars("##-"),
+            );
+            bar.set_prefix(progress.database.clone());
+            bar
+        });
+
+        if let Some(total) = progress.bytes_total {
+            bar.set_length(total);
+        }
+        bar.set_position(progress.bytes_done);
+        bar.set_message(format!(
+            "{} ({}/{} tables)",
+            progress.phase, progress.tables_done, progress.tables_total
+        ));
+    })
+}
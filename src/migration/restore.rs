@@ -1,11 +1,21 @@
 // ABOUTME: Wrapper for psql and pg_restore to import database objects
 // ABOUTME: Restores global objects, schema, and data to target
 
+use crate::neon_http::NeonHttpExecutor;
+use crate::ssh_tunnel::SshTunnelConfig;
 use anyhow::{bail, Context, Result};
 use std::process::{Command, Stdio};
 
 /// Restore global objects using psql
-pub async fn restore_globals(target_url: &str, input_path: &str) -> Result<()> {
+///
+/// When `ssh_tunnel` is set, the connection is routed through an `ssh -L`
+/// tunnel instead of connecting to `target_url`'s host directly (see
+/// [`crate::ssh_tunnel`]).
+pub async fn restore_globals(
+    target_url: &str,
+    input_path: &str,
+    ssh_tunnel: Option<&SshTunnelConfig>,
+) -> Result<()> {
     tracing::info!("Restoring global objects from {}", input_path);
 
     // Parse URL and create .pgpass file for secure authentication
@@ -13,17 +23,19 @@ pub async fn restore_globals(target_url: &str, input_path: &str) -> Result<()> {
         .with_context(|| format!("Failed to parse target URL: {}", target_url))?;
     let pgpass = crate::utils::PgPassFile::new(&parts)
         .context("Failed to create .pgpass file for authentication")?;
+    let (host, port, _tunnel_guard) = crate::ssh_tunnel::resolve_connect_target(ssh_tunnel, &parts)?;
 
     let mut cmd = Command::new("psql");
     cmd.arg("--host")
-        .arg(&parts.host)
+        .arg(&host)
         .arg("--port")
-        .arg(parts.port.to_string())
+        .arg(port.to_string())
         .arg("--dbname")
         .arg(&parts.database)
         .arg(format!("--file={}", input_path))
         .arg("--quiet")
         .env("PGPASSFILE", pgpass.path())
+        .envs(parts.to_pg_env_vars())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit());
 
@@ -49,29 +61,69 @@ pub async fn restore_globals(target_url: &str, input_path: &str) -> Result<()> {
     Ok(())
 }
 
-/// Restore schema using psql
-pub async fn restore_schema(target_url: &str, input_path: &str) -> Result<()> {
+/// Restore schema using psql, or Neon's serverless SQL-over-HTTP API for a
+/// `*.neon.tech` target
+///
+/// A Neon target is applied by POSTing each statement in `input_path` to the
+/// endpoint's `/sql` route (see [`NeonHttpExecutor`]), which works in
+/// sandboxed environments where spawning `psql` isn't possible and removes
+/// the dependency on a locally installed client for this phase. Any other
+/// target falls back to the regular `psql` subprocess below.
+///
+/// When `single_transaction` is set and the target isn't a Neon HTTP
+/// endpoint, `--single-transaction` is passed to `psql` so the whole DDL
+/// script either commits or rolls back as one unit, instead of leaving a
+/// half-applied schema behind on a mid-script failure. This has no effect on
+/// the Neon HTTP path, which has no equivalent transaction wrapper.
+///
+/// When `ssh_tunnel` is set, the (non-Neon-HTTP) connection is routed through
+/// an `ssh -L` tunnel instead of connecting to `target_url`'s host directly
+/// (see [`crate::ssh_tunnel`]).
+pub async fn restore_schema(
+    target_url: &str,
+    input_path: &str,
+    single_transaction: bool,
+    ssh_tunnel: Option<&SshTunnelConfig>,
+) -> Result<()> {
     tracing::info!("Restoring schema from {}", input_path);
 
     // Parse URL and create .pgpass file for secure authentication
     let parts = crate::utils::parse_postgres_url(target_url)
         .with_context(|| format!("Failed to parse target URL: {}", target_url))?;
+
+    if let Some(executor) = NeonHttpExecutor::from_parts(&parts)? {
+        let script = std::fs::read_to_string(input_path)
+            .with_context(|| format!("Failed to read schema file '{}'", input_path))?;
+        executor
+            .execute_script(&script)
+            .await
+            .context("Schema restoration via Neon serverless SQL endpoint failed")?;
+        tracing::info!("✓ Schema restored successfully via Neon serverless SQL endpoint");
+        return Ok(());
+    }
+
     let pgpass = crate::utils::PgPassFile::new(&parts)
         .context("Failed to create .pgpass file for authentication")?;
+    let (host, port, _tunnel_guard) = crate::ssh_tunnel::resolve_connect_target(ssh_tunnel, &parts)?;
 
     let mut cmd = Command::new("psql");
     cmd.arg("--host")
-        .arg(&parts.host)
+        .arg(&host)
         .arg("--port")
-        .arg(parts.port.to_string())
+        .arg(port.to_string())
         .arg("--dbname")
         .arg(&parts.database)
         .arg(format!("--file={}", input_path))
         .arg("--quiet")
         .env("PGPASSFILE", pgpass.path())
+        .envs(parts.to_pg_env_vars())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit());
 
+    if single_transaction {
+        cmd.arg("--single-transaction");
+    }
+
     // Add username if specified
     if let Some(user) = &parts.user {
         cmd.arg("--username").arg(user);
@@ -86,6 +138,18 @@ pub async fn restore_schema(target_url: &str, input_path: &str) -> Result<()> {
     )?;
 
     if !status.success() {
+        if single_transaction {
+            bail!(
+                "Schema restoration failed; --single-transaction rolled back the whole script, \
+                 so nothing was applied to the target. The run can be retried cleanly.\n\
+                 \n\
+                 Common causes:\n\
+                 - Target database does not exist\n\
+                 - User lacks CREATE privileges on target\n\
+                 - Version incompatibility between source and target\n\
+                 - Syntax errors in dump file"
+            );
+        }
         bail!(
             "Schema restoration failed.\n\
              \n\
@@ -109,12 +173,34 @@ pub async fn restore_schema(target_url: &str, input_path: &str) -> Result<()> {
 /// - Automatic decompression of compressed dump files
 /// - Optimized for directory format dumps created by dump_data()
 ///
-/// The number of parallel jobs is automatically determined based on available CPU cores.
-pub async fn restore_data(target_url: &str, input_path: &str) -> Result<()> {
-    // Determine optimal number of parallel jobs (number of CPUs, capped at 8)
-    let num_cpus = std::thread::available_parallelism()
-        .map(|n| n.get().min(8))
-        .unwrap_or(4);
+/// The number of parallel jobs defaults to the available CPU cores (capped at 8)
+/// unless `jobs` overrides it, e.g. via the `init --jobs` flag.
+///
+/// When `single_transaction` is set, `--single-transaction` is passed to
+/// `pg_restore` so the whole data load either commits or rolls back as one
+/// unit instead of leaving a partially-populated database on failure.
+/// `pg_restore` requires `--jobs=1` alongside `--single-transaction`, so
+/// `jobs` (and the CPU-based default) is ignored in that case.
+///
+/// When `ssh_tunnel` is set, the connection is routed through an `ssh -L`
+/// tunnel instead of connecting to `target_url`'s host directly (see
+/// [`crate::ssh_tunnel`]).
+pub async fn restore_data(
+    target_url: &str,
+    input_path: &str,
+    jobs: Option<usize>,
+    single_transaction: bool,
+    ssh_tunnel: Option<&SshTunnelConfig>,
+) -> Result<()> {
+    let num_cpus = if single_transaction {
+        1
+    } else {
+        jobs.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get().min(8))
+                .unwrap_or(4)
+        })
+    };
 
     tracing::info!(
         "Restoring data from {} (parallel={}, format=directory)",
@@ -127,24 +213,30 @@ pub async fn restore_data(target_url: &str, input_path: &str) -> Result<()> {
         .with_context(|| format!("Failed to parse target URL: {}", target_url))?;
     let pgpass = crate::utils::PgPassFile::new(&parts)
         .context("Failed to create .pgpass file for authentication")?;
+    let (host, port, _tunnel_guard) = crate::ssh_tunnel::resolve_connect_target(ssh_tunnel, &parts)?;
 
     let mut cmd = Command::new("pg_restore");
     cmd.arg("--data-only")
         .arg("--no-owner")
         .arg(format!("--jobs={}", num_cpus)) // Parallel restore jobs
         .arg("--host")
-        .arg(&parts.host)
+        .arg(&host)
         .arg("--port")
-        .arg(parts.port.to_string())
+        .arg(port.to_string())
         .arg("--dbname")
         .arg(&parts.database)
         .arg("--format=directory") // Directory format
         .arg("--verbose") // Show progress
         .arg(input_path)
         .env("PGPASSFILE", pgpass.path())
+        .envs(parts.to_pg_env_vars())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit());
 
+    if single_transaction {
+        cmd.arg("--single-transaction");
+    }
+
     // Add username if specified
     if let Some(user) = &parts.user {
         cmd.arg("--username").arg(user);
@@ -159,6 +251,19 @@ pub async fn restore_data(target_url: &str, input_path: &str) -> Result<()> {
     )?;
 
     if !status.success() {
+        if single_transaction {
+            bail!(
+                "Data restoration failed; --single-transaction rolled back the whole load, so \
+                 nothing was applied to the target. The run can be retried cleanly.\n\
+                 \n\
+                 Common causes:\n\
+                 - Foreign key constraint violations\n\
+                 - User lacks INSERT privileges on target tables\n\
+                 - Disk space issues on target\n\
+                 - Data type mismatches\n\
+                 - Input directory is not a valid pg_dump directory format"
+            );
+        }
         bail!(
             "Data restoration failed.\n\
              \n\
@@ -195,12 +300,12 @@ mod tests {
         let dump_file = dir.path().join("globals.sql");
 
         // Dump from source
-        dump::dump_globals(&source_url, dump_file.to_str().unwrap())
+        dump::dump_globals(&source_url, dump_file.to_str().unwrap(), None)
             .await
             .unwrap();
 
         // Restore to target
-        let result = restore_globals(&target_url, dump_file.to_str().unwrap()).await;
+        let result = restore_globals(&target_url, dump_file.to_str().unwrap(), None).await;
         assert!(result.is_ok());
     }
 }
@@ -2,6 +2,7 @@
 // ABOUTME: Discovers databases, tables, and objects that need migration
 
 use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
 use tokio_postgres::Client;
 
 #[derive(Debug, Clone)]
@@ -17,6 +18,19 @@ pub struct TableInfo {
     pub row_count_estimate: i64,
 }
 
+impl TableInfo {
+    /// The name used for filter matching: bare table name for `public`, or
+    /// `schema.table` otherwise - matches the format `--include-tables` /
+    /// `--exclude-tables` patterns are written against
+    pub fn qualified_name(&self) -> String {
+        if self.schema == "public" {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.schema, self.name)
+        }
+    }
+}
+
 /// List all non-system databases in the cluster
 pub async fn list_databases(client: &Client) -> Result<Vec<DatabaseInfo>> {
     let rows = client
@@ -73,6 +87,503 @@ pub async fn list_tables(client: &Client) -> Result<Vec<TableInfo>> {
     Ok(tables)
 }
 
+/// List the non-system schemas present in the current database
+///
+/// Excludes `pg_catalog`, `information_schema`, `pg_toast`, and temp-table schemas
+/// (`pg_temp_*`/`pg_toast_temp_*`), leaving only schemas a user might plausibly want to
+/// replicate.
+pub async fn list_schemas(client: &Client) -> Result<Vec<String>> {
+    let rows = client
+        .query(
+            "SELECT schema_name FROM information_schema.schemata
+             WHERE schema_name NOT IN ('pg_catalog', 'information_schema')
+               AND schema_name NOT LIKE 'pg_toast%'
+               AND schema_name NOT LIKE 'pg_temp%'
+             ORDER BY schema_name",
+            &[],
+        )
+        .await
+        .context("Failed to list schemas")?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// A table found ineligible for logical replication by [`check_replication_eligibility`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplicationEligibilityIssue {
+    pub schema: String,
+    pub table: String,
+    pub reason: String,
+}
+
+impl ReplicationEligibilityIssue {
+    /// The qualified `schema.table` name this issue is about
+    pub fn qualified_name(&self) -> String {
+        format!("{}.{}", self.schema, self.table)
+    }
+}
+
+/// Check each of `tables` for a usable logical-replication identity: a primary key, or
+/// an explicitly configured `REPLICA IDENTITY FULL`/`REPLICA IDENTITY USING INDEX`.
+/// Without one, PostgreSQL can emit `INSERT`s over a logical replication slot but
+/// refuses `UPDATE`/`DELETE` once a subscriber tries to apply them.
+///
+/// Reads `pg_class.relreplident` together with `pg_index.indisprimary`, since
+/// `relreplident = 'd'` (the default) only guarantees a usable identity if a primary
+/// key actually exists; a table with `relreplident = 'n'` (`REPLICA IDENTITY NOTHING`)
+/// is ineligible regardless of its keys.
+///
+/// Tables not found in `pg_class` (e.g. already dropped) are silently skipped rather
+/// than reported as a violation, since there's nothing left to flag.
+///
+/// # Errors
+///
+/// Returns an error if the catalog query fails for any table.
+pub async fn check_replication_eligibility(
+    client: &Client,
+    tables: &[(String, String)],
+) -> Result<Vec<ReplicationEligibilityIssue>> {
+    let mut issues = Vec::new();
+
+    for (schema, table) in tables {
+        let row = client
+            .query_opt(
+                "SELECT c.relreplident,
+                        EXISTS (
+                            SELECT 1 FROM pg_index i
+                            WHERE i.indrelid = c.oid AND i.indisprimary
+                        ) AS has_primary_key
+                 FROM pg_class c
+                 JOIN pg_namespace n ON n.oid = c.relnamespace
+                 WHERE n.nspname = $1 AND c.relname = $2",
+                &[schema, table],
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to check replica identity for '{}.{}'",
+                    schema, table
+                )
+            })?;
+
+        let Some(row) = row else { continue };
+
+        let relreplident: i8 = row.get(0);
+        let has_primary_key: bool = row.get(1);
+
+        let reason = match relreplident as u8 as char {
+            'd' if !has_primary_key => Some(
+                "no primary key and REPLICA IDENTITY DEFAULT — updates/deletes will fail"
+                    .to_string(),
+            ),
+            'n' => Some("REPLICA IDENTITY NOTHING — updates/deletes will fail".to_string()),
+            _ => None,
+        };
+
+        if let Some(reason) = reason {
+            issues.push(ReplicationEligibilityIssue {
+                schema: schema.clone(),
+                table: table.clone(),
+                reason,
+            });
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Whether a [`ReplicationGapIssue`] should block migration or just be surfaced as a
+/// follow-up action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicationGapSeverity {
+    /// Worth fixing up after cutover, but doesn't block it
+    Warning,
+    /// The object won't arrive on the target via logical replication at all
+    Error,
+}
+
+/// A single object or column found by [`scan_replication_gaps`] that logical
+/// replication can't carry, along with the concrete follow-up action
+#[derive(Debug, Clone)]
+pub struct ReplicationGapIssue {
+    pub severity: ReplicationGapSeverity,
+    /// Qualified name of the affected object (`schema.table` or `schema.table.column`)
+    pub object: String,
+    pub message: String,
+}
+
+/// Result of [`scan_replication_gaps`]
+#[derive(Debug, Clone, Default)]
+pub struct ReplicationGapReport {
+    pub issues: Vec<ReplicationGapIssue>,
+}
+
+impl ReplicationGapReport {
+    /// True if any issue in this report is [`ReplicationGapSeverity::Error`]
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == ReplicationGapSeverity::Error)
+    }
+}
+
+/// Scan the current database for objects logical replication silently leaves behind,
+/// so users learn about them before cutover instead of after.
+///
+/// Checks performed (all [`ReplicationGapSeverity::Warning`] unless noted):
+/// - **Sequences**: their current value is never replicated, so every sequence's
+///   `last_value` is reported as a reminder to re-sync it (e.g. with `setval`) after
+///   cutover; this includes the sequences backing `serial`/`IDENTITY` columns.
+/// - **Large objects** (`pg_largeobject`): not carried by logical replication at all -
+///   [`ReplicationGapSeverity::Error`] if any exist, since there is no post-cutover fix
+///   short of migrating them separately (e.g. `pg_dump --blobs` or `lo_export`).
+/// - **Materialized views**: their contents aren't replicated and must be rebuilt with
+///   `REFRESH MATERIALIZED VIEW` on the target after cutover.
+/// - **Unlogged tables**: excluded from logical replication entirely -
+///   [`ReplicationGapSeverity::Error`], since they arrive empty and stay empty.
+/// - **Generated/identity columns** on a filtered table: a generated column's values
+///   aren't published unless `publish_generated_columns` is enabled on the publication;
+///   an identity column's backing sequence has the same unsynced-value problem as any
+///   other sequence.
+/// - **Partitioned parents** among the filtered tables: need
+///   `CREATE PUBLICATION ... WITH (publish_via_partition_root)`, or changes applied to
+///   a partition directly won't replicate as expected.
+///
+/// `filtered_tables` scopes the table/column-level checks (unlogged tables,
+/// generated/identity columns, partitioned parents) to what's actually being
+/// replicated; sequences, large objects, and materialized views are database-wide
+/// concerns and are always reported in full.
+///
+/// # Errors
+///
+/// Returns an error if any of the underlying catalog queries fail.
+pub async fn scan_replication_gaps(
+    client: &Client,
+    filtered_tables: &[(String, String)],
+) -> Result<ReplicationGapReport> {
+    let filtered: HashSet<(&str, &str)> = filtered_tables
+        .iter()
+        .map(|(schema, table)| (schema.as_str(), table.as_str()))
+        .collect();
+    let mut issues = Vec::new();
+
+    let sequences = client
+        .query(
+            "SELECT schemaname, sequencename, last_value FROM pg_sequences
+             ORDER BY schemaname, sequencename",
+            &[],
+        )
+        .await
+        .context("Failed to list sequences")?;
+    for row in &sequences {
+        let schema: String = row.get(0);
+        let name: String = row.get(1);
+        let last_value: Option<i64> = row.get(2);
+        issues.push(ReplicationGapIssue {
+            severity: ReplicationGapSeverity::Warning,
+            object: format!("{}.{}", schema, name),
+            message: format!(
+                "Sequence '{}.{}' (last_value: {}) is not replicated; re-sync it on the \
+                 target after cutover, e.g. SELECT setval('{}.{}', <source value>);",
+                schema,
+                name,
+                last_value
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "unset".to_string()),
+                schema,
+                name
+            ),
+        });
+    }
+
+    let large_object_count: i64 = client
+        .query_one("SELECT count(DISTINCT loid) FROM pg_largeobject", &[])
+        .await
+        .context("Failed to count large objects")?
+        .get(0);
+    if large_object_count > 0 {
+        issues.push(ReplicationGapIssue {
+            severity: ReplicationGapSeverity::Error,
+            object: "pg_largeobject".to_string(),
+            message: format!(
+                "{} large object(s) found; logical replication does not carry large \
+                 objects at all. Migrate them separately (e.g. pg_dump --blobs or \
+                 lo_export/lo_import) before cutover.",
+                large_object_count
+            ),
+        });
+    }
+
+    let materialized_views = client
+        .query(
+            "SELECT schemaname, matviewname FROM pg_matviews ORDER BY schemaname, matviewname",
+            &[],
+        )
+        .await
+        .context("Failed to list materialized views")?;
+    for row in &materialized_views {
+        let schema: String = row.get(0);
+        let name: String = row.get(1);
+        issues.push(ReplicationGapIssue {
+            severity: ReplicationGapSeverity::Warning,
+            object: format!("{}.{}", schema, name),
+            message: format!(
+                "Materialized view '{}.{}' contents are not replicated; run REFRESH \
+                 MATERIALIZED VIEW on the target after cutover.",
+                schema, name
+            ),
+        });
+    }
+
+    let unlogged_tables = client
+        .query(
+            "SELECT n.nspname, c.relname
+             FROM pg_class c
+             JOIN pg_namespace n ON n.oid = c.relnamespace
+             WHERE c.relkind = 'r' AND c.relpersistence = 'u'
+             ORDER BY n.nspname, c.relname",
+            &[],
+        )
+        .await
+        .context("Failed to list unlogged tables")?;
+    for row in &unlogged_tables {
+        let schema: String = row.get(0);
+        let name: String = row.get(1);
+        if !filtered.contains(&(schema.as_str(), name.as_str())) {
+            continue;
+        }
+        issues.push(ReplicationGapIssue {
+            severity: ReplicationGapSeverity::Error,
+            object: format!("{}.{}", schema, name),
+            message: format!(
+                "Table '{}.{}' is UNLOGGED; unlogged tables are excluded from logical \
+                 replication entirely and will arrive empty. Convert it with ALTER \
+                 TABLE {}.{} SET LOGGED before migrating if its data is needed.",
+                schema, name, schema, name
+            ),
+        });
+    }
+
+    let generated_columns = client
+        .query(
+            "SELECT n.nspname, c.relname, a.attname,
+                    a.attgenerated = 's' AS is_generated,
+                    a.attidentity != '' AS is_identity
+             FROM pg_attribute a
+             JOIN pg_class c ON c.oid = a.attrelid
+             JOIN pg_namespace n ON n.oid = c.relnamespace
+             WHERE a.attnum > 0 AND NOT a.attisdropped
+               AND c.relkind IN ('r', 'p')
+               AND (a.attgenerated = 's' OR a.attidentity != '')
+             ORDER BY n.nspname, c.relname, a.attname",
+            &[],
+        )
+        .await
+        .context("Failed to list generated/identity columns")?;
+    for row in &generated_columns {
+        let schema: String = row.get(0);
+        let table: String = row.get(1);
+        let column: String = row.get(2);
+        let is_generated: bool = row.get(3);
+        if !filtered.contains(&(schema.as_str(), table.as_str())) {
+            continue;
+        }
+        let message = if is_generated {
+            format!(
+                "Column '{}.{}.{}' is a generated column; it's excluded from the \
+                 publication's replicated columns unless publish_generated_columns is \
+                 enabled on it.",
+                schema, table, column
+            )
+        } else {
+            format!(
+                "Column '{}.{}.{}' is an identity column; its backing sequence's \
+                 current value is not replicated - re-sync it on the target after \
+                 cutover, e.g. ALTER TABLE {}.{} ALTER COLUMN {} RESTART WITH <source value>;",
+                schema, table, column, schema, table, column
+            )
+        };
+        issues.push(ReplicationGapIssue {
+            severity: ReplicationGapSeverity::Warning,
+            object: format!("{}.{}.{}", schema, table, column),
+            message,
+        });
+    }
+
+    let partitioned_tables = client
+        .query(
+            "SELECT n.nspname, c.relname
+             FROM pg_class c
+             JOIN pg_namespace n ON n.oid = c.relnamespace
+             WHERE c.relkind = 'p'
+             ORDER BY n.nspname, c.relname",
+            &[],
+        )
+        .await
+        .context("Failed to list partitioned tables")?;
+    for row in &partitioned_tables {
+        let schema: String = row.get(0);
+        let name: String = row.get(1);
+        if !filtered.contains(&(schema.as_str(), name.as_str())) {
+            continue;
+        }
+        issues.push(ReplicationGapIssue {
+            severity: ReplicationGapSeverity::Warning,
+            object: format!("{}.{}", schema, name),
+            message: format!(
+                "Table '{}.{}' is a partitioned table; publish it with CREATE \
+                 PUBLICATION ... WITH (publish_via_partition_root), or changes applied \
+                 directly to a partition instead of through the root won't replicate \
+                 as expected.",
+                schema, name
+            ),
+        });
+    }
+
+    Ok(ReplicationGapReport { issues })
+}
+
+/// A single problem found while reconciling a source and target schema by [`diff_schema`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaIssue {
+    /// Present on the source but not found on the target
+    MissingOnTarget { table: String },
+    /// Present on the target but not found on the source
+    ExtraOnTarget { table: String },
+    /// Present on both sides, but the column's type, nullability, or default differs
+    ColumnMismatch {
+        table: String,
+        column: String,
+        source_type: String,
+        target_type: String,
+    },
+}
+
+/// Schema reconciliation between a source and target database, as built by [`diff_schema`]
+#[derive(Debug, Clone, Default)]
+pub struct SchemaDiff {
+    pub issues: Vec<SchemaIssue>,
+}
+
+impl SchemaDiff {
+    /// True if no issues were found
+    pub fn is_compatible(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Describe a column's type, nullability, and default as a single comparable string, so
+/// [`diff_schema`] can flag any of the three drifting independently of the others
+fn describe_column(data_type: &str, is_nullable: bool, column_default: &Option<String>) -> String {
+    format!(
+        "{} {} default={}",
+        data_type,
+        if is_nullable { "NULL" } else { "NOT NULL" },
+        column_default.as_deref().unwrap_or("none")
+    )
+}
+
+/// Fetch `schema.table.column -> description` for every user table in a database, for use by
+/// [`diff_schema`]
+async fn describe_columns(client: &Client) -> Result<HashMap<(String, String, String), String>> {
+    let rows = client
+        .query(
+            "SELECT table_schema, table_name, column_name, data_type, is_nullable, column_default
+             FROM information_schema.columns
+             WHERE table_schema NOT IN ('pg_catalog', 'information_schema')
+             ORDER BY table_schema, table_name, ordinal_position",
+            &[],
+        )
+        .await
+        .context("Failed to query column descriptions")?;
+
+    let columns = rows
+        .iter()
+        .map(|row| {
+            let schema: String = row.get(0);
+            let table: String = row.get(1);
+            let column: String = row.get(2);
+            let data_type: String = row.get(3);
+            let is_nullable: String = row.get(4);
+            let column_default: Option<String> = row.get(5);
+            let description = describe_column(&data_type, is_nullable == "YES", &column_default);
+
+            ((schema, table, column), description)
+        })
+        .collect();
+
+    Ok(columns)
+}
+
+/// Reconcile the tables and columns visible on `source` against what's visible on `target`,
+/// catching schema drift (tables added/dropped, columns changed) before it surfaces as a
+/// confusing subscription or replication error mid-sync.
+///
+/// For each table on `source`, checks:
+/// - [`SchemaIssue::MissingOnTarget`] if the table doesn't exist on `target`
+/// - [`SchemaIssue::ColumnMismatch`] for any column whose type, nullability, or default
+///   differs between `source` and `target` (see [`describe_column`])
+///
+/// Also reports [`SchemaIssue::ExtraOnTarget`] for tables on `target` that don't exist on
+/// `source`, since an extra table can still indicate the two schemas have drifted apart.
+///
+/// # Errors
+///
+/// Returns an error if listing tables or columns on either database fails.
+pub async fn diff_schema(source: &Client, target: &Client) -> Result<SchemaDiff> {
+    let source_tables = list_tables(source).await.context("Failed to list source tables")?;
+    let target_tables = list_tables(target).await.context("Failed to list target tables")?;
+
+    let source_names: std::collections::HashSet<(String, String)> = source_tables
+        .iter()
+        .map(|t| (t.schema.clone(), t.name.clone()))
+        .collect();
+    let target_names: std::collections::HashSet<(String, String)> = target_tables
+        .iter()
+        .map(|t| (t.schema.clone(), t.name.clone()))
+        .collect();
+
+    let mut issues = Vec::new();
+
+    for (schema, name) in source_names.difference(&target_names) {
+        issues.push(SchemaIssue::MissingOnTarget {
+            table: format!("{}.{}", schema, name),
+        });
+    }
+
+    for (schema, name) in target_names.difference(&source_names) {
+        issues.push(SchemaIssue::ExtraOnTarget {
+            table: format!("{}.{}", schema, name),
+        });
+    }
+
+    let source_columns = describe_columns(source).await?;
+    let target_columns = describe_columns(target).await?;
+
+    for (schema, name) in source_names.intersection(&target_names) {
+        for ((col_schema, col_table, column), source_description) in &source_columns {
+            if col_schema != schema || col_table != name {
+                continue;
+            }
+
+            let key = (col_schema.clone(), col_table.clone(), column.clone());
+            match target_columns.get(&key) {
+                Some(target_description) if target_description != source_description => {
+                    issues.push(SchemaIssue::ColumnMismatch {
+                        table: format!("{}.{}", schema, name),
+                        column: column.clone(),
+                        source_type: source_description.clone(),
+                        target_type: target_description.clone(),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(SchemaDiff { issues })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,6 +605,45 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn test_list_schemas() {
+        let url = std::env::var("TEST_SOURCE_URL").unwrap();
+        let client = connect(&url).await.unwrap();
+
+        let schemas = list_schemas(&client).await.unwrap();
+
+        // `public` always exists on a fresh database
+        assert!(schemas.iter().any(|s| s == "public"));
+        assert!(!schemas.iter().any(|s| s.starts_with("pg_")));
+        println!("Found {} schema(s)", schemas.len());
+        for schema in &schemas {
+            println!("  - {}", schema);
+        }
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_check_replication_eligibility() {
+        let url = std::env::var("TEST_SOURCE_URL").unwrap();
+        let client = connect(&url).await.unwrap();
+
+        let tables = list_tables(&client).await.unwrap();
+        let qualified: Vec<(String, String)> = tables
+            .iter()
+            .map(|t| (t.schema.clone(), t.name.clone()))
+            .collect();
+
+        let issues = check_replication_eligibility(&client, &qualified)
+            .await
+            .unwrap();
+
+        println!("Found {} replication eligibility issue(s)", issues.len());
+        for issue in &issues {
+            println!("  - {}: {}", issue.qualified_name(), issue.reason);
+        }
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_list_tables() {
@@ -111,4 +661,48 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_diff_schema() {
+        let source_url = std::env::var("TEST_SOURCE_URL").unwrap();
+        let target_url = std::env::var("TEST_TARGET_URL").unwrap();
+        let source_client = connect(&source_url).await.unwrap();
+        let target_client = connect(&target_url).await.unwrap();
+
+        let diff = diff_schema(&source_client, &target_client).await.unwrap();
+
+        println!("Found {} schema issue(s)", diff.issues.len());
+        for issue in &diff.issues {
+            println!("  - {:?}", issue);
+        }
+    }
+
+    #[test]
+    fn test_describe_column() {
+        assert_eq!(
+            describe_column("integer", false, &None),
+            "integer NOT NULL default=none"
+        );
+        assert_eq!(
+            describe_column("text", true, &Some("'foo'::text".to_string())),
+            "text NULL default='foo'::text"
+        );
+    }
+
+    #[test]
+    fn test_schema_diff_is_compatible_with_no_issues() {
+        let diff = SchemaDiff { issues: Vec::new() };
+        assert!(diff.is_compatible());
+    }
+
+    #[test]
+    fn test_schema_diff_is_incompatible_with_any_issue() {
+        let diff = SchemaDiff {
+            issues: vec![SchemaIssue::MissingOnTarget {
+                table: "public.users".to_string(),
+            }],
+        };
+        assert!(!diff.is_compatible());
+    }
 }
@@ -0,0 +1,124 @@
+// ABOUTME: Snapshot-consistent read helpers for verifying against a live-replicating target
+// ABOUTME: Pins every source table read to one exported snapshot; target reads get their own transaction
+
+use anyhow::{Context, Result};
+use tokio_postgres::Client;
+
+/// A point-in-time view of the source, shared across every connection that
+/// [`join_consistent_snapshot`]s it, plus the source LSN at the moment it was taken
+///
+/// Exporting a snapshot only synchronizes connections within the *same* Postgres
+/// cluster - the target is a separate cluster, so there's no way to hand it this
+/// exact MVCC snapshot. What this buys a verify pass instead: every source table is
+/// read from the identical instant, and [`lsn`](Self::lsn) lets the caller wait for
+/// the target's subscription to have applied everything up to that instant before
+/// reading the target side. That eliminates the "checksum matches but row count
+/// differs" false positives caused by writes landing mid-comparison while a
+/// multi-table verify run is still in progress - it does not, and cannot, make the
+/// target's view pinned to that exact same instant, only caught up to at least it.
+pub struct ConsistentSnapshot {
+    pub snapshot_name: String,
+    pub lsn: String,
+}
+
+/// Open a `REPEATABLE READ` transaction on `client`, export its snapshot, and
+/// capture the source's current WAL position in the same transaction
+///
+/// `client`'s transaction is left open on return; call [`end_consistent_snapshot`]
+/// once every table this snapshot covers has been read. Every other source
+/// connection used for the same verify pass should [`join_consistent_snapshot`]
+/// this snapshot before reading any table.
+///
+/// # Errors
+///
+/// Returns an error if the transaction can't be started or the snapshot can't be
+/// exported.
+pub async fn export_consistent_snapshot(client: &Client) -> Result<ConsistentSnapshot> {
+    client
+        .batch_execute("BEGIN ISOLATION LEVEL REPEATABLE READ")
+        .await
+        .context("Failed to start snapshot transaction for consistent verification")?;
+
+    let snapshot_name: String = client
+        .query_one("SELECT pg_export_snapshot()", &[])
+        .await
+        .context("Failed to export snapshot for consistent verification")?
+        .get(0);
+
+    let lsn: String = client
+        .query_one("SELECT pg_current_wal_lsn()::text", &[])
+        .await
+        .context("Failed to capture source LSN for consistent verification")?
+        .get(0);
+
+    tracing::info!(
+        "✓ Pinned source verification snapshot '{}' at LSN {}",
+        snapshot_name,
+        lsn
+    );
+
+    Ok(ConsistentSnapshot { snapshot_name, lsn })
+}
+
+/// Join an already-[`export_consistent_snapshot`]ed snapshot from another
+/// connection to the same source database, so `client` sees the identical
+/// point-in-time view
+///
+/// `client`'s transaction is left open on return; call [`end_consistent_snapshot`]
+/// once every table this connection reads has been compared.
+///
+/// # Errors
+///
+/// Returns an error if the transaction can't be started or the snapshot has
+/// already expired (e.g. because [`end_consistent_snapshot`] already ran on the
+/// connection that exported it).
+pub async fn join_consistent_snapshot(client: &Client, snapshot_name: &str) -> Result<()> {
+    client
+        .batch_execute("BEGIN ISOLATION LEVEL REPEATABLE READ")
+        .await
+        .context("Failed to start transaction to join consistent verification snapshot")?;
+
+    client
+        .batch_execute(&format!("SET TRANSACTION SNAPSHOT '{}'", snapshot_name))
+        .await
+        .context("Failed to join consistent verification snapshot")?;
+
+    Ok(())
+}
+
+/// Release a transaction opened by [`export_consistent_snapshot`] or
+/// [`join_consistent_snapshot`]
+///
+/// Call this on every connection that joined the snapshot, as soon as it's done
+/// reading tables for this verify pass.
+pub async fn end_consistent_snapshot(client: &Client) -> Result<()> {
+    client
+        .batch_execute("COMMIT")
+        .await
+        .context("Failed to commit consistent verification snapshot transaction")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::postgres::connect;
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_export_and_join_consistent_snapshot() {
+        // This test requires a source database reachable at TEST_SOURCE_URL.
+        let source_url = std::env::var("TEST_SOURCE_URL").unwrap();
+
+        let exporter = connect(&source_url).await.unwrap();
+        let snapshot = export_consistent_snapshot(&exporter).await.unwrap();
+        assert!(!snapshot.snapshot_name.is_empty());
+        assert!(!snapshot.lsn.is_empty());
+
+        let joiner = connect(&source_url).await.unwrap();
+        let result = join_consistent_snapshot(&joiner, &snapshot.snapshot_name).await;
+        assert!(result.is_ok(), "Failed to join snapshot: {:?}", result);
+
+        end_consistent_snapshot(&joiner).await.unwrap();
+        end_consistent_snapshot(&exporter).await.unwrap();
+    }
+}
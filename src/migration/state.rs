@@ -0,0 +1,321 @@
+// ABOUTME: seren_migration_state tracking table: per-database schema fingerprint,
+// ABOUTME: validation checks passed, and per-table progress markers for drift detection and resume
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use tokio_postgres::Client;
+
+/// Name of the bookkeeping table created on the target to track migration state
+const MIGRATION_STATE_TABLE: &str = "seren_migration_state";
+
+/// A stable, ordered fingerprint of a database's schema: one line per table,
+/// column, and extension (`table:schema.name`, `column:schema.table.name:type:position`,
+/// `extension:name:version`), plus the `md5` of those lines joined together.
+///
+/// Unlike [`crate::migration::fingerprint::TableFingerprint`], which tracks one table
+/// at a time for per-table resume checks, this covers an entire database in a single
+/// value, matching what `seren_migration_state` stores per database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaFingerprint {
+    pub objects: Vec<String>,
+    pub hash: String,
+}
+
+/// What's recorded on the target for one database: the schema fingerprint from the
+/// last `validate` run that found no drift, the validation checks that passed then, and which
+/// tables `init` has finished copying since
+#[derive(Debug, Clone)]
+pub struct RecordedMigrationState {
+    pub schema_hash: String,
+    pub schema_objects: Vec<String>,
+    pub checks_passed: Vec<String>,
+    pub table_progress: HashMap<String, bool>,
+}
+
+/// Create the `seren_migration_state` bookkeeping table if it doesn't already exist
+async fn ensure_migration_state_table(client: &Client) -> Result<()> {
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                db_name TEXT PRIMARY KEY,
+                schema_hash TEXT NOT NULL,
+                schema_objects TEXT NOT NULL,
+                checks_passed TEXT[] NOT NULL DEFAULT '{{}}',
+                table_progress JSONB NOT NULL DEFAULT '{{}}'::jsonb,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+            )",
+            MIGRATION_STATE_TABLE
+        ))
+        .await
+        .context("Failed to create seren_migration_state tracking table")?;
+
+    Ok(())
+}
+
+/// Compute the current database's schema fingerprint: every table, column, and
+/// installed extension, ordered so the same schema always produces the same lines
+/// (and the same hash) regardless of catalog scan order.
+///
+/// # Errors
+///
+/// Returns an error if the catalogs can't be queried.
+pub async fn compute_schema_fingerprint(client: &Client) -> Result<SchemaFingerprint> {
+    let rows = client
+        .query(
+            "SELECT line FROM (
+                SELECT format('table:%s.%s', n.nspname, c.relname) AS line
+                FROM pg_catalog.pg_class c
+                JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                WHERE c.relkind IN ('r', 'p')
+                  AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+                UNION ALL
+                SELECT format(
+                    'column:%s.%s.%s:%s:%s',
+                    n.nspname, c.relname, a.attname,
+                    pg_catalog.format_type(a.atttypid, a.atttypmod),
+                    a.attnum
+                ) AS line
+                FROM pg_catalog.pg_attribute a
+                JOIN pg_catalog.pg_class c ON c.oid = a.attrelid
+                JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                WHERE c.relkind IN ('r', 'p')
+                  AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+                  AND a.attnum > 0 AND NOT a.attisdropped
+                UNION ALL
+                SELECT format('extension:%s:%s', extname, extversion) AS line
+                FROM pg_catalog.pg_extension
+             ) objects
+             ORDER BY line",
+            &[],
+        )
+        .await
+        .context("Failed to compute schema fingerprint")?;
+
+    let objects: Vec<String> = rows.iter().map(|row| row.get(0)).collect();
+    let joined = objects.join("\n");
+    let hash_row = client
+        .query_one("SELECT md5($1)", &[&joined])
+        .await
+        .context("Failed to hash schema fingerprint")?;
+    let hash: String = hash_row.get(0);
+
+    Ok(SchemaFingerprint { objects, hash })
+}
+
+/// Record `fingerprint` and `checks_passed` for `db_name` on the target, creating
+/// `seren_migration_state` if needed. Overwrites any previously recorded fingerprint
+/// and checks, but leaves `table_progress` untouched - that's only ever updated by
+/// [`record_table_progress`].
+///
+/// # Errors
+///
+/// Returns an error if the tracking table can't be created or written to.
+pub async fn record_migration_state(
+    client: &Client,
+    db_name: &str,
+    fingerprint: &SchemaFingerprint,
+    checks_passed: &[String],
+) -> Result<()> {
+    ensure_migration_state_table(client).await?;
+
+    let schema_objects = fingerprint.objects.join("\n");
+    client
+        .execute(
+            &format!(
+                "INSERT INTO {} (db_name, schema_hash, schema_objects, checks_passed, updated_at)
+                 VALUES ($1, $2, $3, $4, now())
+                 ON CONFLICT (db_name)
+                 DO UPDATE SET schema_hash = EXCLUDED.schema_hash,
+                               schema_objects = EXCLUDED.schema_objects,
+                               checks_passed = EXCLUDED.checks_passed,
+                               updated_at = now()",
+                MIGRATION_STATE_TABLE
+            ),
+            &[&db_name, &fingerprint.hash, &schema_objects, &checks_passed],
+        )
+        .await
+        .with_context(|| format!("Failed to record migration state for '{}'", db_name))?;
+
+    Ok(())
+}
+
+/// Mark `table_name` as completed in `db_name`'s progress markers, merging into
+/// whatever's already recorded rather than overwriting the whole map - so
+/// concurrent per-table completions (e.g. from [`crate::migration::copy_filtered_tables`])
+/// don't clobber each other.
+///
+/// # Errors
+///
+/// Returns an error if the tracking table can't be created or written to.
+pub async fn record_table_progress(client: &Client, db_name: &str, table_name: &str) -> Result<()> {
+    ensure_migration_state_table(client).await?;
+
+    client
+        .execute(
+            &format!(
+                "INSERT INTO {} (db_name, schema_hash, schema_objects, table_progress, updated_at)
+                 VALUES ($1, '', '', jsonb_build_object($2::text, true), now())
+                 ON CONFLICT (db_name)
+                 DO UPDATE SET table_progress = {}.table_progress || jsonb_build_object($2::text, true),
+                               updated_at = now()",
+                MIGRATION_STATE_TABLE, MIGRATION_STATE_TABLE
+            ),
+            &[&db_name, &table_name],
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to record table progress for '{}.{}'",
+                db_name, table_name
+            )
+        })?;
+
+    Ok(())
+}
+
+/// Load the recorded migration state for `db_name`, if any
+///
+/// # Errors
+///
+/// Returns an error if the tracking table can't be created or read, or a recorded
+/// `table_progress` value can't be parsed.
+pub async fn load_migration_state(
+    client: &Client,
+    db_name: &str,
+) -> Result<Option<RecordedMigrationState>> {
+    ensure_migration_state_table(client).await?;
+
+    let row = client
+        .query_opt(
+            &format!(
+                "SELECT schema_hash, schema_objects, checks_passed, table_progress
+                 FROM {} WHERE db_name = $1",
+                MIGRATION_STATE_TABLE
+            ),
+            &[&db_name],
+        )
+        .await
+        .with_context(|| format!("Failed to read migration state for '{}'", db_name))?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let schema_hash: String = row.get(0);
+    let schema_objects_raw: String = row.get(1);
+    let checks_passed: Vec<String> = row.get(2);
+    let table_progress_json: serde_json::Value = row.get(3);
+
+    let schema_objects = if schema_objects_raw.is_empty() {
+        Vec::new()
+    } else {
+        schema_objects_raw.lines().map(str::to_string).collect()
+    };
+
+    let table_progress = table_progress_json
+        .as_object()
+        .map(|obj| {
+            obj.iter()
+                .map(|(table, done)| (table.clone(), done.as_bool().unwrap_or(false)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(Some(RecordedMigrationState {
+        schema_hash,
+        schema_objects,
+        checks_passed,
+        table_progress,
+    }))
+}
+
+/// Require that `current` exactly matches `recorded`'s schema fingerprint.
+///
+/// # Errors
+///
+/// Returns `Ok(())` if the hashes match. Otherwise returns an error listing every
+/// object line that's present in one fingerprint but not the other - tables,
+/// columns, or extensions added, removed, or changed on the source since the last
+/// `validate` run that found no drift.
+pub fn check_schema_drift(
+    db_name: &str,
+    current: &SchemaFingerprint,
+    recorded: &RecordedMigrationState,
+) -> Result<()> {
+    if current.hash == recorded.schema_hash {
+        return Ok(());
+    }
+
+    let current_set: std::collections::HashSet<&str> =
+        current.objects.iter().map(String::as_str).collect();
+    let recorded_set: std::collections::HashSet<&str> =
+        recorded.schema_objects.iter().map(String::as_str).collect();
+
+    let added: Vec<&str> = current_set.difference(&recorded_set).copied().collect();
+    let removed: Vec<&str> = recorded_set.difference(&current_set).copied().collect();
+
+    bail!(
+        "Source schema changed since '{}' was last validated: {} object(s) added, {} removed.\n\
+         Added: {}\n\
+         Removed: {}\n\
+         Re-run `validate` to confirm the new schema before resuming or migrating.",
+        db_name,
+        added.len(),
+        removed.len(),
+        if added.is_empty() { "(none)".to_string() } else { added.join(", ") },
+        if removed.is_empty() { "(none)".to_string() } else { removed.join(", ") },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint(objects: Vec<&str>) -> SchemaFingerprint {
+        let objects: Vec<String> = objects.into_iter().map(str::to_string).collect();
+        SchemaFingerprint {
+            hash: objects.join("\n"),
+            objects,
+        }
+    }
+
+    fn recorded(objects: Vec<&str>, hash: &str) -> RecordedMigrationState {
+        RecordedMigrationState {
+            schema_hash: hash.to_string(),
+            schema_objects: objects.into_iter().map(str::to_string).collect(),
+            checks_passed: Vec::new(),
+            table_progress: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_schema_drift_matching_hash_ok() {
+        let current = fingerprint(vec!["table:public.users"]);
+        let hash = current.hash.clone();
+        let recorded_state = recorded(vec!["table:public.users"], &hash);
+
+        assert!(check_schema_drift("mydb", &current, &recorded_state).is_ok());
+    }
+
+    #[test]
+    fn test_check_schema_drift_added_table() {
+        let current = fingerprint(vec!["table:public.users", "table:public.orders"]);
+        let recorded_state = recorded(vec!["table:public.users"], "stale-hash");
+
+        let err = check_schema_drift("mydb", &current, &recorded_state).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("1 object(s) added"));
+        assert!(message.contains("table:public.orders"));
+    }
+
+    #[test]
+    fn test_check_schema_drift_removed_table() {
+        let current = fingerprint(vec!["table:public.users"]);
+        let recorded_state = recorded(vec!["table:public.users", "table:public.orders"], "stale-hash");
+
+        let err = check_schema_drift("mydb", &current, &recorded_state).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("1 removed"));
+        assert!(message.contains("table:public.orders"));
+    }
+}
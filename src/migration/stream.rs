@@ -0,0 +1,178 @@
+// ABOUTME: Streams pg_dump directly into pg_restore via an in-process pipe
+// ABOUTME: Avoids staging the data dump on local disk for disk-constrained environments
+
+use crate::filters::ReplicationFilter;
+use crate::ssh_tunnel::SshTunnelConfig;
+use anyhow::{bail, Context, Result};
+use std::process::{Command, Stdio};
+
+/// Dump a database's data with `pg_dump --format=custom` and pipe it directly
+/// into `pg_restore` on the target, without ever writing the dump to local disk
+///
+/// [`super::dump::dump_data`] / [`super::restore::restore_data`] write a
+/// directory-format dump to disk and read it back later, which requires
+/// enough local disk for the entire dataset. Custom format is a single,
+/// non-parallel stream, so this trades away `dump_data`'s parallel jobs for
+/// zero disk usage - pick this only when local disk can't hold the dump
+/// (e.g. via `init --stream`), and use the directory-format path otherwise.
+///
+/// `filter`'s table selection is applied to the `pg_dump` side exactly as in
+/// `dump_data`; the `pg_restore` side just replays whatever came through the
+/// pipe.
+///
+/// When `source_ssh_tunnel` / `target_ssh_tunnel` is set, that side's
+/// connection is routed through an `ssh -L` tunnel instead of connecting to
+/// the host directly (see [`crate::ssh_tunnel`]).
+///
+/// # Errors
+///
+/// Returns an error identifying which side of the pipe failed if either
+/// `pg_dump` or `pg_restore` exits non-zero; if both sides fail, both are
+/// named.
+pub async fn stream_dump_to_restore(
+    source_url: &str,
+    target_url: &str,
+    database: &str,
+    filter: &ReplicationFilter,
+    source_ssh_tunnel: Option<&SshTunnelConfig>,
+    target_ssh_tunnel: Option<&SshTunnelConfig>,
+) -> Result<()> {
+    tracing::info!(
+        "Streaming data for database '{}' directly from pg_dump to pg_restore (format=custom)",
+        database
+    );
+
+    let source_parts = crate::utils::parse_postgres_url(source_url)
+        .with_context(|| format!("Failed to parse source URL: {}", source_url))?;
+    let source_pgpass = crate::utils::PgPassFile::new(&source_parts)
+        .context("Failed to create .pgpass file for source authentication")?;
+    let (source_host, source_port, _source_tunnel_guard) =
+        crate::ssh_tunnel::resolve_connect_target(source_ssh_tunnel, &source_parts)?;
+
+    let target_parts = crate::utils::parse_postgres_url(target_url)
+        .with_context(|| format!("Failed to parse target URL: {}", target_url))?;
+    let target_pgpass = crate::utils::PgPassFile::new(&target_parts)
+        .context("Failed to create .pgpass file for target authentication")?;
+    let (target_host, target_port, _target_tunnel_guard) =
+        crate::ssh_tunnel::resolve_connect_target(target_ssh_tunnel, &target_parts)?;
+
+    let mut dump_cmd = Command::new("pg_dump");
+    dump_cmd
+        .arg("--data-only")
+        .arg("--no-owner")
+        .arg("--format=custom")
+        .arg("--blobs");
+
+    if let Some(exclude_tables) = super::dump::get_excluded_tables_for_db(filter, database) {
+        for table in exclude_tables {
+            dump_cmd.arg("--exclude-table-data").arg(&table);
+        }
+    }
+
+    if let Some(include_tables) = super::dump::get_included_tables_for_db(filter, database) {
+        for table in include_tables {
+            dump_cmd.arg("--table").arg(&table);
+        }
+    }
+
+    dump_cmd
+        .arg("--host")
+        .arg(&source_host)
+        .arg("--port")
+        .arg(source_port.to_string())
+        .arg("--dbname")
+        .arg(&source_parts.database)
+        .env("PGPASSFILE", source_pgpass.path())
+        .envs(source_parts.to_pg_env_vars())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit());
+
+    if let Some(user) = &source_parts.user {
+        dump_cmd.arg("--username").arg(user);
+    }
+
+    let mut dump_child = dump_cmd.spawn().context(
+        "Failed to execute pg_dump. Is PostgreSQL client installed?\n\
+         Install with:\n\
+         - Ubuntu/Debian: sudo apt-get install postgresql-client\n\
+         - macOS: brew install postgresql\n\
+         - RHEL/CentOS: sudo yum install postgresql",
+    )?;
+    let dump_stdout = dump_child
+        .stdout
+        .take()
+        .context("Failed to capture pg_dump stdout for streaming")?;
+
+    let mut restore_cmd = Command::new("pg_restore");
+    restore_cmd
+        .arg("--data-only")
+        .arg("--no-owner")
+        .arg("--host")
+        .arg(&target_host)
+        .arg("--port")
+        .arg(target_port.to_string())
+        .arg("--dbname")
+        .arg(&target_parts.database)
+        .arg("--format=custom")
+        .arg("--verbose")
+        .env("PGPASSFILE", target_pgpass.path())
+        .envs(target_parts.to_pg_env_vars())
+        .stdin(Stdio::from(dump_stdout))
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit());
+
+    if let Some(user) = &target_parts.user {
+        restore_cmd.arg("--username").arg(user);
+    }
+
+    let mut restore_child = restore_cmd.spawn().context(
+        "Failed to execute pg_restore. Is PostgreSQL client installed?\n\
+         Install with:\n\
+         - Ubuntu/Debian: sudo apt-get install postgresql-client\n\
+         - macOS: brew install postgresql\n\
+         - RHEL/CentOS: sudo yum install postgresql",
+    )?;
+
+    // Both processes run concurrently off the real OS pipe between them, so the
+    // order these are awaited in doesn't affect whether data flows - but wait on
+    // the write end (pg_dump) first so a slow/stuck pg_restore doesn't mask a
+    // pg_dump failure that already happened.
+    let dump_status = dump_child
+        .wait()
+        .context("Failed to wait for pg_dump to exit")?;
+    let restore_status = restore_child
+        .wait()
+        .context("Failed to wait for pg_restore to exit")?;
+
+    match (dump_status.success(), restore_status.success()) {
+        (true, true) => {}
+        (false, true) => bail!(
+            "pg_dump failed while streaming data for database '{}'; pg_restore exited cleanly \
+             on a truncated stream, so the target is likely only partially populated.\n\
+             \n\
+             Common causes:\n\
+             - Database does not exist\n\
+             - Connection authentication failed\n\
+             - User lacks privileges to read table data\n\
+             - Network connectivity issues",
+            database
+        ),
+        (true, false) => bail!(
+            "pg_restore failed while streaming data for database '{}'.\n\
+             \n\
+             Common causes:\n\
+             - Foreign key constraint violations\n\
+             - User lacks INSERT privileges on target tables\n\
+             - Disk space issues on target\n\
+             - Data type mismatches",
+            database
+        ),
+        (false, false) => bail!(
+            "Both pg_dump and pg_restore failed while streaming data for database '{}'.",
+            database
+        ),
+    }
+
+    tracing::info!("✓ Data streamed successfully");
+    Ok(())
+}
@@ -1,28 +1,59 @@
-// ABOUTME: MongoDB BSON to JSONB type conversion for PostgreSQL storage
-// ABOUTME: Handles all BSON types with lossless conversion and special type encoding
+// ABOUTME: MongoDB BSON <-> JSON conversion using MongoDB Extended JSON v2
+// ABOUTME: Handles all BSON types with lossless, round-trippable encoding/decoding
 
-use anyhow::{Context, Result};
-use bson::{Bson, Document};
+use anyhow::{bail, Context, Result};
+use bson::spec::BinarySubtype;
+use bson::{Binary, Bson, DbPointer, Decimal128, Document, JavaScriptCodeWithScope, Regex, Timestamp};
+use futures::stream::{Stream, StreamExt};
 use mongodb::Database;
 use serde_json::Value as JsonValue;
+use std::str::FromStr;
 
-/// Convert a BSON value to JSON
+/// Which [MongoDB Extended JSON v2](https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/)
+/// flavor [`bson_to_json`] and [`document_to_json`] should emit.
 ///
-/// Maps BSON types to JSON types:
-/// - Int32/Int64 → number
-/// - Double → number
-/// - String → string
-/// - Bool → boolean
-/// - Array → array
-/// - Document → object
-/// - ObjectId → object with $oid field
-/// - DateTime → object with $date field
-/// - Binary → object with $binary field (base64)
-/// - Null/Undefined → null
+/// Both flavors are lossless in value and decode with [`json_to_bson`]; only
+/// [`ConversionMode::Canonical`] round-trips every value's exact BSON type, since
+/// relaxed output drops the wrappers that tell, for example, an `Int64` from an
+/// `Int32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversionMode {
+    /// Every BSON type except string/bool/null/array/object is wrapped in a
+    /// `$`-prefixed type tag (`{"$numberLong":"42"}`, `{"$oid":"..."}`, ...), so the
+    /// JSON's shape never depends on a value's magnitude. Matches
+    /// `mongoexport --jsonFormat canonical`.
+    Canonical,
+    /// Ordinary-looking numbers render as plain JSON (`42`) and in-range dates as
+    /// an ISO-8601 string inside `$date` (`{"$date":"2024-01-01T00:00:00Z"}`);
+    /// types JSON has no native representation for
+    /// (ObjectId, Decimal128, Binary, regex, timestamp, min/max key) still use their
+    /// canonical wrapper. Matches `mongoexport`'s default `--jsonFormat relaxed`.
+    Relaxed,
+}
+
+impl Default for ConversionMode {
+    fn default() -> Self {
+        ConversionMode::Relaxed
+    }
+}
+
+/// Largest magnitude an `i64`/`f64` can hold while still round-tripping exactly
+/// through an IEEE-754 double (2^53). Relaxed mode falls back to the canonical
+/// wrapper above this so a JSON-number reader can't silently lose precision.
+const MAX_SAFE_INTEGER: i64 = 1 << 53;
+
+/// Convert a BSON value to JSON using MongoDB Extended JSON v2 (`mode` selects
+/// [`ConversionMode::Canonical`] or [`ConversionMode::Relaxed`]).
+///
+/// The output is interchangeable with `mongoexport`/`mongoimport` and any other
+/// Extended-JSON-aware tooling, and decodes back via [`json_to_bson`]: to the
+/// exact original `Bson` in canonical mode; relaxed mode keeps values but not
+/// integer widths.
 ///
 /// # Arguments
 ///
 /// * `value` - BSON value from MongoDB
+/// * `mode` - Canonical (always type-wrapped) or relaxed (JSON-native where possible)
 ///
 /// # Returns
 ///
@@ -31,102 +62,65 @@ use serde_json::Value as JsonValue;
 /// # Examples
 ///
 /// ```no_run
-/// # use postgres_seren_replicator::mongodb::converter::bson_to_json;
+/// # use postgres_seren_replicator::mongodb::converter::{bson_to_json, ConversionMode};
 /// # use bson::Bson;
 /// let bson_int = Bson::Int32(42);
-/// let json = bson_to_json(&bson_int).unwrap();
+/// let json = bson_to_json(&bson_int, ConversionMode::Relaxed).unwrap();
 /// assert_eq!(json, serde_json::json!(42));
 /// ```
-pub fn bson_to_json(value: &Bson) -> Result<JsonValue> {
+pub fn bson_to_json(value: &Bson, mode: ConversionMode) -> Result<JsonValue> {
     match value {
-        Bson::Double(f) => {
-            // Handle non-finite numbers
-            if f.is_finite() {
-                serde_json::Number::from_f64(*f)
-                    .map(JsonValue::Number)
-                    .ok_or_else(|| anyhow::anyhow!("Failed to convert double {} to JSON number", f))
-            } else {
-                // Store non-finite as strings
-                Ok(JsonValue::String(f.to_string()))
-            }
-        }
+        Bson::Double(f) => Ok(encode_double(*f, mode)),
         Bson::String(s) => Ok(JsonValue::String(s.clone())),
         Bson::Array(arr) => {
-            let json_arr: Result<Vec<JsonValue>> = arr.iter().map(bson_to_json).collect();
+            let json_arr: Result<Vec<JsonValue>> =
+                arr.iter().map(|v| bson_to_json(v, mode)).collect();
             Ok(JsonValue::Array(json_arr?))
         }
-        Bson::Document(doc) => {
-            let json_obj: Result<serde_json::Map<String, JsonValue>> = doc
-                .iter()
-                .map(|(k, v)| bson_to_json(v).map(|json_v| (k.clone(), json_v)))
-                .collect();
-            Ok(JsonValue::Object(json_obj?))
-        }
+        Bson::Document(doc) => document_to_json(doc, mode),
         Bson::Boolean(b) => Ok(JsonValue::Bool(*b)),
         Bson::Null => Ok(JsonValue::Null),
-        Bson::Int32(i) => Ok(JsonValue::Number((*i).into())),
-        Bson::Int64(i) => Ok(JsonValue::Number((*i).into())),
-        Bson::ObjectId(oid) => {
-            // Store ObjectId as object with $oid field for type preservation
-            Ok(serde_json::json!({
-                "_type": "objectid",
-                "$oid": oid.to_hex()
-            }))
-        }
-        Bson::DateTime(dt) => {
-            // Store DateTime as object with $date field
-            // Using milliseconds since epoch for precision
-            Ok(serde_json::json!({
-                "_type": "datetime",
-                "$date": dt.timestamp_millis()
-            }))
-        }
-        Bson::Binary(bin) => {
-            // Encode binary as base64 in object
-            let encoded =
-                base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bin.bytes);
-            Ok(serde_json::json!({
-                "_type": "binary",
-                "subtype": u8::from(bin.subtype),
-                "data": encoded
-            }))
-        }
-        Bson::RegularExpression(regex) => {
-            // Store regex as object with pattern and options
-            Ok(serde_json::json!({
-                "_type": "regex",
-                "pattern": regex.pattern,
-                "options": regex.options
-            }))
-        }
-        Bson::Timestamp(ts) => {
-            // Store timestamp as object
-            Ok(serde_json::json!({
-                "_type": "timestamp",
-                "t": ts.time,
-                "i": ts.increment
-            }))
-        }
-        Bson::Decimal128(dec) => {
-            // Store Decimal128 as string to preserve precision
-            Ok(JsonValue::String(dec.to_string()))
-        }
-        Bson::Undefined => {
-            // Treat undefined as null
-            Ok(JsonValue::Null)
-        }
-        Bson::MaxKey => {
-            // Store MaxKey as special object
-            Ok(serde_json::json!({
-                "_type": "maxkey"
-            }))
-        }
-        Bson::MinKey => {
-            // Store MinKey as special object
-            Ok(serde_json::json!({
-                "_type": "minkey"
-            }))
-        }
+        Bson::Int32(i) => Ok(match mode {
+            ConversionMode::Canonical => serde_json::json!({ "$numberInt": i.to_string() }),
+            ConversionMode::Relaxed => JsonValue::Number((*i).into()),
+        }),
+        Bson::Int64(i) => Ok(match mode {
+            ConversionMode::Canonical => serde_json::json!({ "$numberLong": i.to_string() }),
+            ConversionMode::Relaxed if i.unsigned_abs() <= MAX_SAFE_INTEGER as u64 => {
+                JsonValue::Number((*i).into())
+            }
+            ConversionMode::Relaxed => serde_json::json!({ "$numberLong": i.to_string() }),
+        }),
+        Bson::ObjectId(oid) => Ok(serde_json::json!({ "$oid": oid.to_hex() })),
+        Bson::DateTime(dt) => Ok(encode_date(*dt, mode)),
+        Bson::Binary(bin) => Ok(serde_json::json!({
+            "$binary": {
+                "base64": base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bin.bytes),
+                "subType": format!("{:02x}", u8::from(bin.subtype)),
+            }
+        })),
+        Bson::RegularExpression(regex) => Ok(serde_json::json!({
+            "$regularExpression": {
+                "pattern": regex.pattern.as_str(),
+                "options": regex.options.as_str(),
+            }
+        })),
+        Bson::Timestamp(ts) => Ok(serde_json::json!({
+            "$timestamp": { "t": ts.time, "i": ts.increment }
+        })),
+        Bson::Decimal128(dec) => Ok(serde_json::json!({ "$numberDecimal": dec.to_string() })),
+        Bson::Undefined => Ok(serde_json::json!({ "$undefined": true })),
+        Bson::MaxKey => Ok(serde_json::json!({ "$maxKey": 1 })),
+        Bson::MinKey => Ok(serde_json::json!({ "$minKey": 1 })),
+        Bson::JavaScriptCode(code) => Ok(serde_json::json!({ "$code": code })),
+        Bson::JavaScriptCodeWithScope(js) => Ok(serde_json::json!({
+            "$code": js.code,
+            "$scope": document_to_json(&js.scope, mode)?,
+        })),
+        Bson::Symbol(s) => Ok(serde_json::json!({ "$symbol": s })),
+        Bson::DbPointer(ptr) => Ok(serde_json::json!({
+            "$dbPointer": { "$ref": ptr.namespace, "$id": { "$oid": ptr.id.to_hex() } }
+        })),
         _ => {
             // For any unsupported types, convert to string representation
             Ok(JsonValue::String(format!("{:?}", value)))
@@ -134,6 +128,40 @@ pub fn bson_to_json(value: &Bson) -> Result<JsonValue> {
     }
 }
 
+fn encode_double(f: f64, mode: ConversionMode) -> JsonValue {
+    if !f.is_finite() {
+        // NaN/+-Infinity have no JSON-native form in either mode.
+        let s = if f.is_nan() {
+            "NaN"
+        } else if f.is_sign_negative() {
+            "-Infinity"
+        } else {
+            "Infinity"
+        };
+        return serde_json::json!({ "$numberDouble": s });
+    }
+    match mode {
+        ConversionMode::Canonical => serde_json::json!({ "$numberDouble": f.to_string() }),
+        ConversionMode::Relaxed => serde_json::Number::from_f64(f)
+            .map(JsonValue::Number)
+            .unwrap_or_else(|| serde_json::json!({ "$numberDouble": f.to_string() })),
+    }
+}
+
+fn encode_date(dt: bson::DateTime, mode: ConversionMode) -> JsonValue {
+    let millis = dt.timestamp_millis();
+    let canonical = || serde_json::json!({ "$date": { "$numberLong": millis.to_string() } });
+    match mode {
+        ConversionMode::Canonical => canonical(),
+        // Extended JSON v2 only allows the relaxed ISO-8601 form for dates between
+        // 0000 and 9999; outside that range it falls back to the canonical form.
+        ConversionMode::Relaxed => match dt.try_to_rfc3339_string() {
+            Ok(iso) => serde_json::json!({ "$date": iso }),
+            Err(_) => canonical(),
+        },
+    }
+}
+
 /// Convert a MongoDB document to JSON object
 ///
 /// Converts all fields in the document to JSON, preserving all types.
@@ -141,6 +169,7 @@ pub fn bson_to_json(value: &Bson) -> Result<JsonValue> {
 /// # Arguments
 ///
 /// * `document` - BSON document from MongoDB
+/// * `mode` - Canonical (always type-wrapped) or relaxed (JSON-native where possible)
 ///
 /// # Returns
 ///
@@ -149,22 +178,22 @@ pub fn bson_to_json(value: &Bson) -> Result<JsonValue> {
 /// # Examples
 ///
 /// ```no_run
-/// # use postgres_seren_replicator::mongodb::converter::document_to_json;
+/// # use postgres_seren_replicator::mongodb::converter::{document_to_json, ConversionMode};
 /// # use bson::{doc, Bson};
 /// let doc = doc! {
 ///     "name": "Alice",
 ///     "age": 30,
 ///     "active": true
 /// };
-/// let json = document_to_json(&doc).unwrap();
+/// let json = document_to_json(&doc, ConversionMode::Relaxed).unwrap();
 /// assert_eq!(json["name"], "Alice");
 /// assert_eq!(json["age"], 30);
 /// ```
-pub fn document_to_json(document: &Document) -> Result<JsonValue> {
+pub fn document_to_json(document: &Document, mode: ConversionMode) -> Result<JsonValue> {
     let mut json_obj = serde_json::Map::new();
 
     for (key, value) in document.iter() {
-        let json_value = bson_to_json(value)
+        let json_value = bson_to_json(value, mode)
             .with_context(|| format!("Failed to convert field '{}' to JSON", key))?;
         json_obj.insert(key.clone(), json_value);
     }
@@ -172,10 +201,366 @@ pub fn document_to_json(document: &Document) -> Result<JsonValue> {
     Ok(JsonValue::Object(json_obj))
 }
 
+/// Reconstruct a `Bson` value from its MongoDB Extended JSON v2 representation
+/// (the inverse of [`bson_to_json`]).
+///
+/// Accepts both [`ConversionMode::Canonical`] and [`ConversionMode::Relaxed`] output
+/// (and a mix of the two within the same document, since relaxed mode only omits
+/// wrappers where they're optional) so a JSONB value written by this crate can be
+/// faithfully turned back into BSON for round-trips and rollbacks.
+///
+/// # Errors
+///
+/// Returns an error if a `$`-prefixed single-key object doesn't match one of the
+/// known Extended JSON wrappers, or if a wrapper's payload doesn't parse (e.g. a
+/// non-hex-digit `subType`, a malformed `$oid`).
+pub fn json_to_bson(value: &JsonValue) -> Result<Bson> {
+    match value {
+        JsonValue::Null => Ok(Bson::Null),
+        JsonValue::Bool(b) => Ok(Bson::Boolean(*b)),
+        JsonValue::Number(n) => Ok(number_to_bson(n)),
+        JsonValue::String(s) => Ok(Bson::String(s.clone())),
+        JsonValue::Array(arr) => Ok(Bson::Array(
+            arr.iter().map(json_to_bson).collect::<Result<_>>()?,
+        )),
+        JsonValue::Object(map) => {
+            // `{"$code": "...", "$scope": {...}}` is the one wrapper spread across two
+            // sibling keys rather than nested inside a single `$key`, so it can't go
+            // through the single_entry() dispatch below.
+            if map.contains_key("$code") {
+                return code_wrapper_to_bson(map);
+            }
+            if let Some((key, inner)) = single_entry(map) {
+                if let Some(key) = key.strip_prefix('$') {
+                    return extended_json_wrapper_to_bson(key, inner)
+                        .with_context(|| format!("Invalid Extended JSON wrapper '${}'", key));
+                }
+            }
+            let doc: Document = map
+                .iter()
+                .map(|(k, v)| json_to_bson(v).map(|bson_v| (k.clone(), bson_v)))
+                .collect::<Result<_>>()?;
+            Ok(Bson::Document(doc))
+        }
+    }
+}
+
+fn code_wrapper_to_bson(map: &serde_json::Map<String, JsonValue>) -> Result<Bson> {
+    let code = map
+        .get("$code")
+        .and_then(JsonValue::as_str)
+        .context("$code must be a string")?
+        .to_string();
+    match map.get("$scope") {
+        Some(scope_json) => {
+            let Bson::Document(scope) = json_to_bson(scope_json)? else {
+                bail!("$scope must be an object");
+            };
+            Ok(Bson::JavaScriptCodeWithScope(JavaScriptCodeWithScope {
+                code,
+                scope,
+            }))
+        }
+        None => Ok(Bson::JavaScriptCode(code)),
+    }
+}
+
+/// An object's single `(key, value)` pair, or `None` if it doesn't have exactly one.
+fn single_entry(map: &serde_json::Map<String, JsonValue>) -> Option<(&String, &JsonValue)> {
+    let mut iter = map.iter();
+    let first = iter.next()?;
+    if iter.next().is_some() {
+        return None;
+    }
+    Some(first)
+}
+
+fn number_to_bson(n: &serde_json::Number) -> Bson {
+    if let Some(i) = n.as_i64() {
+        if i32::try_from(i).is_ok() {
+            Bson::Int32(i as i32)
+        } else {
+            Bson::Int64(i)
+        }
+    } else {
+        Bson::Double(n.as_f64().unwrap_or(0.0))
+    }
+}
+
+fn extended_json_wrapper_to_bson(tag: &str, inner: &JsonValue) -> Result<Bson> {
+    match tag {
+        "numberInt" => Ok(Bson::Int32(json_number_string(inner)?.parse()?)),
+        "numberLong" => Ok(Bson::Int64(json_number_string(inner)?.parse()?)),
+        "numberDouble" => Ok(Bson::Double(match json_number_string(inner)?.as_str() {
+            "NaN" => f64::NAN,
+            "Infinity" => f64::INFINITY,
+            "-Infinity" => f64::NEG_INFINITY,
+            other => other.parse()?,
+        })),
+        "numberDecimal" => Ok(Bson::Decimal128(Decimal128::from_str(&json_number_string(
+            inner,
+        )?)?)),
+        "oid" => Ok(Bson::ObjectId(bson::oid::ObjectId::parse_str(
+            inner.as_str().context("$oid value must be a string")?,
+        )?)),
+        "date" => parse_date_wrapper(inner),
+        "binary" => {
+            let base64 = inner
+                .get("base64")
+                .and_then(JsonValue::as_str)
+                .context("$binary.base64 must be a string")?;
+            let subtype = inner
+                .get("subType")
+                .and_then(JsonValue::as_str)
+                .context("$binary.subType must be a string")?;
+            let subtype = u8::from_str_radix(subtype, 16).context("$binary.subType must be hex")?;
+            let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, base64)
+                .context("$binary.base64 is not valid base64")?;
+            Ok(Bson::Binary(Binary {
+                subtype: BinarySubtype::from(subtype),
+                bytes,
+            }))
+        }
+        "timestamp" => {
+            let t = inner
+                .get("t")
+                .and_then(JsonValue::as_u64)
+                .context("$timestamp.t must be an integer")?;
+            let i = inner
+                .get("i")
+                .and_then(JsonValue::as_u64)
+                .context("$timestamp.i must be an integer")?;
+            Ok(Bson::Timestamp(Timestamp {
+                time: t as u32,
+                increment: i as u32,
+            }))
+        }
+        "regularExpression" => {
+            let pattern = inner
+                .get("pattern")
+                .and_then(JsonValue::as_str)
+                .context("$regularExpression.pattern must be a string")?;
+            let options = inner
+                .get("options")
+                .and_then(JsonValue::as_str)
+                .context("$regularExpression.options must be a string")?;
+            Ok(Bson::RegularExpression(Regex {
+                pattern: pattern.into(),
+                options: options.into(),
+            }))
+        }
+        "minKey" => Ok(Bson::MinKey),
+        "maxKey" => Ok(Bson::MaxKey),
+        "undefined" => Ok(Bson::Undefined),
+        "symbol" => Ok(Bson::Symbol(
+            inner
+                .as_str()
+                .context("$symbol value must be a string")?
+                .to_string(),
+        )),
+        "dbPointer" => {
+            let namespace = inner
+                .get("$ref")
+                .and_then(JsonValue::as_str)
+                .context("$dbPointer.$ref must be a string")?
+                .to_string();
+            let id_json = inner.get("$id").context("$dbPointer.$id is required")?;
+            let Bson::ObjectId(id) = json_to_bson(id_json)? else {
+                bail!("$dbPointer.$id must be an ObjectId");
+            };
+            Ok(Bson::DbPointer(DbPointer { namespace, id }))
+        }
+        other => bail!("Unknown Extended JSON wrapper '${}'", other),
+    }
+}
+
+/// `$numberInt`/`$numberLong`/`$numberDouble`/`$numberDecimal` payloads are always
+/// strings per the Extended JSON spec, but tolerate a bare JSON number too since
+/// some producers emit one.
+fn json_number_string(value: &JsonValue) -> Result<String> {
+    match value {
+        JsonValue::String(s) => Ok(s.clone()),
+        JsonValue::Number(n) => Ok(n.to_string()),
+        other => bail!("Expected a numeric string, got {}", other),
+    }
+}
+
+fn parse_date_wrapper(inner: &JsonValue) -> Result<Bson> {
+    match inner {
+        // Canonical: {"$date": {"$numberLong": "<ms>"}}
+        JsonValue::Object(_) => {
+            let millis: i64 = json_number_string(
+                inner
+                    .get("$numberLong")
+                    .context("$date object must contain $numberLong")?,
+            )?
+            .parse()?;
+            Ok(Bson::DateTime(bson::DateTime::from_millis(millis)))
+        }
+        // Relaxed: {"$date": "2024-01-01T00:00:00Z"}
+        JsonValue::String(iso) => Ok(Bson::DateTime(
+            bson::DateTime::parse_rfc3339_str(iso)
+                .with_context(|| format!("Invalid $date string '{}'", iso))?,
+        )),
+        // Some producers emit the millisecond count directly.
+        JsonValue::Number(n) => Ok(Bson::DateTime(bson::DateTime::from_millis(
+            n.as_i64().context("$date number must be an integer")?,
+        ))),
+        other => bail!("Unsupported $date payload: {}", other),
+    }
+}
+
+/// Derive the JSONB row ID for a document, falling back to its 1-based position in
+/// the collection (`doc_num`, 0-based internally) when `_id` is missing or isn't one
+/// of the types this crate knows how to stringify
+fn document_id(document: &Document, doc_num: usize, collection_name: &str) -> String {
+    match document.get("_id") {
+        Some(Bson::ObjectId(oid)) => oid.to_hex(),
+        Some(Bson::String(s)) => s.clone(),
+        Some(Bson::Int32(i)) => i.to_string(),
+        Some(Bson::Int64(i)) => i.to_string(),
+        Some(_) => {
+            tracing::warn!(
+                "Document {} in collection '{}' has unsupported _id type, using doc number",
+                doc_num + 1,
+                collection_name
+            );
+            (doc_num + 1).to_string()
+        }
+        None => {
+            tracing::warn!(
+                "Document {} in collection '{}' has no _id field, using doc number",
+                doc_num + 1,
+                collection_name
+            );
+            (doc_num + 1).to_string()
+        }
+    }
+}
+
+/// Convert a MongoDB collection to JSONB as a stream of bounded-size batches
+///
+/// Reads the collection via [`read_collection_stream`](crate::mongodb::reader::read_collection_stream)
+/// and converts each document to JSON as it arrives, flushing a batch of up to
+/// `batch_size` `(id, json_data)` pairs at a time instead of materializing the whole
+/// collection, so peak memory stays bounded regardless of collection size. A
+/// document that fails to convert is logged and skipped (counted in the yielded
+/// batch's skip count) rather than aborting the whole run, since one malformed
+/// document shouldn't sink an otherwise-good collection copy.
+///
+/// # Arguments
+///
+/// * `database` - MongoDB database reference
+/// * `collection_name` - Collection name (must be validated)
+/// * `batch_size` - Maximum number of converted documents per yielded batch, and the
+///   cursor batch size passed through to `read_collection_stream`
+/// * `mode` - Canonical (always type-wrapped, needed to round-trip via [`json_to_bson`])
+///   or relaxed (more ergonomic to query, still lossless)
+///
+/// # Returns
+///
+/// A stream yielding `Ok((rows, skipped))` for each batch - `rows` is up to
+/// `batch_size` `(id_string, json_data)` pairs, and `skipped` is how many documents in
+/// that batch failed to convert and were dropped. An `Err` is yielded (terminating the
+/// stream) if the underlying read itself fails.
+///
+/// # Security
+///
+/// Collection name should be validated before calling this function.
+pub async fn convert_collection_stream(
+    database: &Database,
+    collection_name: &str,
+    batch_size: u32,
+    mode: ConversionMode,
+) -> Result<impl Stream<Item = Result<(Vec<(String, JsonValue)>, usize)>>> {
+    // Validate collection name
+    crate::jsonb::validate_table_name(collection_name)
+        .context("Invalid collection name for JSONB conversion")?;
+
+    tracing::info!(
+        "Converting MongoDB collection '{}' to JSONB (batch_size: {})",
+        collection_name,
+        batch_size
+    );
+
+    let stream =
+        crate::mongodb::reader::read_collection_stream(database, collection_name, batch_size)
+            .await
+            .with_context(|| format!("Failed to read data from collection '{}'", collection_name))?;
+
+    let collection_name = collection_name.to_string();
+    let batch_size = batch_size.max(1) as usize;
+
+    // `unfold` drives the underlying document stream to completion one batch at a
+    // time: each call pulls documents until `batch_size` is reached, the source
+    // stream ends, or a read itself errors (which ends the stream after reporting
+    // it). `done` distinguishes "flush the final partial batch" from "truly finished".
+    struct State<S> {
+        stream: std::pin::Pin<Box<S>>,
+        doc_num: usize,
+        done: bool,
+    }
+
+    let state = State {
+        stream: Box::pin(stream),
+        doc_num: 0,
+        done: false,
+    };
+
+    Ok(futures::stream::unfold(state, move |mut state| {
+        let collection_name = collection_name.clone();
+        async move {
+            if state.done {
+                return None;
+            }
+
+            let mut batch = Vec::with_capacity(batch_size);
+            let mut skipped = 0usize;
+
+            while batch.len() < batch_size {
+                match state.stream.next().await {
+                    Some(Ok(document)) => {
+                        let id = document_id(&document, state.doc_num, &collection_name);
+                        match document_to_json(&document, mode) {
+                            Ok(json_data) => batch.push((id, json_data)),
+                            Err(err) => {
+                                tracing::warn!(
+                                    "Skipping document {} in collection '{}': {:#}",
+                                    state.doc_num + 1,
+                                    collection_name,
+                                    err
+                                );
+                                skipped += 1;
+                            }
+                        }
+                        state.doc_num += 1;
+                    }
+                    Some(Err(err)) => {
+                        state.done = true;
+                        return Some((Err(err), state));
+                    }
+                    None => {
+                        state.done = true;
+                        break;
+                    }
+                }
+            }
+
+            if batch.is_empty() && skipped == 0 && state.done {
+                return None;
+            }
+
+            Some((Ok((batch, skipped)), state))
+        }
+    }))
+}
+
 /// Convert an entire MongoDB collection to JSONB format
 ///
-/// Reads all documents from a collection and converts them to JSONB.
-/// Returns a vector of (id, json_data) tuples ready for insertion.
+/// Thin wrapper around [`convert_collection_stream`] that drains every batch into a
+/// single `Vec`. For large collections, prefer consuming the stream directly so a
+/// writer can insert-and-forget each batch instead of waiting on the whole
+/// collection to convert.
 ///
 /// # ID Generation Strategy
 ///
@@ -187,6 +572,8 @@ pub fn document_to_json(document: &Document) -> Result<JsonValue> {
 ///
 /// * `database` - MongoDB database reference
 /// * `collection_name` - Collection name (must be validated)
+/// * `batch_size` - Cursor/conversion batch size, see [`convert_collection_stream`]
+/// * `mode` - Canonical or relaxed Extended JSON, see [`convert_collection_stream`]
 ///
 /// # Returns
 ///
@@ -199,14 +586,14 @@ pub fn document_to_json(document: &Document) -> Result<JsonValue> {
 /// # Examples
 ///
 /// ```no_run
-/// # use postgres_seren_replicator::mongodb::{connect_mongodb, converter::convert_collection_to_jsonb};
+/// # use postgres_seren_replicator::mongodb::{connect_mongodb, converter::{convert_collection_to_jsonb, ConversionMode}};
 /// # use postgres_seren_replicator::jsonb::validate_table_name;
 /// # async fn example() -> anyhow::Result<()> {
 /// let client = connect_mongodb("mongodb://localhost:27017/mydb").await?;
 /// let db = client.database("mydb");
 /// let collection = "users";
 /// validate_table_name(collection)?;
-/// let rows = convert_collection_to_jsonb(&db, collection).await?;
+/// let rows = convert_collection_to_jsonb(&db, collection, 1000, ConversionMode::Relaxed).await?;
 /// println!("Converted {} documents to JSONB", rows.len());
 /// # Ok(())
 /// # }
@@ -214,61 +601,27 @@ pub fn document_to_json(document: &Document) -> Result<JsonValue> {
 pub async fn convert_collection_to_jsonb(
     database: &Database,
     collection_name: &str,
+    batch_size: u32,
+    mode: ConversionMode,
 ) -> Result<Vec<(String, JsonValue)>> {
-    // Validate collection name
-    crate::jsonb::validate_table_name(collection_name)
-        .context("Invalid collection name for JSONB conversion")?;
+    let stream = convert_collection_stream(database, collection_name, batch_size, mode).await?;
+    futures::pin_mut!(stream);
 
-    tracing::info!(
-        "Converting MongoDB collection '{}' to JSONB",
-        collection_name
-    );
+    let mut result = Vec::new();
+    let mut total_skipped = 0usize;
 
-    // Read all documents using our reader
-    let documents = crate::mongodb::reader::read_collection_data(database, collection_name)
-        .await
-        .with_context(|| format!("Failed to read data from collection '{}'", collection_name))?;
-
-    let mut result = Vec::with_capacity(documents.len());
-
-    for (doc_num, document) in documents.into_iter().enumerate() {
-        // Extract or generate ID
-        let id = if let Some(id_value) = document.get("_id") {
-            // Use _id field from document
-            match id_value {
-                Bson::ObjectId(oid) => oid.to_hex(),
-                Bson::String(s) => s.clone(),
-                Bson::Int32(i) => i.to_string(),
-                Bson::Int64(i) => i.to_string(),
-                _ => {
-                    tracing::warn!(
-                        "Document {} in collection '{}' has unsupported _id type, using doc number",
-                        doc_num + 1,
-                        collection_name
-                    );
-                    (doc_num + 1).to_string()
-                }
-            }
-        } else {
-            // No _id field, use document number
-            tracing::warn!(
-                "Document {} in collection '{}' has no _id field, using doc number",
-                doc_num + 1,
-                collection_name
-            );
-            (doc_num + 1).to_string()
-        };
-
-        // Convert document to JSON
-        let json_data = document_to_json(&document).with_context(|| {
-            format!(
-                "Failed to convert document {} in collection '{}' to JSON",
-                doc_num + 1,
-                collection_name
-            )
-        })?;
+    while let Some(batch) = stream.next().await {
+        let (rows, skipped) = batch?;
+        result.extend(rows);
+        total_skipped += skipped;
+    }
 
-        result.push((id, json_data));
+    if total_skipped > 0 {
+        tracing::warn!(
+            "Skipped {} document(s) in collection '{}' that failed to convert",
+            total_skipped,
+            collection_name
+        );
     }
 
     tracing::info!(
@@ -286,55 +639,69 @@ mod tests {
     use bson::{doc, oid::ObjectId, Bson};
 
     #[test]
-    fn test_convert_int32() {
+    fn test_convert_int32_relaxed() {
         let bson = Bson::Int32(42);
-        let json = bson_to_json(&bson).unwrap();
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
         assert_eq!(json, serde_json::json!(42));
     }
 
     #[test]
-    fn test_convert_int64() {
+    fn test_convert_int32_canonical() {
+        let bson = Bson::Int32(42);
+        let json = bson_to_json(&bson, ConversionMode::Canonical).unwrap();
+        assert_eq!(json, serde_json::json!({ "$numberInt": "42" }));
+    }
+
+    #[test]
+    fn test_convert_int64_relaxed() {
         let bson = Bson::Int64(42i64);
-        let json = bson_to_json(&bson).unwrap();
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
         assert_eq!(json, serde_json::json!(42));
     }
 
     #[test]
-    fn test_convert_double() {
+    fn test_convert_int64_relaxed_unsafe_magnitude_falls_back_to_canonical() {
+        let bson = Bson::Int64(i64::MAX);
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
+        assert_eq!(json, serde_json::json!({ "$numberLong": i64::MAX.to_string() }));
+    }
+
+    #[test]
+    fn test_convert_double_relaxed() {
         let bson = Bson::Double(42.75);
-        let json = bson_to_json(&bson).unwrap();
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
         assert_eq!(json, serde_json::json!(42.75));
     }
 
     #[test]
     fn test_convert_string() {
         let bson = Bson::String("Hello, World!".to_string());
-        let json = bson_to_json(&bson).unwrap();
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
         assert_eq!(json, serde_json::json!("Hello, World!"));
     }
 
     #[test]
     fn test_convert_bool() {
         let bson_true = Bson::Boolean(true);
-        let json_true = bson_to_json(&bson_true).unwrap();
+        let json_true = bson_to_json(&bson_true, ConversionMode::Relaxed).unwrap();
         assert_eq!(json_true, serde_json::json!(true));
 
         let bson_false = Bson::Boolean(false);
-        let json_false = bson_to_json(&bson_false).unwrap();
+        let json_false = bson_to_json(&bson_false, ConversionMode::Relaxed).unwrap();
         assert_eq!(json_false, serde_json::json!(false));
     }
 
     #[test]
     fn test_convert_null() {
         let bson = Bson::Null;
-        let json = bson_to_json(&bson).unwrap();
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
         assert_eq!(json, JsonValue::Null);
     }
 
     #[test]
     fn test_convert_array() {
         let bson = Bson::Array(vec![Bson::Int32(1), Bson::Int32(2), Bson::Int32(3)]);
-        let json = bson_to_json(&bson).unwrap();
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
         assert_eq!(json, serde_json::json!([1, 2, 3]));
     }
 
@@ -345,7 +712,7 @@ mod tests {
             "age": 30,
             "active": true
         };
-        let json = document_to_json(&doc).unwrap();
+        let json = document_to_json(&doc, ConversionMode::Relaxed).unwrap();
         assert_eq!(json["name"], "Alice");
         assert_eq!(json["age"], 30);
         assert_eq!(json["active"], true);
@@ -355,23 +722,19 @@ mod tests {
     fn test_convert_objectid() {
         let oid = ObjectId::new();
         let bson = Bson::ObjectId(oid);
-        let json = bson_to_json(&bson).unwrap();
-
-        // Should be wrapped in object with _type and $oid
-        assert!(json.is_object());
-        assert_eq!(json["_type"], "objectid");
-        assert_eq!(json["$oid"], oid.to_hex());
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
+        assert_eq!(json, serde_json::json!({ "$oid": oid.to_hex() }));
     }
 
     #[test]
     fn test_convert_non_finite_double() {
         let nan_bson = Bson::Double(f64::NAN);
-        let json = bson_to_json(&nan_bson).unwrap();
-        assert!(json.is_string());
+        let json = bson_to_json(&nan_bson, ConversionMode::Relaxed).unwrap();
+        assert_eq!(json, serde_json::json!({ "$numberDouble": "NaN" }));
 
         let inf_bson = Bson::Double(f64::INFINITY);
-        let json = bson_to_json(&inf_bson).unwrap();
-        assert!(json.is_string());
+        let json = bson_to_json(&inf_bson, ConversionMode::Relaxed).unwrap();
+        assert_eq!(json, serde_json::json!({ "$numberDouble": "Infinity" }));
     }
 
     #[test]
@@ -383,11 +746,94 @@ mod tests {
             },
             "tags": ["admin", "user"]
         };
-        let json = document_to_json(&doc).unwrap();
+        let json = document_to_json(&doc, ConversionMode::Relaxed).unwrap();
 
         assert_eq!(json["user"]["name"], "Alice");
         assert_eq!(json["user"]["email"], "alice@example.com");
         assert_eq!(json["tags"][0], "admin");
         assert_eq!(json["tags"][1], "user");
     }
+
+    #[test]
+    fn test_round_trip_canonical() {
+        // Canonical mode type-tags every ambiguous value, so it round-trips exactly -
+        // this is what makes it suitable for rollback/reconstruction.
+        let doc = doc! {
+            "name": "Alice",
+            "count": 42i64,
+            "big": i64::MAX,
+            "price": 19.99,
+            "id": ObjectId::new(),
+            "tags": ["a", "b"],
+            "nested": { "x": 1 },
+        };
+
+        let json = document_to_json(&doc, ConversionMode::Canonical).unwrap();
+        let Bson::Document(round_tripped) = json_to_bson(&json).unwrap() else {
+            panic!("expected a document");
+        };
+        assert_eq!(round_tripped, doc);
+    }
+
+    #[test]
+    fn test_round_trip_relaxed_loses_int_width_but_keeps_value() {
+        // Relaxed mode renders a small Int64 as a plain JSON number, so decoding it
+        // back can't distinguish "was Int32" from "was Int64 that happened to fit" -
+        // the numeric value survives even though the exact BSON type doesn't.
+        let bson = Bson::Int64(42);
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
+        assert_eq!(json_to_bson(&json).unwrap().as_i32(), Some(42));
+    }
+
+    #[test]
+    fn test_json_to_bson_rejects_unknown_wrapper() {
+        let json = serde_json::json!({ "$notARealWrapper": 1 });
+        let err = json_to_bson(&json).unwrap_err();
+        assert!(err.to_string().contains("notARealWrapper"));
+    }
+
+    #[test]
+    fn test_convert_javascript_code() {
+        let bson = Bson::JavaScriptCode("function() { return 1; }".to_string());
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
+        assert_eq!(json, serde_json::json!({ "$code": "function() { return 1; }" }));
+        assert_eq!(json_to_bson(&json).unwrap(), bson);
+    }
+
+    #[test]
+    fn test_convert_javascript_code_with_scope() {
+        let bson = Bson::JavaScriptCodeWithScope(bson::JavaScriptCodeWithScope {
+            code: "function() { return x; }".to_string(),
+            scope: doc! { "x": 1 },
+        });
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "$code": "function() { return x; }", "$scope": { "x": 1 } })
+        );
+        assert_eq!(json_to_bson(&json).unwrap(), bson);
+    }
+
+    #[test]
+    fn test_convert_symbol() {
+        let bson = Bson::Symbol("legacy_symbol".to_string());
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
+        assert_eq!(json, serde_json::json!({ "$symbol": "legacy_symbol" }));
+        assert_eq!(json_to_bson(&json).unwrap(), bson);
+    }
+
+    #[test]
+    fn test_convert_db_pointer() {
+        let id = ObjectId::new();
+        let bson = Bson::DbPointer(bson::DbPointer {
+            namespace: "db.collection".to_string(),
+            id,
+        });
+        let json = bson_to_json(&bson, ConversionMode::Relaxed).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "$dbPointer": { "$ref": "db.collection", "$id": { "$oid": id.to_hex() } } })
+        );
+        assert_eq!(json_to_bson(&json).unwrap(), bson);
+    }
 }
@@ -3,6 +3,7 @@
 
 pub mod converter;
 pub mod reader;
+pub mod schema;
 
 use anyhow::{bail, Context, Result};
 use mongodb::{options::ClientOptions, Client};
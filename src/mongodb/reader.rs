@@ -2,8 +2,32 @@
 // ABOUTME: Provides read-only access to MongoDB collections with security validation
 
 use anyhow::{Context, Result};
-use bson::Document;
+use bson::oid::ObjectId;
+use bson::{doc, Bson, DateTime, Document, RawDocumentBuf};
+use futures::stream::{Stream, StreamExt};
+use mongodb::options::FindOptions;
 use mongodb::{Client, Database};
+use std::time::Duration;
+
+/// Default cursor batch size for [`read_collection_data`], which doesn't need to
+/// tune memory usage itself
+const DEFAULT_BATCH_SIZE: u32 = 1000;
+
+/// How far before the watermark [`read_collection_since`] re-reads, to cover
+/// documents whose server clock lagged slightly behind whatever produced the
+/// watermark. Overlap is harmless as long as downstream writes are idempotent
+/// upserts keyed on `_id`.
+const CLOCK_SKEW_SAFETY_WINDOW: Duration = Duration::from_secs(60);
+
+/// Options controlling how [`read_collection_data_with_options`] decodes documents
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReadOptions {
+    /// Recover documents with invalid UTF-8 string fields instead of failing the
+    /// whole read. The document is re-decoded from its raw BSON bytes with invalid
+    /// byte sequences replaced by U+FFFD, and the affected `_id` is logged via
+    /// `tracing::warn!`. Off by default, since it silently alters document content.
+    pub utf8_lossy: bool,
+}
 
 /// List all collection names in a MongoDB database
 ///
@@ -116,10 +140,86 @@ pub async fn get_collection_count(database: &Database, collection_name: &str) ->
     Ok(count as usize)
 }
 
+/// Stream documents from a MongoDB collection in cursor-sized batches
+///
+/// Sets the cursor's `batch_size` so the driver fetches `batch_size` documents per
+/// round trip and yields them incrementally, instead of buffering the whole
+/// collection in memory. Callers can pipe the stream directly into a Postgres
+/// writer to keep memory use bounded regardless of collection size.
+///
+/// # Arguments
+///
+/// * `database` - MongoDB database reference
+/// * `collection_name` - Collection name (must be validated)
+/// * `batch_size` - Number of documents the cursor fetches per round trip
+///
+/// # Returns
+///
+/// A stream yielding each document as it's read from the cursor
+///
+/// # Security
+///
+/// - Collection name is validated before querying
+/// - Read-only operation, no modifications possible
+///
+/// # Examples
+///
+/// ```no_run
+/// # use postgres_seren_replicator::mongodb::{connect_mongodb, reader::read_collection_stream};
+/// # use postgres_seren_replicator::jsonb::validate_table_name;
+/// # use futures::stream::StreamExt;
+/// # async fn example() -> anyhow::Result<()> {
+/// let client = connect_mongodb("mongodb://localhost:27017/mydb").await?;
+/// let db = client.database("mydb");
+/// let collection = "users";
+/// validate_table_name(collection)?;
+/// let mut documents = Box::pin(read_collection_stream(&db, collection, 500).await?);
+/// while let Some(document) = documents.next().await {
+///     let document = document?;
+///     // hand off to the writer without buffering the rest of the collection
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn read_collection_stream(
+    database: &Database,
+    collection_name: &str,
+    batch_size: u32,
+) -> Result<impl Stream<Item = Result<Document>>> {
+    // Validate collection name to prevent injection
+    crate::jsonb::validate_table_name(collection_name)
+        .context("Invalid collection name for data reading")?;
+
+    tracing::info!(
+        "Streaming documents from collection '{}' (batch_size: {})",
+        collection_name,
+        batch_size
+    );
+
+    let collection = database.collection::<Document>(collection_name);
+    let options = FindOptions::builder().batch_size(batch_size).build();
+
+    let cursor = collection
+        .find(None, options)
+        .await
+        .with_context(|| format!("Failed to query collection '{}'", collection_name))?;
+
+    let collection_name = collection_name.to_string();
+    Ok(cursor.map(move |result| {
+        result.with_context(|| {
+            format!(
+                "Failed to read document from collection '{}'",
+                collection_name
+            )
+        })
+    }))
+}
+
 /// Read all documents from a MongoDB collection
 ///
-/// Reads all documents from the collection and returns them as BSON documents.
-/// For large collections, this may consume significant memory.
+/// Thin wrapper around [`read_collection_stream`] that collects the whole
+/// collection into memory. For large collections, prefer streaming documents
+/// directly to the writer instead.
 ///
 /// # Arguments
 ///
@@ -154,16 +254,147 @@ pub async fn read_collection_data(
     database: &Database,
     collection_name: &str,
 ) -> Result<Vec<Document>> {
+    let stream = read_collection_stream(database, collection_name, DEFAULT_BATCH_SIZE).await?;
+    futures::pin_mut!(stream);
+
+    let mut documents = Vec::new();
+    while let Some(document) = stream.next().await {
+        documents.push(document?);
+    }
+
+    tracing::info!(
+        "Read {} documents from collection '{}'",
+        documents.len(),
+        collection_name
+    );
+
+    Ok(documents)
+}
+
+/// Read multiple collections concurrently, up to a bounded concurrency limit
+///
+/// Fans out [`read_collection_data`] across `names`, running up to
+/// `max_concurrency` reads at once via
+/// [`buffer_unordered`](futures::stream::StreamExt::buffer_unordered), and yields
+/// `(collection_name, result)` pairs as each collection finishes - not necessarily
+/// in `names` order - so a writer can start on the first completed collection
+/// instead of waiting for the slowest one. `max_concurrency` is clamped to at
+/// least 1, at which point `buffer_unordered` reduces to sequential reads.
+///
+/// # Arguments
+///
+/// * `database` - MongoDB database reference
+/// * `names` - Collection names to read (each is validated individually; an
+///   invalid name surfaces as an `Err` for that collection, not a failure of the
+///   whole fan-out)
+/// * `max_concurrency` - Maximum number of collections read at once
+///
+/// # Returns
+///
+/// A stream yielding `(collection_name, Result<Vec<Document>>)` pairs as each
+/// collection's read completes
+///
+/// # Security
+///
+/// - Each collection name is validated before querying
+/// - Read-only operation, no modifications possible
+///
+/// # Examples
+///
+/// ```no_run
+/// # use postgres_seren_replicator::mongodb::{connect_mongodb, reader::read_collections_concurrent};
+/// # use futures::stream::StreamExt;
+/// # async fn example() -> anyhow::Result<()> {
+/// let client = connect_mongodb("mongodb://localhost:27017/mydb").await?;
+/// let db = client.database("mydb");
+/// let names = vec!["users".to_string(), "events".to_string()];
+/// let mut results = Box::pin(read_collections_concurrent(&db, &names, 4));
+/// while let Some((name, result)) = results.next().await {
+///     let documents = result?;
+///     println!("'{}': {} documents", name, documents.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_collections_concurrent<'a>(
+    database: &'a Database,
+    names: &'a [String],
+    max_concurrency: usize,
+) -> impl Stream<Item = (String, Result<Vec<Document>>)> + 'a {
+    let concurrency = max_concurrency.max(1);
+
+    if concurrency == 1 {
+        tracing::debug!(
+            "Reading {} collections sequentially (max_concurrency = 1)",
+            names.len()
+        );
+    } else {
+        tracing::info!(
+            "Reading {} collections with up to {} concurrently",
+            names.len(),
+            concurrency
+        );
+    }
+
+    futures::stream::iter(names.iter().cloned())
+        .map(move |name| async move {
+            tracing::debug!("Starting read of collection '{}'", name);
+            let result = read_collection_data(database, &name).await;
+
+            match &result {
+                Ok(documents) => tracing::info!(
+                    "Finished reading {} documents from collection '{}'",
+                    documents.len(),
+                    name
+                ),
+                Err(err) => tracing::warn!("Failed to read collection '{}': {:#}", name, err),
+            }
+
+            (name, result)
+        })
+        .buffer_unordered(concurrency)
+}
+
+/// Read all documents from a MongoDB collection, with recovery options
+///
+/// Like [`read_collection_data`], but lets the caller opt into lossy UTF-8 recovery
+/// via [`ReadOptions`] for legacy/dirty datasets that contain documents the driver's
+/// strict decode path would otherwise reject outright.
+///
+/// # Arguments
+///
+/// * `database` - MongoDB database reference
+/// * `collection_name` - Collection name (must be validated)
+/// * `options` - Decode options; `ReadOptions::default()` behaves exactly like
+///   [`read_collection_data`]
+///
+/// # Returns
+///
+/// Vector of BSON documents from the collection
+///
+/// # Security
+///
+/// - Collection name is validated before querying
+/// - Read-only operation, no modifications possible
+pub async fn read_collection_data_with_options(
+    database: &Database,
+    collection_name: &str,
+    options: &ReadOptions,
+) -> Result<Vec<Document>> {
+    if !options.utf8_lossy {
+        return read_collection_data(database, collection_name).await;
+    }
+
     // Validate collection name to prevent injection
     crate::jsonb::validate_table_name(collection_name)
         .context("Invalid collection name for data reading")?;
 
     tracing::info!(
-        "Reading all documents from collection '{}'",
+        "Reading all documents from collection '{}' (utf8_lossy recovery enabled)",
         collection_name
     );
 
-    let collection = database.collection::<Document>(collection_name);
+    let collection = database.collection_with_type::<RawDocumentBuf>(collection_name);
 
     let mut cursor = collection
         .find(None, None)
@@ -172,14 +403,41 @@ pub async fn read_collection_data(
 
     let mut documents = Vec::new();
 
-    use futures::stream::StreamExt;
     while let Some(result) = cursor.next().await {
-        let document = result.with_context(|| {
+        let raw = result.with_context(|| {
             format!(
                 "Failed to read document from collection '{}'",
                 collection_name
             )
         })?;
+
+        let document = match bson::from_slice::<Document>(raw.as_bytes()) {
+            Ok(document) => document,
+            Err(_) => {
+                let sanitized = sanitize_document_utf8(raw.as_bytes());
+                let document: Document = bson::from_slice(&sanitized).with_context(|| {
+                    format!(
+                        "Failed to decode document from collection '{}' even after \
+                         UTF-8 lossy recovery",
+                        collection_name
+                    )
+                })?;
+
+                let id = document
+                    .get("_id")
+                    .map(|id| id.to_string())
+                    .unwrap_or_else(|| "<no _id>".to_string());
+                tracing::warn!(
+                    "Document '{}' in collection '{}' contained invalid UTF-8; \
+                     recovered with lossy replacement",
+                    id,
+                    collection_name
+                );
+
+                document
+            }
+        };
+
         documents.push(document);
     }
 
@@ -192,6 +450,232 @@ pub async fn read_collection_data(
     Ok(documents)
 }
 
+/// Read documents from a collection created after a watermark, for resumable
+/// incremental syncs
+///
+/// MongoDB's default `_id` is a 12-byte ObjectId whose first 4 bytes are the
+/// creation time (Unix seconds, big-endian). `since` is converted into a synthetic
+/// lower-bound ObjectId with those timestamp bytes followed by 8 zero bytes - the
+/// smallest possible ObjectId for any document created at or after `since` - and
+/// documents are read with `{ _id: { $gt: lower_bound } }`, sorted ascending by
+/// `_id`. Querying `CLOCK_SKEW_SAFETY_WINDOW` before `since` re-reads a small
+/// overlap so a document whose server clock lagged slightly isn't missed.
+///
+/// Falls back to a full [`read_collection_data`] if the collection's `_id` values
+/// aren't ObjectIds, since the timestamp-prefix trick doesn't apply.
+///
+/// Pass the result through [`max_object_id`] to compute the next call's `since`.
+///
+/// # Arguments
+///
+/// * `database` - MongoDB database reference
+/// * `collection_name` - Collection name (must be validated)
+/// * `since` - Watermark: only documents created at or after this time (minus the
+///   clock-skew safety window) are returned
+///
+/// # Returns
+///
+/// Vector of BSON documents created since the watermark, oldest first
+///
+/// # Security
+///
+/// - Collection name is validated before querying
+/// - Read-only operation, no modifications possible
+pub async fn read_collection_since(
+    database: &Database,
+    collection_name: &str,
+    since: DateTime,
+) -> Result<Vec<Document>> {
+    crate::jsonb::validate_table_name(collection_name)
+        .context("Invalid collection name for data reading")?;
+
+    let collection = database.collection::<Document>(collection_name);
+
+    // Peek a document to check whether `_id` is actually an ObjectId; if not, the
+    // timestamp-prefix trick doesn't apply.
+    let sample = collection
+        .find_one(None, None)
+        .await
+        .with_context(|| format!("Failed to probe collection '{}' for _id type", collection_name))?;
+    let uses_object_id = matches!(
+        sample.as_ref().and_then(|doc| doc.get("_id")),
+        Some(Bson::ObjectId(_))
+    );
+
+    if !uses_object_id {
+        tracing::warn!(
+            "Collection '{}' doesn't use ObjectId '_id' values; falling back to a full read \
+             instead of an incremental one",
+            collection_name
+        );
+        return read_collection_data(database, collection_name).await;
+    }
+
+    let watermark_millis = since.timestamp_millis() - CLOCK_SKEW_SAFETY_WINDOW.as_millis() as i64;
+    let lower_bound = lower_bound_object_id(DateTime::from_millis(watermark_millis.max(0)));
+
+    tracing::info!(
+        "Reading documents from collection '{}' created since {} (watermark _id {})",
+        collection_name,
+        since,
+        lower_bound
+    );
+
+    let query = doc! { "_id": { "$gt": lower_bound } };
+    let options = FindOptions::builder().sort(doc! { "_id": 1 }).build();
+
+    let mut cursor = collection
+        .find(query, options)
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to query collection '{}' incrementally",
+                collection_name
+            )
+        })?;
+
+    let mut documents = Vec::new();
+    while let Some(result) = cursor.next().await {
+        documents.push(result.with_context(|| {
+            format!(
+                "Failed to read document from collection '{}'",
+                collection_name
+            )
+        })?);
+    }
+
+    tracing::info!(
+        "Read {} documents from collection '{}' since watermark",
+        documents.len(),
+        collection_name
+    );
+
+    Ok(documents)
+}
+
+/// Compute the next watermark for [`read_collection_since`]: the latest `_id`
+/// among `documents`. Returns `None` if none of them have an ObjectId `_id` (e.g.
+/// the read fell back to a full scan), in which case the caller should keep
+/// whatever watermark it already had.
+pub fn max_object_id(documents: &[Document]) -> Option<ObjectId> {
+    documents
+        .iter()
+        .filter_map(|document| document.get_object_id("_id").ok().copied())
+        .max()
+}
+
+/// Build a synthetic ObjectId usable as a `{ _id: { $gt: ... } }` lower bound: the
+/// timestamp's Unix seconds packed big-endian into the first 4 bytes (ObjectId's
+/// creation-time prefix), followed by 8 zero bytes
+fn lower_bound_object_id(timestamp: DateTime) -> ObjectId {
+    let seconds = (timestamp.timestamp_millis() / 1000) as u32;
+    let mut bytes = [0u8; 12];
+    bytes[0..4].copy_from_slice(&seconds.to_be_bytes());
+    ObjectId::from_bytes(bytes)
+}
+
+/// Re-encode a raw BSON document buffer, replacing invalid UTF-8 in string-bearing
+/// elements (string, JS code, symbol) with U+FFFD rather than the bytes the driver
+/// would otherwise refuse to decode. Recurses into embedded documents and arrays;
+/// other element types are copied through unchanged since their length doesn't
+/// depend on UTF-8 validity.
+fn sanitize_document_utf8(buf: &[u8]) -> Vec<u8> {
+    let end = buf.len().saturating_sub(1); // index of the document's terminating 0x00
+    let mut pos = 4; // skip the 4-byte length prefix
+    let mut body = Vec::new();
+
+    while pos < end {
+        let element_type = buf[pos];
+        pos += 1;
+
+        let name_start = pos;
+        while buf[pos] != 0 {
+            pos += 1;
+        }
+        let name_end = pos;
+        pos += 1; // skip the name's terminating 0x00
+
+        let value_len = bson_value_len(element_type, &buf[pos..]);
+        let value = &buf[pos..pos + value_len];
+        pos += value_len;
+
+        body.push(element_type);
+        body.extend_from_slice(&buf[name_start..name_end]);
+        body.push(0);
+        body.extend_from_slice(&sanitize_value_utf8(element_type, value));
+    }
+
+    let total_len = (4 + body.len() + 1) as i32;
+    let mut out = Vec::with_capacity(total_len as usize);
+    out.extend_from_slice(&total_len.to_le_bytes());
+    out.extend_from_slice(&body);
+    out.push(0);
+    out
+}
+
+/// Length in bytes of a single element's value, given its BSON type tag, not
+/// including the type tag or element name that precede it
+fn bson_value_len(element_type: u8, buf: &[u8]) -> usize {
+    match element_type {
+        0x01 /* double */ => 8,
+        0x02 | 0x0D | 0x0E /* string, JS code, symbol */ => {
+            4 + i32::from_le_bytes(buf[0..4].try_into().unwrap()) as usize
+        }
+        0x03 | 0x04 /* embedded document, array */ => {
+            i32::from_le_bytes(buf[0..4].try_into().unwrap()) as usize
+        }
+        0x05 /* binary */ => 5 + i32::from_le_bytes(buf[0..4].try_into().unwrap()) as usize,
+        0x06 | 0x0A /* undefined, null */ => 0,
+        0x07 /* objectid */ => 12,
+        0x08 /* bool */ => 1,
+        0x09 /* UTC datetime */ => 8,
+        0x0B /* regex: pattern cstring + options cstring */ => {
+            let mut i = 0;
+            while buf[i] != 0 {
+                i += 1;
+            }
+            i += 1;
+            while buf[i] != 0 {
+                i += 1;
+            }
+            i + 1
+        }
+        0x0C /* DBPointer (deprecated): string + 12-byte objectid */ => {
+            4 + i32::from_le_bytes(buf[0..4].try_into().unwrap()) as usize + 12
+        }
+        0x0F /* JS code with scope: self-describing total length */ => {
+            i32::from_le_bytes(buf[0..4].try_into().unwrap()) as usize
+        }
+        0x10 /* int32 */ => 4,
+        0x11 /* timestamp */ | 0x12 /* int64 */ => 8,
+        0x13 /* decimal128 */ => 16,
+        0x7F | 0xFF /* max key, min key */ => 0,
+        _ => 0,
+    }
+}
+
+/// Rebuild a single element's value with lossy UTF-8 replacement where it carries a
+/// string, recursing into embedded documents/arrays; everything else is returned
+/// unchanged
+fn sanitize_value_utf8(element_type: u8, value: &[u8]) -> Vec<u8> {
+    match element_type {
+        0x02 | 0x0D | 0x0E => {
+            let raw_len = i32::from_le_bytes(value[0..4].try_into().unwrap()) as usize;
+            let string_bytes = &value[4..4 + raw_len - 1]; // excludes the terminating 0x00
+            let lossy = String::from_utf8_lossy(string_bytes);
+            let lossy_bytes = lossy.as_bytes();
+
+            let mut out = Vec::with_capacity(4 + lossy_bytes.len() + 1);
+            out.extend_from_slice(&((lossy_bytes.len() + 1) as i32).to_le_bytes());
+            out.extend_from_slice(lossy_bytes);
+            out.push(0);
+            out
+        }
+        0x03 | 0x04 => sanitize_document_utf8(value),
+        _ => value.to_vec(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -229,4 +713,100 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_sanitize_document_utf8_repairs_invalid_string() {
+        use super::sanitize_document_utf8;
+        use bson::{doc, Document};
+
+        let original = doc! { "name": "valid", "count": 3_i32 };
+        let mut bytes = bson::to_vec(&original).unwrap();
+
+        // Corrupt one byte of the "name" string's value so it's no longer valid UTF-8.
+        let corrupt_at = bytes
+            .windows(5)
+            .position(|window| window == b"valid")
+            .expect("encoded string bytes should be present in the buffer");
+        bytes[corrupt_at] = 0xFF;
+
+        // The driver's strict decode should reject the corrupted buffer...
+        assert!(bson::from_slice::<Document>(&bytes).is_err());
+
+        // ...but the sanitizer should repair it into a decodable document.
+        let sanitized = sanitize_document_utf8(&bytes);
+        let document: Document = bson::from_slice(&sanitized).unwrap();
+        assert_eq!(document.get_i32("count").unwrap(), 3);
+        assert!(document.get_str("name").unwrap().contains('\u{FFFD}'));
+    }
+
+    #[test]
+    fn test_sanitize_document_utf8_preserves_valid_document() {
+        use super::sanitize_document_utf8;
+        use bson::{doc, Document};
+
+        let original = doc! { "name": "valid", "nested": { "flag": true } };
+        let bytes = bson::to_vec(&original).unwrap();
+
+        let sanitized = sanitize_document_utf8(&bytes);
+        let document: Document = bson::from_slice(&sanitized).unwrap();
+        assert_eq!(document, original);
+    }
+
+    #[test]
+    fn test_lower_bound_object_id_packs_seconds_prefix() {
+        use super::lower_bound_object_id;
+        use bson::DateTime;
+
+        let timestamp = DateTime::from_millis(1_700_000_000_000);
+        let object_id = lower_bound_object_id(timestamp);
+
+        let bytes = object_id.bytes();
+        assert_eq!(
+            u32::from_be_bytes(bytes[0..4].try_into().unwrap()),
+            1_700_000_000
+        );
+        assert_eq!(&bytes[4..12], &[0u8; 8]);
+    }
+
+    #[test]
+    fn test_lower_bound_object_id_is_smaller_than_same_second_ids() {
+        use super::lower_bound_object_id;
+        use bson::oid::ObjectId;
+        use bson::DateTime;
+
+        let timestamp = DateTime::from_millis(1_700_000_000_000);
+        let lower_bound = lower_bound_object_id(timestamp);
+
+        // Any ObjectId actually minted at that same second has non-zero trailing
+        // bytes, so it must compare greater than our synthetic all-zero-tail bound.
+        let real_id = ObjectId::parse_str("6553f100aabbccddeeff0011").unwrap();
+        assert!(real_id > lower_bound);
+    }
+
+    #[test]
+    fn test_max_object_id_picks_latest_and_ignores_missing() {
+        use super::max_object_id;
+        use bson::oid::ObjectId;
+        use bson::{doc, Document};
+
+        let older = ObjectId::parse_str("5f00000000000000000000aa").unwrap();
+        let newer = ObjectId::parse_str("65f0000000000000000000bb").unwrap();
+
+        let documents: Vec<Document> = vec![
+            doc! { "_id": older, "v": 1_i32 },
+            doc! { "_id": newer, "v": 2_i32 },
+            doc! { "_id": "not-an-object-id", "v": 3_i32 },
+        ];
+
+        assert_eq!(max_object_id(&documents), Some(newer));
+    }
+
+    #[test]
+    fn test_max_object_id_empty_when_no_object_ids() {
+        use super::max_object_id;
+        use bson::{doc, Document};
+
+        let documents: Vec<Document> = vec![doc! { "_id": "no-object-ids-here" }];
+        assert_eq!(max_object_id(&documents), None);
+    }
 }
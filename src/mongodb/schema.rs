@@ -0,0 +1,537 @@
+// ABOUTME: Schema inference and typed-column mapping for MongoDB collections
+// ABOUTME: Samples documents to derive a PostgreSQL table shape instead of one JSONB blob per row
+
+use anyhow::{Context, Result};
+use bson::{doc, Bson, Document};
+use futures::stream::StreamExt;
+use mongodb::Database;
+use serde_json::Value as JsonValue;
+
+use crate::mongodb::converter::{bson_to_json, ConversionMode};
+use crate::mongodb::reader::get_collection_count;
+
+/// Number of documents sampled to infer a collection's schema when the
+/// collection is larger than the sample size.
+pub const DEFAULT_SAMPLE_SIZE: usize = 1000;
+
+/// PostgreSQL column type inferred from one or more BSON field values.
+///
+/// Conflicting scalar types observed for the same field widen according to
+/// [`merge_types`]; nested documents and arrays always map to `Jsonb` since
+/// they have no flat relational representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InferredType {
+    Boolean,
+    Integer,
+    BigInt,
+    Double,
+    Numeric,
+    Text,
+    Bytea,
+    Timestamptz,
+    Jsonb,
+}
+
+impl InferredType {
+    /// Render the PostgreSQL type name used in a `CREATE TABLE` column definition.
+    pub fn pg_type_name(&self) -> &'static str {
+        match self {
+            InferredType::Boolean => "boolean",
+            InferredType::Integer => "integer",
+            InferredType::BigInt => "bigint",
+            InferredType::Double => "double precision",
+            InferredType::Numeric => "numeric",
+            InferredType::Text => "text",
+            InferredType::Bytea => "bytea",
+            InferredType::Timestamptz => "timestamptz",
+            InferredType::Jsonb => "jsonb",
+        }
+    }
+}
+
+/// One inferred column of a [`CollectionSchema`].
+#[derive(Debug, Clone)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub pg_type: InferredType,
+    pub nullable: bool,
+    pub is_primary_key: bool,
+}
+
+/// Inferred relational shape of a MongoDB collection.
+///
+/// Columns appear in first-seen order across the sample, with `_id` always
+/// first since it is always present.
+#[derive(Debug, Clone)]
+pub struct CollectionSchema {
+    pub table_name: String,
+    pub columns: Vec<ColumnSchema>,
+}
+
+/// Widen two previously-observed column types into one that can hold both.
+///
+/// `Jsonb` is sticky: once a field has been seen as a nested document or
+/// array, it stays `Jsonb` no matter what else is observed. Numeric types
+/// widen among themselves (`Integer` → `BigInt` → `Double` → `Numeric`).
+/// Any other mismatch (e.g. a field that's sometimes a string, sometimes a
+/// boolean) falls back to `Text`, which can hold either's textual form.
+fn merge_types(a: InferredType, b: InferredType) -> InferredType {
+    use InferredType::*;
+
+    if a == b {
+        return a;
+    }
+    if a == Jsonb || b == Jsonb {
+        return Jsonb;
+    }
+
+    let is_numeric = |t: InferredType| matches!(t, Integer | BigInt | Double | Numeric);
+    if is_numeric(a) && is_numeric(b) {
+        return match (a, b) {
+            (Numeric, _) | (_, Numeric) => Numeric,
+            (Double, _) | (_, Double) => Double,
+            (BigInt, _) | (_, BigInt) => BigInt,
+            _ => Integer,
+        };
+    }
+
+    Text
+}
+
+/// Classify a single non-null BSON value.
+///
+/// Returns `None` for `Null`/`Undefined`, which constrain nullability but not
+/// the column's type.
+fn classify_bson(value: &Bson) -> Option<InferredType> {
+    match value {
+        Bson::Boolean(_) => Some(InferredType::Boolean),
+        Bson::Int32(_) => Some(InferredType::Integer),
+        Bson::Int64(_) => Some(InferredType::BigInt),
+        Bson::Double(_) => Some(InferredType::Double),
+        Bson::Decimal128(_) => Some(InferredType::Numeric),
+        Bson::String(_) | Bson::ObjectId(_) => Some(InferredType::Text),
+        Bson::DateTime(_) => Some(InferredType::Timestamptz),
+        Bson::Binary(_) => Some(InferredType::Bytea),
+        Bson::Document(_) | Bson::Array(_) => Some(InferredType::Jsonb),
+        Bson::Null | Bson::Undefined => None,
+        // Regex/Timestamp/MaxKey/MinKey and any future BSON extras have no
+        // native relational representation; store them losslessly as JSONB
+        // rather than guessing a scalar type for them.
+        _ => Some(InferredType::Jsonb),
+    }
+}
+
+/// Fetch a representative sample of documents from a collection.
+///
+/// Collections no larger than `sample_size` are read in full via
+/// [`read_collection_data`](crate::mongodb::reader::read_collection_data).
+/// Larger collections use MongoDB's `$sample` aggregation stage, which
+/// performs server-side reservoir sampling instead of pulling every document
+/// over the wire.
+///
+/// # Security
+///
+/// Collection name should be validated before calling this function.
+pub async fn sample_documents(
+    database: &Database,
+    collection_name: &str,
+    sample_size: usize,
+) -> Result<Vec<Document>> {
+    crate::jsonb::validate_table_name(collection_name)
+        .context("Invalid collection name for schema sampling")?;
+
+    let total = get_collection_count(database, collection_name).await?;
+
+    if total <= sample_size {
+        tracing::info!(
+            "Sampling collection '{}' in full ({} documents) for schema inference",
+            collection_name,
+            total
+        );
+        return crate::mongodb::reader::read_collection_data(database, collection_name).await;
+    }
+
+    tracing::info!(
+        "Collection '{}' has {} documents; sampling {} via $sample for schema inference",
+        collection_name,
+        total,
+        sample_size
+    );
+
+    let collection = database.collection::<Document>(collection_name);
+    let pipeline = vec![doc! { "$sample": { "size": sample_size as i64 } }];
+
+    let cursor = collection
+        .aggregate(pipeline, None)
+        .await
+        .with_context(|| format!("Failed to sample collection '{}'", collection_name))?;
+
+    cursor
+        .map(|result| {
+            result.with_context(|| {
+                format!(
+                    "Failed to read sampled document from collection '{}'",
+                    collection_name
+                )
+            })
+        })
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect()
+}
+
+/// Infer a [`CollectionSchema`] from a set of sampled documents.
+///
+/// Each top-level field's type is the union of every non-null value observed
+/// for it, widened via [`merge_types`]. A field is nullable if it was ever
+/// absent or `null` in the sample. `_id` is always the first column and is
+/// never nullable; if its inferred type would be `Jsonb` (e.g. a compound
+/// `_id` document), it falls back to `Text` since PostgreSQL has no default
+/// btree operator class for `jsonb` and so cannot use it as a primary key.
+pub fn infer_schema(table_name: &str, documents: &[Document]) -> CollectionSchema {
+    let mut field_order: Vec<String> = Vec::new();
+    let mut types: std::collections::HashMap<String, InferredType> =
+        std::collections::HashMap::new();
+    let mut present_count: std::collections::HashMap<String, usize> =
+        std::collections::HashMap::new();
+    let mut ever_null: std::collections::HashSet<String> = std::collections::HashSet::new();
+
+    for document in documents {
+        for (key, value) in document.iter() {
+            if !field_order.contains(key) {
+                field_order.push(key.clone());
+            }
+
+            match classify_bson(value) {
+                Some(inferred) => {
+                    types
+                        .entry(key.clone())
+                        .and_modify(|existing| *existing = merge_types(*existing, inferred))
+                        .or_insert(inferred);
+                    *present_count.entry(key.clone()).or_insert(0) += 1;
+                }
+                None => {
+                    ever_null.insert(key.clone());
+                }
+            }
+        }
+    }
+
+    let total = documents.len();
+    let columns = field_order
+        .into_iter()
+        .map(|name| {
+            let is_primary_key = name == "_id";
+            let mut pg_type = types.get(&name).copied().unwrap_or(InferredType::Text);
+            if is_primary_key && pg_type == InferredType::Jsonb {
+                pg_type = InferredType::Text;
+            }
+
+            let present = present_count.get(&name).copied().unwrap_or(0);
+            let nullable = !is_primary_key && (present < total || ever_null.contains(&name));
+
+            ColumnSchema {
+                name,
+                pg_type,
+                nullable,
+                is_primary_key,
+            }
+        })
+        .collect();
+
+    CollectionSchema {
+        table_name: table_name.to_string(),
+        columns,
+    }
+}
+
+/// Sample a collection and infer its schema in one call.
+///
+/// # Security
+///
+/// Collection name should be validated before calling this function.
+pub async fn infer_collection_schema(
+    database: &Database,
+    collection_name: &str,
+    sample_size: usize,
+) -> Result<CollectionSchema> {
+    let documents = sample_documents(database, collection_name, sample_size).await?;
+    Ok(infer_schema(collection_name, &documents))
+}
+
+/// Render a `CREATE TABLE` statement for an inferred schema.
+///
+/// # Security
+///
+/// `schema.table_name` and column names come from MongoDB collection/field
+/// names and are not escaped beyond double-quoting; validate the collection
+/// name with [`validate_table_name`](crate::jsonb::validate_table_name)
+/// before inferring a schema from untrusted input.
+pub fn render_create_table(schema: &CollectionSchema) -> String {
+    let column_defs: Vec<String> = schema
+        .columns
+        .iter()
+        .map(|column| {
+            let mut def = format!("\"{}\" {}", column.name, column.pg_type.pg_type_name());
+            if column.is_primary_key {
+                def.push_str(" PRIMARY KEY");
+            } else if !column.nullable {
+                def.push_str(" NOT NULL");
+            }
+            def
+        })
+        .collect();
+
+    format!(
+        "CREATE TABLE \"{}\" (\n    {}\n)",
+        schema.table_name,
+        column_defs.join(",\n    ")
+    )
+}
+
+/// Convert one document into a row matching `schema`'s column order.
+///
+/// Each cell is encoded as the [`serde_json::Value`] representation of its
+/// column's `pg_type` (e.g. a `Text` column yields a JSON string, a
+/// `Jsonb` column yields the full nested value via [`bson_to_json`]), ready
+/// to bind as a query parameter with an explicit `::type` cast. A narrower
+/// numeric value in a widened numeric column is converted to that column's
+/// type. A field that's missing, `null`, or doesn't match its column's type
+/// (possible when a later document in the full collection disagrees with the
+/// sample) is encoded as JSON `null`, except in a `Text` column, which takes
+/// any other value's debug representation.
+pub fn document_to_row(document: &Document, schema: &CollectionSchema) -> Vec<JsonValue> {
+    schema
+        .columns
+        .iter()
+        .map(|column| encode_column_value(document.get(&column.name), column.pg_type))
+        .collect()
+}
+
+fn encode_column_value(value: Option<&Bson>, pg_type: InferredType) -> JsonValue {
+    let Some(value) = value else {
+        return JsonValue::Null;
+    };
+    if matches!(value, Bson::Null | Bson::Undefined) {
+        return JsonValue::Null;
+    }
+
+    match pg_type {
+        InferredType::Boolean => value
+            .as_bool()
+            .map(JsonValue::Bool)
+            .unwrap_or(JsonValue::Null),
+        InferredType::Integer => value
+            .as_i32()
+            .map(|v| serde_json::json!(v))
+            .unwrap_or(JsonValue::Null),
+        InferredType::BigInt => match value {
+            Bson::Int32(v) => serde_json::json!(i64::from(*v)),
+            Bson::Int64(v) => serde_json::json!(v),
+            _ => JsonValue::Null,
+        },
+        InferredType::Double => match value {
+            Bson::Int32(v) => serde_json::json!(f64::from(*v)),
+            Bson::Int64(v) => serde_json::json!(*v as f64),
+            Bson::Double(v) => serde_json::json!(v),
+            _ => JsonValue::Null,
+        },
+        InferredType::Numeric => match value {
+            Bson::Decimal128(dec) => JsonValue::String(dec.to_string()),
+            Bson::Int32(v) => JsonValue::String(v.to_string()),
+            Bson::Int64(v) => JsonValue::String(v.to_string()),
+            Bson::Double(v) => JsonValue::String(v.to_string()),
+            _ => JsonValue::Null,
+        },
+        InferredType::Text => match value {
+            Bson::String(s) => JsonValue::String(s.clone()),
+            Bson::ObjectId(oid) => JsonValue::String(oid.to_hex()),
+            // A field that widened to Text due to mixed scalar types across
+            // the sample; fall back to its debug representation.
+            other => JsonValue::String(format!("{:?}", other)),
+        },
+        InferredType::Bytea => match value {
+            Bson::Binary(bin) => {
+                let encoded =
+                    base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bin.bytes);
+                JsonValue::String(encoded)
+            }
+            _ => JsonValue::Null,
+        },
+        InferredType::Timestamptz => match value {
+            Bson::DateTime(dt) => serde_json::json!(dt.timestamp_millis()),
+            _ => JsonValue::Null,
+        },
+        InferredType::Jsonb => {
+            bson_to_json(value, ConversionMode::Relaxed).unwrap_or(JsonValue::Null)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::oid::ObjectId;
+
+    #[test]
+    fn test_merge_types_identical() {
+        assert_eq!(
+            merge_types(InferredType::Text, InferredType::Text),
+            InferredType::Text
+        );
+    }
+
+    #[test]
+    fn test_merge_types_numeric_widening() {
+        assert_eq!(
+            merge_types(InferredType::Integer, InferredType::BigInt),
+            InferredType::BigInt
+        );
+        assert_eq!(
+            merge_types(InferredType::BigInt, InferredType::Double),
+            InferredType::Double
+        );
+        assert_eq!(
+            merge_types(InferredType::Double, InferredType::Numeric),
+            InferredType::Numeric
+        );
+    }
+
+    #[test]
+    fn test_merge_types_jsonb_is_sticky() {
+        assert_eq!(
+            merge_types(InferredType::Jsonb, InferredType::Text),
+            InferredType::Jsonb
+        );
+        assert_eq!(
+            merge_types(InferredType::Boolean, InferredType::Jsonb),
+            InferredType::Jsonb
+        );
+    }
+
+    #[test]
+    fn test_merge_types_incompatible_scalars_fall_back_to_text() {
+        assert_eq!(
+            merge_types(InferredType::Boolean, InferredType::Text),
+            InferredType::Text
+        );
+        assert_eq!(
+            merge_types(InferredType::Integer, InferredType::Text),
+            InferredType::Text
+        );
+    }
+
+    #[test]
+    fn test_infer_schema_basic_types() {
+        let docs = vec![
+            doc! { "_id": ObjectId::new(), "name": "Alice", "age": 30, "active": true },
+            doc! { "_id": ObjectId::new(), "name": "Bob", "age": 25, "active": false },
+        ];
+        let schema = infer_schema("users", &docs);
+
+        let id_col = schema.columns.iter().find(|c| c.name == "_id").unwrap();
+        assert!(id_col.is_primary_key);
+        assert!(!id_col.nullable);
+        assert_eq!(id_col.pg_type, InferredType::Text);
+
+        let name_col = schema.columns.iter().find(|c| c.name == "name").unwrap();
+        assert_eq!(name_col.pg_type, InferredType::Text);
+        assert!(!name_col.nullable);
+
+        let age_col = schema.columns.iter().find(|c| c.name == "age").unwrap();
+        assert_eq!(age_col.pg_type, InferredType::Integer);
+    }
+
+    #[test]
+    fn test_infer_schema_missing_field_is_nullable() {
+        let docs = vec![
+            doc! { "_id": ObjectId::new(), "email": "alice@example.com" },
+            doc! { "_id": ObjectId::new() },
+        ];
+        let schema = infer_schema("users", &docs);
+
+        let email_col = schema.columns.iter().find(|c| c.name == "email").unwrap();
+        assert!(email_col.nullable);
+    }
+
+    #[test]
+    fn test_infer_schema_nested_document_becomes_jsonb() {
+        let docs = vec![doc! { "_id": ObjectId::new(), "address": { "city": "NYC" } }];
+        let schema = infer_schema("users", &docs);
+
+        let address_col = schema.columns.iter().find(|c| c.name == "address").unwrap();
+        assert_eq!(address_col.pg_type, InferredType::Jsonb);
+    }
+
+    #[test]
+    fn test_infer_schema_compound_id_falls_back_to_text() {
+        let docs = vec![doc! { "_id": { "a": 1, "b": 2 } }];
+        let schema = infer_schema("users", &docs);
+
+        let id_col = schema.columns.iter().find(|c| c.name == "_id").unwrap();
+        assert_eq!(id_col.pg_type, InferredType::Text);
+        assert!(id_col.is_primary_key);
+        assert!(!id_col.nullable);
+    }
+
+    #[test]
+    fn test_render_create_table() {
+        let schema = CollectionSchema {
+            table_name: "users".to_string(),
+            columns: vec![
+                ColumnSchema {
+                    name: "_id".to_string(),
+                    pg_type: InferredType::Text,
+                    nullable: false,
+                    is_primary_key: true,
+                },
+                ColumnSchema {
+                    name: "age".to_string(),
+                    pg_type: InferredType::Integer,
+                    nullable: true,
+                    is_primary_key: false,
+                },
+            ],
+        };
+        let sql = render_create_table(&schema);
+        assert!(sql.contains("CREATE TABLE \"users\""));
+        assert!(sql.contains("\"_id\" text PRIMARY KEY"));
+        assert!(sql.contains("\"age\" integer"));
+        assert!(!sql.contains("\"age\" integer NOT NULL"));
+    }
+
+    #[test]
+    fn test_document_to_row_encodes_by_column_type() {
+        let schema = CollectionSchema {
+            table_name: "users".to_string(),
+            columns: vec![
+                ColumnSchema {
+                    name: "_id".to_string(),
+                    pg_type: InferredType::Text,
+                    nullable: false,
+                    is_primary_key: true,
+                },
+                ColumnSchema {
+                    name: "age".to_string(),
+                    pg_type: InferredType::Integer,
+                    nullable: true,
+                    is_primary_key: false,
+                },
+                ColumnSchema {
+                    name: "missing".to_string(),
+                    pg_type: InferredType::Text,
+                    nullable: true,
+                    is_primary_key: false,
+                },
+            ],
+        };
+        let oid = ObjectId::new();
+        let document = doc! { "_id": oid, "age": 30 };
+        let row = document_to_row(&document, &schema);
+
+        assert_eq!(row[0], JsonValue::String(oid.to_hex()));
+        assert_eq!(row[1], serde_json::json!(30));
+        assert_eq!(row[2], JsonValue::Null);
+    }
+}
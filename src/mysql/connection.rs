@@ -0,0 +1,29 @@
+// ABOUTME: MySQL/MariaDB connection utilities, mirroring postgres::connection's shape
+// ABOUTME: Handles connection string validation and pool-backed connection lifecycle
+
+use anyhow::{Context, Result};
+use mysql_async::Pool;
+
+/// Connect to a MySQL/MariaDB database
+///
+/// Establishes a connection pool from a `mysql://` connection string. Unlike
+/// [`crate::postgres::connect`], which hands back a single long-lived `Client`,
+/// this returns a `Pool`: callers check out a connection per operation via
+/// `Pool::get_conn`, which is how `mysql_async` expects to be used.
+///
+/// # Errors
+///
+/// Returns an error if an initial connection can't be established, so a bad
+/// connection string or unreachable server fails immediately rather than on
+/// whatever query happens to run first.
+pub async fn connect(connection_string: &str) -> Result<Pool> {
+    let pool = Pool::new(connection_string);
+
+    // Eagerly validate the connection by checking out (and dropping) a
+    // connection now, instead of deferring the first error to later.
+    pool.get_conn()
+        .await
+        .context("Failed to connect to MySQL source")?;
+
+    Ok(pool)
+}
@@ -0,0 +1,122 @@
+// ABOUTME: MySQL value to JSON conversion, mirroring mongodb::converter's bson_to_json
+// ABOUTME: Used to turn a MySQL JSON column's raw bytes into the value a jsonb target expects
+
+use anyhow::{Context, Result};
+use mysql_async::Value as MysqlValue;
+use serde_json::Value as JsonValue;
+
+/// Convert a `mysql_async::Value` to JSON
+///
+/// Maps MySQL's wire-level value representation to JSON:
+/// - `NULL` → null
+/// - `Int`/`UInt` → number
+/// - `Float`/`Double` → number (non-finite values are stored as plain strings
+///   such as `"NaN"`, not in the `$numberDouble` wrapper
+///   [`crate::mongodb::converter::bson_to_json`] uses for non-finite doubles)
+/// - `Bytes` → string (UTF-8 lossy; this also covers `JSON` columns, whose
+///   bytes are the column's JSON text and are parsed as such rather than
+///   treated as an opaque string)
+/// - `Date`/`Time` → ISO-8601-ish string
+///
+/// # Errors
+///
+/// Returns an error only if a `JSON` column's bytes aren't valid UTF-8 JSON,
+/// since that would mean the source database itself has a malformed value.
+pub fn mysql_value_to_json(value: &MysqlValue, is_json_column: bool) -> Result<JsonValue> {
+    match value {
+        MysqlValue::NULL => Ok(JsonValue::Null),
+        MysqlValue::Int(i) => Ok(JsonValue::Number((*i).into())),
+        MysqlValue::UInt(u) => Ok(JsonValue::Number((*u).into())),
+        MysqlValue::Float(f) => Ok(finite_or_string(*f as f64, f.to_string())),
+        MysqlValue::Double(d) => Ok(finite_or_string(*d, d.to_string())),
+        MysqlValue::Bytes(bytes) => {
+            let text = String::from_utf8_lossy(bytes).into_owned();
+            if is_json_column {
+                serde_json::from_str(&text)
+                    .with_context(|| format!("Invalid JSON in MySQL JSON column: {}", text))
+            } else {
+                Ok(JsonValue::String(text))
+            }
+        }
+        MysqlValue::Date(year, month, day, hour, minute, second, micro) => {
+            Ok(JsonValue::String(format!(
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}",
+                year, month, day, hour, minute, second, micro
+            )))
+        }
+        MysqlValue::Time(is_negative, days, hours, minutes, seconds, micro) => {
+            let sign = if *is_negative { "-" } else { "" };
+            let total_hours = u32::from(*hours) + days * 24;
+            Ok(JsonValue::String(format!(
+                "{}{:02}:{:02}:{:02}.{:06}",
+                sign, total_hours, minutes, seconds, micro
+            )))
+        }
+    }
+}
+
+/// Store a non-finite float as its plain string representation -
+/// `serde_json::Number` has no representation for `NaN`/`Infinity`
+fn finite_or_string(value: f64, as_string: String) -> JsonValue {
+    if value.is_finite() {
+        serde_json::Number::from_f64(value)
+            .map(JsonValue::Number)
+            .unwrap_or(JsonValue::String(as_string))
+    } else {
+        JsonValue::String(as_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mysql_value_to_json_null() {
+        assert_eq!(mysql_value_to_json(&MysqlValue::NULL, false).unwrap(), JsonValue::Null);
+    }
+
+    #[test]
+    fn test_mysql_value_to_json_integers() {
+        assert_eq!(
+            mysql_value_to_json(&MysqlValue::Int(-42), false).unwrap(),
+            serde_json::json!(-42)
+        );
+        assert_eq!(
+            mysql_value_to_json(&MysqlValue::UInt(42), false).unwrap(),
+            serde_json::json!(42)
+        );
+    }
+
+    #[test]
+    fn test_mysql_value_to_json_non_finite_float_becomes_string() {
+        let json = mysql_value_to_json(&MysqlValue::Double(f64::NAN), false).unwrap();
+        assert_eq!(json, JsonValue::String("NaN".to_string()));
+    }
+
+    #[test]
+    fn test_mysql_value_to_json_json_column_is_parsed() {
+        let value = MysqlValue::Bytes(br#"{"a":1}"#.to_vec());
+        let json = mysql_value_to_json(&value, true).unwrap();
+        assert_eq!(json, serde_json::json!({"a": 1}));
+    }
+
+    #[test]
+    fn test_mysql_value_to_json_plain_bytes_become_string() {
+        let value = MysqlValue::Bytes(b"hello".to_vec());
+        let json = mysql_value_to_json(&value, false).unwrap();
+        assert_eq!(json, JsonValue::String("hello".to_string()));
+    }
+
+    #[test]
+    fn test_mysql_value_to_json_invalid_json_column_errors() {
+        let value = MysqlValue::Bytes(b"not json".to_vec());
+        assert!(mysql_value_to_json(&value, true).is_err());
+    }
+
+    #[test]
+    fn test_mysql_value_to_json_date() {
+        let json = mysql_value_to_json(&MysqlValue::Date(2024, 1, 15, 10, 30, 0, 0), false).unwrap();
+        assert_eq!(json, JsonValue::String("2024-01-15 10:30:00.000000".to_string()));
+    }
+}
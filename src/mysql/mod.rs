@@ -0,0 +1,10 @@
+// ABOUTME: MySQL/MariaDB utilities module, gated behind the `mysql` Cargo feature
+// ABOUTME: Exports connection management, schema introspection, and value conversion
+
+pub mod connection;
+pub mod converter;
+pub mod schema;
+
+pub use connection::connect;
+pub use converter::mysql_value_to_json;
+pub use schema::{describe_columns, list_tables, map_mysql_type};
@@ -0,0 +1,246 @@
+// ABOUTME: Schema introspection utilities for MySQL/MariaDB sources
+// ABOUTME: Mirrors migration::schema's shape, plus MySQL -> PostgreSQL type mapping
+
+use anyhow::{Context, Result};
+use mysql_async::prelude::Queryable;
+use mysql_async::Pool;
+
+use crate::migration::TableInfo;
+
+/// A single column as reported by [`describe_columns`], still in MySQL's own
+/// vocabulary - [`map_mysql_type`] is what translates `data_type`/`is_unsigned`
+/// into the PostgreSQL type a target schema should use for it
+#[derive(Debug, Clone)]
+pub struct MysqlColumnInfo {
+    pub name: String,
+    /// Base type name (`int`, `varchar`, `enum`, ...), from
+    /// `information_schema.columns.data_type`
+    pub data_type: String,
+    /// Whether `column_type` contains the `unsigned` modifier - needed
+    /// because MySQL's unsigned integers don't fit PostgreSQL's signed-only
+    /// integer types of the same width
+    pub is_unsigned: bool,
+    pub is_nullable: bool,
+}
+
+/// List all base tables in the MySQL connection's default database
+///
+/// # Errors
+///
+/// Returns an error if a connection can't be checked out or the query fails.
+pub async fn list_tables(pool: &Pool) -> Result<Vec<TableInfo>> {
+    let mut conn = pool
+        .get_conn()
+        .await
+        .context("Failed to check out MySQL connection")?;
+
+    let rows: Vec<(String, Option<i64>)> = conn
+        .query(
+            "SELECT table_name, table_rows FROM information_schema.tables \
+             WHERE table_schema = DATABASE() AND table_type = 'BASE TABLE' \
+             ORDER BY table_name",
+        )
+        .await
+        .context("Failed to list MySQL tables")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(name, row_count_estimate)| TableInfo {
+            // MySQL has no separate schema namespace within a connection's
+            // database the way PostgreSQL does - every table lives directly
+            // under `DATABASE()`, so `schema` is left empty rather than
+            // invented.
+            schema: String::new(),
+            name,
+            row_count_estimate: row_count_estimate.unwrap_or(0),
+        })
+        .collect())
+}
+
+/// Describe the columns of `table` in the connection's default database
+///
+/// # Errors
+///
+/// Returns an error if `table` isn't a valid identifier, a connection can't
+/// be checked out, or the query fails.
+pub async fn describe_columns(pool: &Pool, table: &str) -> Result<Vec<MysqlColumnInfo>> {
+    crate::jsonb::validate_table_name(table)?;
+
+    let mut conn = pool
+        .get_conn()
+        .await
+        .context("Failed to check out MySQL connection")?;
+
+    let rows: Vec<(String, String, String, String)> = conn
+        .exec(
+            "SELECT column_name, data_type, column_type, is_nullable \
+             FROM information_schema.columns \
+             WHERE table_schema = DATABASE() AND table_name = ? \
+             ORDER BY ordinal_position",
+            (table,),
+        )
+        .await
+        .with_context(|| format!("Failed to describe MySQL table '{}'", table))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(name, data_type, column_type, is_nullable)| MysqlColumnInfo {
+            name,
+            is_unsigned: column_type.to_ascii_lowercase().contains("unsigned"),
+            is_nullable: is_nullable.eq_ignore_ascii_case("YES"),
+            data_type,
+        })
+        .collect())
+}
+
+/// Map a MySQL column's base type to the PostgreSQL type that should hold it
+/// on the target, following the crate's intermediate representation:
+///
+/// - Unsigned integers widen to the next-larger signed PostgreSQL integer
+///   type, since PostgreSQL has no unsigned integer types of its own and a
+///   same-width signed type would silently truncate large values
+/// - `JSON` maps straight to `jsonb` - see [`crate::jsonb`] and
+///   [`super::converter::mysql_value_to_json`] for the matching value-level
+///   conversion
+/// - `ENUM`/`SET` have no PostgreSQL equivalent without a matching
+///   `CREATE TYPE`, so they flatten to `text`
+///
+/// Any type name not recognized falls back to `text`, which can always hold
+/// whatever MySQL sends even if it isn't the most precise target type.
+pub fn map_mysql_type(data_type: &str, is_unsigned: bool) -> &'static str {
+    match data_type.to_ascii_lowercase().as_str() {
+        "tinyint" | "smallint" => {
+            if is_unsigned {
+                "integer"
+            } else {
+                "smallint"
+            }
+        }
+        "mediumint" | "int" => {
+            if is_unsigned {
+                "bigint"
+            } else {
+                "integer"
+            }
+        }
+        "bigint" => {
+            if is_unsigned {
+                "numeric"
+            } else {
+                "bigint"
+            }
+        }
+        "float" => "real",
+        "double" | "decimal" => "double precision",
+        "json" => "jsonb",
+        "enum" | "set" => "text",
+        "datetime" | "timestamp" => "timestamp",
+        "date" => "date",
+        "time" => "time",
+        "tinyblob" | "blob" | "mediumblob" | "longblob" | "varbinary" | "binary" => "bytea",
+        "bit" => "bit varying",
+        _ => "text",
+    }
+}
+
+/// Build the source-wide type map a [`crate::source::Source`] implementation
+/// hands back from `type_map()`, covering every base type name
+/// [`describe_columns`] can report so [`map_mysql_type`] doesn't need to be
+/// called again downstream. Built once per [`crate::source::MySqlSource`]
+/// rather than per-column, since the mapping doesn't depend on unsignedness
+/// for most types - the one exception (integers) is covered by listing both
+/// the signed type name here and letting callers that care about
+/// unsignedness go through [`map_mysql_type`] directly instead.
+pub fn build_type_map() -> crate::source::TypeMap {
+    const BASE_TYPES: &[&str] = &[
+        "tinyint",
+        "smallint",
+        "mediumint",
+        "int",
+        "bigint",
+        "float",
+        "double",
+        "decimal",
+        "json",
+        "enum",
+        "set",
+        "datetime",
+        "timestamp",
+        "date",
+        "time",
+        "char",
+        "varchar",
+        "tinytext",
+        "text",
+        "mediumtext",
+        "longtext",
+        "tinyblob",
+        "blob",
+        "mediumblob",
+        "longblob",
+        "varbinary",
+        "binary",
+        "bit",
+    ];
+
+    BASE_TYPES
+        .iter()
+        .map(|&data_type| (data_type.to_string(), map_mysql_type(data_type, false).to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_mysql_type_signed_integers() {
+        assert_eq!(map_mysql_type("tinyint", false), "smallint");
+        assert_eq!(map_mysql_type("int", false), "integer");
+        assert_eq!(map_mysql_type("bigint", false), "bigint");
+    }
+
+    #[test]
+    fn test_map_mysql_type_unsigned_integers_widen() {
+        assert_eq!(map_mysql_type("tinyint", true), "integer");
+        assert_eq!(map_mysql_type("int", true), "bigint");
+        assert_eq!(map_mysql_type("bigint", true), "numeric");
+    }
+
+    #[test]
+    fn test_map_mysql_type_json_maps_to_jsonb() {
+        assert_eq!(map_mysql_type("json", false), "jsonb");
+    }
+
+    #[test]
+    fn test_map_mysql_type_enum_and_set_flatten_to_text() {
+        assert_eq!(map_mysql_type("enum", false), "text");
+        assert_eq!(map_mysql_type("set", false), "text");
+    }
+
+    #[test]
+    fn test_map_mysql_type_unknown_falls_back_to_text() {
+        assert_eq!(map_mysql_type("geometry", false), "text");
+    }
+
+    #[test]
+    fn test_build_type_map_covers_json_and_integers() {
+        let map = build_type_map();
+        assert_eq!(map.get("json"), Some(&"jsonb".to_string()));
+        assert_eq!(map.get("int"), Some(&"integer".to_string()));
+        assert_eq!(map.get("varchar"), Some(&"text".to_string()));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_list_tables() {
+        let url = std::env::var("TEST_MYSQL_SOURCE_URL").unwrap();
+        let pool = crate::mysql::connect(&url).await.unwrap();
+
+        let tables = list_tables(&pool).await.unwrap();
+        println!("Found {} tables", tables.len());
+        for table in &tables {
+            println!("  - {} ({} rows)", table.name, table.row_count_estimate);
+        }
+    }
+}
@@ -94,6 +94,119 @@ pub fn requires_preload(extension_name: &str) -> bool {
     PRELOAD_REQUIRED_EXTENSIONS.contains(&extension_name)
 }
 
+/// A single problem found with one source extension by [`check_extension_compatibility`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtensionIssue {
+    /// Installed on the source, but not listed in the target's `pg_available_extensions`
+    Missing,
+    /// Available on the target, but its installed (or default) version doesn't match
+    /// the source's `extversion`
+    VersionMismatch { source: String, target_default: String },
+    /// In [`PRELOAD_REQUIRED_EXTENSIONS`], but absent from the target's
+    /// `shared_preload_libraries`
+    PreloadNotConfigured,
+}
+
+/// Compatibility problems found for a single source extension
+#[derive(Debug, Clone)]
+pub struct ExtensionCompatibility {
+    pub name: String,
+    pub issues: Vec<ExtensionIssue>,
+}
+
+impl ExtensionCompatibility {
+    /// True if no issues were found for this extension
+    pub fn is_compatible(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Extension-compatibility reconciliation of a source database's extensions against a
+/// target database, as built by [`check_extension_compatibility`]; extensions present
+/// only on the target are not reported
+#[derive(Debug, Clone, Default)]
+pub struct CompatibilityReport {
+    pub extensions: Vec<ExtensionCompatibility>,
+}
+
+impl CompatibilityReport {
+    /// True if every extension in the report has no issues
+    pub fn is_compatible(&self) -> bool {
+        self.extensions.iter().all(|ext| ext.is_compatible())
+    }
+}
+
+/// Reconcile every extension installed on `source` (excluding `plpgsql`) against what's
+/// available on `target`, catching the common failure where e.g. `timescaledb`/`citus`
+/// exist on the source but the target is missing the preload or carries an incompatible
+/// version - something that otherwise only surfaces as a cryptic subscription error mid-sync.
+///
+/// For each source extension, checks:
+/// - [`ExtensionIssue::Missing`] if `target` doesn't list it in `pg_available_extensions`
+/// - [`ExtensionIssue::VersionMismatch`] if it's available on `target` but the source's
+///   `extversion` doesn't match the target's installed (or default) version
+/// - [`ExtensionIssue::PreloadNotConfigured`] if [`requires_preload`] is true for it but
+///   it's absent from `target`'s `shared_preload_libraries` (see [`get_preloaded_libraries`])
+///
+/// # Errors
+///
+/// Returns an error if querying installed/available extensions or preloaded libraries
+/// on either database fails.
+pub async fn check_extension_compatibility(
+    source: &Client,
+    target: &Client,
+) -> Result<CompatibilityReport> {
+    let source_extensions = get_installed_extensions(source)
+        .await
+        .context("Failed to get source extensions")?;
+    let target_available = get_available_extensions(target)
+        .await
+        .context("Failed to get target available extensions")?;
+    let target_preloaded = get_preloaded_libraries(target)
+        .await
+        .context("Failed to get target preloaded libraries")?;
+
+    let mut extensions = Vec::with_capacity(source_extensions.len());
+
+    for source_ext in &source_extensions {
+        let mut issues = Vec::new();
+        let target_ext = target_available
+            .iter()
+            .find(|candidate| candidate.name == source_ext.name);
+
+        match target_ext {
+            None => issues.push(ExtensionIssue::Missing),
+            Some(target_ext) => {
+                if let Some(target_version) = target_ext
+                    .installed_version
+                    .as_ref()
+                    .or(target_ext.default_version.as_ref())
+                {
+                    if *target_version != source_ext.version {
+                        issues.push(ExtensionIssue::VersionMismatch {
+                            source: source_ext.version.clone(),
+                            target_default: target_version.clone(),
+                        });
+                    }
+                }
+
+                if requires_preload(&source_ext.name)
+                    && !target_preloaded.iter().any(|lib| lib == &source_ext.name)
+                {
+                    issues.push(ExtensionIssue::PreloadNotConfigured);
+                }
+            }
+        }
+
+        extensions.push(ExtensionCompatibility {
+            name: source_ext.name.clone(),
+            issues,
+        });
+    }
+
+    Ok(CompatibilityReport { extensions })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,4 +219,34 @@ mod tests {
         assert!(!requires_preload("pg_trgm"));
         assert!(!requires_preload("uuid-ossp"));
     }
+
+    #[test]
+    fn test_compatibility_report_is_compatible_with_no_issues() {
+        let report = CompatibilityReport {
+            extensions: vec![ExtensionCompatibility {
+                name: "pg_trgm".to_string(),
+                issues: Vec::new(),
+            }],
+        };
+        assert!(report.is_compatible());
+    }
+
+    #[test]
+    fn test_compatibility_report_is_incompatible_with_any_issue() {
+        let report = CompatibilityReport {
+            extensions: vec![
+                ExtensionCompatibility {
+                    name: "pg_trgm".to_string(),
+                    issues: Vec::new(),
+                },
+                ExtensionCompatibility {
+                    name: "timescaledb".to_string(),
+                    issues: vec![ExtensionIssue::PreloadNotConfigured],
+                },
+            ],
+        };
+        assert!(!report.is_compatible());
+        assert!(report.extensions[0].is_compatible());
+        assert!(!report.extensions[1].is_compatible());
+    }
 }
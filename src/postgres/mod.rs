@@ -1,15 +1,42 @@
 // ABOUTME: PostgreSQL utilities module
 // ABOUTME: Exports connection management and common database operations
+//
+// `connection`, `extensions`, `pool`, and `privileges` are all built on
+// `tokio-postgres`, which needs native sockets and doesn't target
+// `wasm32-unknown-unknown`. On that target only `wasm` is compiled, which
+// defines the injected-driver-adapter extension point a real wasm build
+// would need instead - see `wasm`'s module doc for what that does and
+// doesn't cover yet.
 
+#[cfg(not(target_arch = "wasm32"))]
 pub mod connection;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod extensions;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod pool;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod privileges;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod serverless;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
 
-pub use connection::connect;
+#[cfg(not(target_arch = "wasm32"))]
+pub use connection::{connect, connect_with_retry_until_elapsed};
+#[cfg(not(target_arch = "wasm32"))]
 pub use extensions::{
-    get_available_extensions, get_installed_extensions, get_preloaded_libraries, requires_preload,
-    AvailableExtension, Extension,
+    check_extension_compatibility, get_available_extensions, get_installed_extensions,
+    get_preloaded_libraries, requires_preload, AvailableExtension, CompatibilityReport,
+    Extension, ExtensionCompatibility, ExtensionIssue,
 };
+#[cfg(not(target_arch = "wasm32"))]
+pub use pool::{ConnectionPool, PgPool, PgPoolOptions, PooledConnection};
+#[cfg(not(target_arch = "wasm32"))]
 pub use privileges::{
-    check_source_privileges, check_target_privileges, check_wal_level, PrivilegeCheck,
+    check_replication_readiness, check_source_privileges, check_target_privileges,
+    check_wal_level, PrivilegeCheck, ReadinessIssue, ReadinessSeverity, ReplicationReadiness,
 };
+#[cfg(not(target_arch = "wasm32"))]
+pub use serverless::TargetBackend;
+#[cfg(target_arch = "wasm32")]
+pub use wasm::connect;
@@ -0,0 +1,255 @@
+// ABOUTME: Bounded pool of PostgreSQL connections for concurrent table operations
+// ABOUTME: Used by commands that copy/verify many tables in parallel (e.g. init, verify)
+
+use super::connect;
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_postgres::Client;
+
+/// A small pool of ready-to-use connections to a single database
+///
+/// Unlike a generic connection pool, this is sized once up front for a fixed amount
+/// of concurrent work (e.g. `--jobs N`) rather than growing/shrinking on demand:
+/// commands that process a known list of tables open exactly `size` connections and
+/// hand them out round-robin to `size` concurrent workers, giving each worker its own
+/// connection for the lifetime of the run.
+pub struct ConnectionPool {
+    clients: Vec<Client>,
+}
+
+impl ConnectionPool {
+    /// Open `size` connections to `connection_string`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any connection fails to establish.
+    pub async fn new(connection_string: &str, size: usize) -> Result<Self> {
+        let size = size.max(1);
+        let mut clients = Vec::with_capacity(size);
+        for _ in 0..size {
+            clients.push(connect(connection_string).await?);
+        }
+        Ok(Self { clients })
+    }
+
+    /// Number of connections held by the pool
+    pub fn size(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Borrow the `idx`-th connection, wrapping around the pool size
+    ///
+    /// Intended for round-robin assignment: give worker `idx` a stable connection
+    /// to use for the duration of its task.
+    pub fn client(&self, idx: usize) -> &Client {
+        &self.clients[idx % self.clients.len()]
+    }
+}
+
+/// Configuration for [`PgPool`]
+///
+/// Mirrors the min/max size, idle timeout, and connection-acquire timeout knobs of a
+/// typical connection pool; all fields have workable defaults, so callers only need
+/// to set the ones they care about.
+#[derive(Debug, Clone)]
+pub struct PgPoolOptions {
+    min_size: usize,
+    max_size: usize,
+    idle_timeout: Duration,
+    acquire_timeout: Duration,
+}
+
+impl Default for PgPoolOptions {
+    fn default() -> Self {
+        Self {
+            min_size: 0,
+            max_size: 5,
+            idle_timeout: Duration::from_secs(300),
+            acquire_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl PgPoolOptions {
+    /// Start from the default options (`min_size` 0, `max_size` 5, 5 minute idle
+    /// timeout, 30 second acquire timeout)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connections kept open (even if idle) rather than dropped after `idle_timeout`
+    pub fn min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Connections this pool will hold open at once; callers beyond this block until
+    /// one is checked in or `acquire_timeout` elapses
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// How long a connection may sit idle in the pool before it's dropped instead of
+    /// being handed out on the next checkout
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// How long [`PgPool::get`] waits for a free connection before giving up
+    pub fn acquire_timeout(mut self, acquire_timeout: Duration) -> Self {
+        self.acquire_timeout = acquire_timeout;
+        self
+    }
+
+    /// Build a [`PgPool`] to `connection_string`, eagerly opening `min_size`
+    /// connections
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if one of the `min_size` initial connections fails to
+    /// establish.
+    pub async fn build(self, connection_string: &str) -> Result<PgPool> {
+        let max_size = self.max_size.max(1);
+        let min_size = self.min_size.min(max_size);
+
+        let mut idle = Vec::with_capacity(max_size);
+        for _ in 0..min_size {
+            idle.push(IdleConnection {
+                client: connect(connection_string).await?,
+                idled_at: Instant::now(),
+            });
+        }
+
+        Ok(PgPool {
+            inner: Arc::new(PgPoolInner {
+                connection_string: connection_string.to_string(),
+                idle: tokio::sync::Mutex::new(idle),
+                semaphore: Arc::new(Semaphore::new(max_size)),
+                options: self,
+            }),
+        })
+    }
+}
+
+struct IdleConnection {
+    client: Client,
+    idled_at: Instant,
+}
+
+struct PgPoolInner {
+    connection_string: String,
+    idle: tokio::sync::Mutex<Vec<IdleConnection>>,
+    semaphore: Arc<Semaphore>,
+    options: PgPoolOptions,
+}
+
+/// A dynamically-sized pool of connections to a single database
+///
+/// Unlike [`ConnectionPool`], which opens a fixed number of connections up front for
+/// a known amount of concurrent work, `PgPool` is for commands that make many short,
+/// sequential connections to the *same* database over the life of a run - e.g.
+/// polling subscription status every few seconds. Checking a connection back in
+/// keeps it warm for the next caller instead of paying a fresh TLS handshake every
+/// time; connections are still built through [`connect`], so they get the same
+/// TLS/`sslmode` handling as everywhere else. Cheaply `Clone`-able - clones share the
+/// same underlying pool.
+#[derive(Clone)]
+pub struct PgPool {
+    inner: Arc<PgPoolInner>,
+}
+
+impl PgPool {
+    /// Build a pool to `connection_string` with the given `max_size` and otherwise
+    /// default options - a shorthand for `PgPoolOptions::new().max_size(max_size).build(...)`
+    /// for callers that don't need to tune idle/acquire timeouts
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if opening the pool's initial connections fails.
+    pub async fn new(connection_string: &str, max_size: usize) -> Result<Self> {
+        PgPoolOptions::new()
+            .max_size(max_size)
+            .build(connection_string)
+            .await
+    }
+
+    /// Check out a connection, reusing an idle one if a healthy one is available, or
+    /// opening a new one if not (and the pool has room under `max_size`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no connection becomes available within `acquire_timeout`,
+    /// or if opening a new connection fails.
+    pub async fn get(&self) -> Result<PooledConnection> {
+        let permit = tokio::time::timeout(
+            self.inner.options.acquire_timeout,
+            Arc::clone(&self.inner.semaphore).acquire_owned(),
+        )
+        .await
+        .context("Timed out waiting for a pooled connection")?
+        .expect("pool semaphore is never closed");
+
+        loop {
+            let candidate = self.inner.idle.lock().await.pop();
+            let Some(IdleConnection { client, idled_at }) = candidate else {
+                let client = connect(&self.inner.connection_string).await?;
+                return Ok(PooledConnection {
+                    inner: Arc::clone(&self.inner),
+                    client: Some(client),
+                    _permit: permit,
+                });
+            };
+
+            // Idle too long, or the server dropped it since check-in - discard and
+            // try the next idle connection (or open a fresh one).
+            if idled_at.elapsed() > self.inner.options.idle_timeout {
+                continue;
+            }
+            if client.simple_query("SELECT 1").await.is_err() {
+                continue;
+            }
+
+            return Ok(PooledConnection {
+                inner: Arc::clone(&self.inner),
+                client: Some(client),
+                _permit: permit,
+            });
+        }
+    }
+}
+
+/// A checked-out [`PgPool`] connection
+///
+/// Derefs to `&Client` for use at call sites that take one. Checks itself back into
+/// the pool when dropped, so callers don't need to do anything explicit to return it.
+pub struct PooledConnection {
+    inner: Arc<PgPoolInner>,
+    client: Option<Client>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl std::ops::Deref for PooledConnection {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        self.client.as_ref().expect("connection already checked in")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            let inner = Arc::clone(&self.inner);
+            tokio::spawn(async move {
+                inner.idle.lock().await.push(IdleConnection {
+                    client,
+                    idled_at: Instant::now(),
+                });
+            });
+        }
+    }
+}
@@ -141,6 +141,170 @@ pub async fn check_wal_level(client: &Client) -> Result<String> {
     Ok(wal_level)
 }
 
+/// Severity of a single [`ReplicationReadiness`] problem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadinessSeverity {
+    /// Would fail a subscription outright (e.g. no replication slots left)
+    Blocking,
+    /// Worth flagging before migrating, but wouldn't necessarily break it
+    Advisory,
+}
+
+/// A single problem found by [`check_replication_readiness`]
+#[derive(Debug, Clone)]
+pub struct ReadinessIssue {
+    pub severity: ReadinessSeverity,
+    pub message: String,
+}
+
+/// Logical-replication capacity on a source database: configured limits, how much of
+/// each is already in use, and any headroom problems worth surfacing before a
+/// migration starts
+#[derive(Debug, Clone)]
+pub struct ReplicationReadiness {
+    pub max_replication_slots: i32,
+    pub used_replication_slots: i64,
+    pub max_wal_senders: i32,
+    pub active_wal_senders: i64,
+    pub max_worker_processes: i32,
+    /// Name of a managed-provider logical replication flag (e.g.
+    /// `rds.logical_replication`) if one is present and disabled
+    pub managed_provider_flag_disabled: Option<String>,
+    pub issues: Vec<ReadinessIssue>,
+}
+
+impl ReplicationReadiness {
+    /// True if any issue is [`ReadinessSeverity::Blocking`]
+    pub fn has_blocking_issues(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == ReadinessSeverity::Blocking)
+    }
+}
+
+/// Check whether the source has enough replication-slot/WAL-sender headroom to take
+/// on another subscription, and surface managed-provider flags that gate logical
+/// replication independently of `wal_level`
+///
+/// `wal_level = logical` (see [`check_wal_level`]) is necessary but not sufficient: a
+/// subscription also needs a free replication slot and a free WAL sender process, and
+/// managed providers like RDS/Aurora gate logical replication behind their own flag
+/// (`rds.logical_replication`) even when `wal_level` is already `logical`. This
+/// queries `max_replication_slots`/`max_wal_senders`/`max_worker_processes` alongside
+/// how many replication slots and WAL senders are already in use (worker-process
+/// usage is not queried), returning a structured list of blocking vs. advisory
+/// problems so callers (e.g. `validate`) can print an actionable checklist instead of
+/// failing mid-migration when a subscription can't actually be created.
+///
+/// # Errors
+///
+/// Returns an error if any of the underlying queries fail.
+pub async fn check_replication_readiness(client: &Client) -> Result<ReplicationReadiness> {
+    let max_replication_slots = show_int_setting(client, "max_replication_slots").await?;
+    let max_wal_senders = show_int_setting(client, "max_wal_senders").await?;
+    let max_worker_processes = show_int_setting(client, "max_worker_processes").await?;
+
+    let used_replication_slots: i64 = client
+        .query_one("SELECT count(*) FROM pg_replication_slots", &[])
+        .await
+        .context("Failed to count existing replication slots")?
+        .get(0);
+
+    let active_wal_senders: i64 = client
+        .query_one("SELECT count(*) FROM pg_stat_replication", &[])
+        .await
+        .context("Failed to count active WAL senders")?
+        .get(0);
+
+    let managed_provider_flag_disabled = check_managed_provider_flag(client).await?;
+
+    let mut issues = Vec::new();
+
+    let free_slots = i64::from(max_replication_slots) - used_replication_slots;
+    if free_slots <= 0 {
+        issues.push(ReadinessIssue {
+            severity: ReadinessSeverity::Blocking,
+            message: format!(
+                "All {} replication slot(s) are already in use; a new subscription needs a free slot. Increase max_replication_slots or drop an unused slot.",
+                max_replication_slots
+            ),
+        });
+    } else if free_slots <= 1 {
+        issues.push(ReadinessIssue {
+            severity: ReadinessSeverity::Advisory,
+            message: format!(
+                "Only {} replication slot(s) free out of {}; little headroom for retries or additional subscriptions.",
+                free_slots, max_replication_slots
+            ),
+        });
+    }
+
+    if active_wal_senders >= i64::from(max_wal_senders) {
+        issues.push(ReadinessIssue {
+            severity: ReadinessSeverity::Blocking,
+            message: format!(
+                "All {} WAL sender process(es) are already in use; a new subscription needs a free one. Increase max_wal_senders.",
+                max_wal_senders
+            ),
+        });
+    }
+
+    if let Some(setting) = &managed_provider_flag_disabled {
+        issues.push(ReadinessIssue {
+            severity: ReadinessSeverity::Blocking,
+            message: format!(
+                "Managed-provider logical replication flag '{}' is disabled; enable it before creating a publication.",
+                setting
+            ),
+        });
+    }
+
+    Ok(ReplicationReadiness {
+        max_replication_slots,
+        used_replication_slots,
+        max_wal_senders,
+        active_wal_senders,
+        max_worker_processes,
+        managed_provider_flag_disabled,
+        issues,
+    })
+}
+
+/// `SHOW <setting>` and parse the result as an integer
+async fn show_int_setting(client: &Client, setting: &str) -> Result<i32> {
+    let row = client
+        .query_one(&format!("SHOW {}", setting), &[])
+        .await
+        .with_context(|| format!("Failed to query {} setting", setting))?;
+
+    let value: String = row.get(0);
+    value
+        .parse()
+        .with_context(|| format!("Unexpected non-numeric value for {}: '{}'", setting, value))
+}
+
+/// Check `rds.logical_replication` (the RDS/Aurora gate for logical replication),
+/// returning the setting's name if it's present and disabled
+async fn check_managed_provider_flag(client: &Client) -> Result<Option<String>> {
+    let rows = client
+        .query(
+            "SELECT name, setting FROM pg_settings WHERE name = 'rds.logical_replication'",
+            &[],
+        )
+        .await
+        .context("Failed to query managed-provider replication settings")?;
+
+    for row in rows {
+        let name: String = row.get(0);
+        let setting: String = row.get(1);
+        if setting == "0" || setting.eq_ignore_ascii_case("off") {
+            return Ok(Some(name));
+        }
+    }
+
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,165 @@
+// ABOUTME: TOML selection-profile format for replaying interactive choices non-interactively
+// ABOUTME: Lets a vetted `select_databases_and_tables` run be checked in and reused by CI
+
+use crate::{
+    filters::ReplicationFilter,
+    table_rules::{QualifiedTable, TableRules},
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One schema-only table recorded in a [`SelectionProfile`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaOnlyEntry {
+    pub database: String,
+    pub schema: String,
+    pub table: String,
+}
+
+/// One time-filtered table recorded in a [`SelectionProfile`]
+///
+/// Stores the raw `column`/`window` passed to [`TableRules::add_time_filter`] rather
+/// than the rendered predicate, so [`load_profile`] reconstructs the exact same filter
+/// instead of trying to parse one back out of SQL text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeFilterEntry {
+    pub database: String,
+    pub schema: String,
+    pub table: String,
+    pub column: String,
+    pub window: String,
+}
+
+/// A recorded set of choices from [`crate::interactive::select_databases_and_tables`],
+/// serializable to TOML so a vetted configuration can be replayed non-interactively
+/// (e.g. in CI, or for a repeated migration) without a TTY
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SelectionProfile {
+    pub databases: Vec<String>,
+    #[serde(default)]
+    pub excluded_tables: Vec<String>,
+    #[serde(default)]
+    pub schema_only_tables: Vec<SchemaOnlyEntry>,
+    #[serde(default)]
+    pub time_filters: Vec<TimeFilterEntry>,
+}
+
+impl SelectionProfile {
+    /// Start a profile for the given set of selected databases, with no
+    /// exclusions, schema-only tables, or time filters yet
+    pub fn new(databases: Vec<String>) -> Self {
+        Self {
+            databases,
+            ..Self::default()
+        }
+    }
+}
+
+/// Write `profile` to `path` as TOML
+///
+/// # Errors
+///
+/// Returns an error if the profile can't be serialized or the file can't be written.
+pub fn save_profile(path: &Path, profile: &SelectionProfile) -> Result<()> {
+    let contents =
+        toml::to_string_pretty(profile).context("Failed to serialize selection profile")?;
+    std::fs::write(path, contents)
+        .with_context(|| format!("Failed to write selection profile to '{}'", path.display()))
+}
+
+/// Load a profile written by [`save_profile`] and reconstruct the exact
+/// `(ReplicationFilter, TableRules)` it represents, for non-interactive reuse
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read, isn't valid TOML matching
+/// [`SelectionProfile`]'s shape, or if any recorded table rule is invalid.
+pub fn load_profile(path: &Path) -> Result<(ReplicationFilter, TableRules)> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read selection profile '{}'", path.display()))?;
+    let profile: SelectionProfile = toml::from_str(&contents).with_context(|| {
+        format!(
+            "Failed to parse selection profile '{}' as TOML",
+            path.display()
+        )
+    })?;
+
+    let filter = if profile.excluded_tables.is_empty() {
+        ReplicationFilter::new(Some(profile.databases.clone()), None, None, None)?
+    } else {
+        ReplicationFilter::new(
+            Some(profile.databases.clone()),
+            None,
+            None,
+            Some(profile.excluded_tables.clone()),
+        )?
+    };
+
+    let mut rules = TableRules::default();
+
+    for entry in &profile.schema_only_tables {
+        let qualified = QualifiedTable::new(
+            Some(entry.database.clone()),
+            entry.schema.clone(),
+            entry.table.clone(),
+        );
+        rules.add_schema_only_table(qualified)?;
+    }
+
+    for entry in &profile.time_filters {
+        let qualified = QualifiedTable::new(
+            Some(entry.database.clone()),
+            entry.schema.clone(),
+            entry.table.clone(),
+        );
+        rules.add_time_filter(qualified, entry.column.clone(), entry.window.clone())?;
+    }
+
+    Ok((filter, rules))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load_profile_round_trips() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!(
+            "selection_profile_test_{}.toml",
+            std::process::id()
+        ));
+
+        let mut profile = SelectionProfile::new(vec!["mydb".to_string()]);
+        profile.excluded_tables.push("mydb.audit_log".to_string());
+        profile.schema_only_tables.push(SchemaOnlyEntry {
+            database: "mydb".to_string(),
+            schema: "public".to_string(),
+            table: "archive".to_string(),
+        });
+        profile.time_filters.push(TimeFilterEntry {
+            database: "mydb".to_string(),
+            schema: "public".to_string(),
+            table: "events".to_string(),
+            column: "created_at".to_string(),
+            window: "2 months".to_string(),
+        });
+
+        save_profile(&path, &profile).unwrap();
+        let (_filter, rules) = load_profile(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(
+            rules.schema_only_tables("mydb"),
+            vec!["archive".to_string()]
+        );
+        assert_eq!(rules.predicate_tables("mydb").len(), 1);
+    }
+
+    #[test]
+    fn test_load_profile_missing_file() {
+        let path = Path::new("/nonexistent/selection_profile.toml");
+        assert!(load_profile(path).is_err());
+    }
+}
@@ -0,0 +1,354 @@
+// ABOUTME: Postgres-backed task queue for the horizontally-scalable `worker` fleet
+// ABOUTME: Stores per-database/per-table replication tasks in a control table on the target
+
+use anyhow::{Context, Result};
+use tokio_postgres::Client;
+
+/// Name of the control table created on the target to hold replication tasks
+const TASKS_TABLE: &str = "_seren_replication_tasks";
+
+/// Sentinel stored in the `table_name` column for a whole-database task. A plain
+/// SQL `NULL` can't be used here: Postgres treats every `NULL` as distinct for the
+/// purposes of `UNIQUE (database, table_name)`, so re-enqueuing the same database
+/// would insert a duplicate row instead of being caught by `ON CONFLICT DO NOTHING`.
+const WHOLE_DATABASE_SENTINEL: &str = "";
+
+/// Default number of attempts before a failing task is left in `failed` state for
+/// good rather than rescheduled
+pub const DEFAULT_MAX_ATTEMPTS: i32 = 5;
+
+/// A unit of work a `worker` process can claim from the queue: either "stand up
+/// continuous replication for this whole database" (`table_name: None`, same as one
+/// iteration of [`crate::commands::sync`]) or "copy this one predicate-filtered
+/// table" (`table_name: Some(..)`, via [`crate::migration::copy_single_table`])
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplicationTask {
+    pub id: i64,
+    pub database: String,
+    pub table_name: Option<String>,
+    pub predicate: Option<String>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+}
+
+/// Task counts by status, as surfaced by `status`'s queue backlog summary
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueSummary {
+    pub pending: i64,
+    pub in_progress: i64,
+    pub done: i64,
+    pub failed: i64,
+}
+
+impl QueueSummary {
+    /// Total tasks across all statuses
+    pub fn total(&self) -> i64 {
+        self.pending + self.in_progress + self.done + self.failed
+    }
+}
+
+/// Create the `_seren_replication_tasks` control table on `client`'s database if it
+/// doesn't already exist
+///
+/// # Errors
+///
+/// Returns an error if the table can't be created.
+pub async fn ensure_queue_table(client: &Client) -> Result<()> {
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                id BIGSERIAL PRIMARY KEY,
+                database TEXT NOT NULL,
+                table_name TEXT NOT NULL DEFAULT '',
+                predicate TEXT,
+                status TEXT NOT NULL DEFAULT 'pending',
+                attempts INT NOT NULL DEFAULT 0,
+                last_error TEXT,
+                available_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                claimed_by TEXT,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                UNIQUE (database, table_name)
+            )",
+            table = TASKS_TABLE
+        ))
+        .await
+        .context("Failed to create _seren_replication_tasks control table")?;
+
+    Ok(())
+}
+
+/// Enqueue one whole-database task, plus one task per `filtered_tables` entry
+/// (`(qualified_name, predicate)` pairs, see [`crate::migration::copy_filtered_tables`]),
+/// for `database`. Tasks already present (matched by `(database, table_name)`) are
+/// left untouched, so re-running `worker` against an existing queue tops it up
+/// rather than duplicating or resetting in-flight work.
+///
+/// # Errors
+///
+/// Returns an error if the inserts fail.
+pub async fn enqueue_tasks(
+    client: &Client,
+    database: &str,
+    filtered_tables: &[(String, String)],
+) -> Result<()> {
+    client
+        .execute(
+            &format!(
+                "INSERT INTO {table} (database, table_name)
+                 VALUES ($1, $2)
+                 ON CONFLICT (database, table_name) DO NOTHING",
+                table = TASKS_TABLE
+            ),
+            &[&database, &WHOLE_DATABASE_SENTINEL],
+        )
+        .await
+        .with_context(|| format!("Failed to enqueue database task for '{}'", database))?;
+
+    for (qualified_name, predicate) in filtered_tables {
+        client
+            .execute(
+                &format!(
+                    "INSERT INTO {table} (database, table_name, predicate)
+                     VALUES ($1, $2, $3)
+                     ON CONFLICT (database, table_name) DO NOTHING",
+                    table = TASKS_TABLE
+                ),
+                &[&database, qualified_name, predicate],
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to enqueue table task for '{}.{}'",
+                    database, qualified_name
+                )
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Atomically claim the oldest available task for `claimed_by` using a single
+/// `UPDATE ... WHERE id = (SELECT ... FOR UPDATE SKIP LOCKED)` statement: the
+/// inner `SELECT` locks and skips rows other workers already hold, so concurrent
+/// `worker` processes never block on, or double-claim, the same row. Returns
+/// `None` once no task is `pending` and due (`available_at <= now()`).
+///
+/// `claimed_by` is recorded purely for observability (e.g. `status`); `SKIP
+/// LOCKED` plus the row lock taken by the inner `SELECT` is what actually
+/// prevents double-claims.
+///
+/// # Errors
+///
+/// Returns an error if the claim query fails.
+pub async fn claim_next_task(client: &Client, claimed_by: &str) -> Result<Option<ReplicationTask>> {
+    let row = client
+        .query_opt(
+            &format!(
+                "UPDATE {table}
+                 SET status = 'in_progress', claimed_by = $1, updated_at = now()
+                 WHERE id = (
+                     SELECT id FROM {table}
+                     WHERE status = 'pending' AND available_at <= now()
+                     ORDER BY id
+                     FOR UPDATE SKIP LOCKED
+                     LIMIT 1
+                 )
+                 RETURNING id, database, table_name, predicate, attempts, last_error",
+                table = TASKS_TABLE
+            ),
+            &[&claimed_by],
+        )
+        .await
+        .context("Failed to claim next task")?;
+
+    Ok(row.map(|row| {
+        let table_name: String = row.get(2);
+        ReplicationTask {
+            id: row.get(0),
+            database: row.get(1),
+            table_name: (table_name != WHOLE_DATABASE_SENTINEL).then_some(table_name),
+            predicate: row.get(3),
+            attempts: row.get(4),
+            last_error: row.get(5),
+        }
+    }))
+}
+
+/// Mark a claimed task as done
+///
+/// # Errors
+///
+/// Returns an error if the update fails.
+pub async fn complete_task(client: &Client, task_id: i64) -> Result<()> {
+    client
+        .execute(
+            &format!(
+                "UPDATE {} SET status = 'done', updated_at = now() WHERE id = $1",
+                TASKS_TABLE
+            ),
+            &[&task_id],
+        )
+        .await
+        .with_context(|| format!("Failed to mark task {} done", task_id))?;
+
+    Ok(())
+}
+
+/// Record a claimed task's failed attempt. Below `max_attempts`, the task is put
+/// back to `pending` with `available_at` pushed out by an exponential backoff
+/// (capped at 5 minutes) so it can be reclaimed later; at `max_attempts` it's left
+/// in `failed` state for good, which `status` surfaces in its queue summary.
+///
+/// # Errors
+///
+/// Returns an error if the update fails.
+pub async fn fail_task(
+    client: &Client,
+    task: &ReplicationTask,
+    error: &str,
+    max_attempts: i32,
+) -> Result<()> {
+    let attempts = task.attempts + 1;
+
+    if attempts >= max_attempts {
+        client
+            .execute(
+                &format!(
+                    "UPDATE {} SET status = 'failed', attempts = $1, last_error = $2,
+                     updated_at = now() WHERE id = $3",
+                    TASKS_TABLE
+                ),
+                &[&attempts, &error, &task.id],
+            )
+            .await
+            .with_context(|| format!("Failed to mark task {} failed", task.id))?;
+    } else {
+        let backoff_secs = backoff_seconds(attempts);
+        client
+            .execute(
+                &format!(
+                    "UPDATE {} SET status = 'pending', attempts = $1, last_error = $2,
+                     available_at = now() + make_interval(secs => $3), updated_at = now()
+                     WHERE id = $4",
+                    TASKS_TABLE
+                ),
+                &[&attempts, &error, &(backoff_secs as f64), &task.id],
+            )
+            .await
+            .with_context(|| format!("Failed to reschedule task {} after failure", task.id))?;
+    }
+
+    Ok(())
+}
+
+/// Exponential backoff delay, in seconds, before a task's `attempts`-th retry
+/// (2, 4, 8, 16, ... for attempts 1, 2, 3, 4, capped at 5 minutes)
+fn backoff_seconds(attempts: i32) -> i64 {
+    2i64.saturating_pow(attempts.max(0) as u32).min(300)
+}
+
+/// Whether the `_seren_replication_tasks` control table exists on `client`'s database
+///
+/// # Errors
+///
+/// Returns an error if the catalog lookup fails.
+pub async fn queue_table_exists(client: &Client) -> Result<bool> {
+    let row = client
+        .query_one(
+            "SELECT EXISTS (SELECT 1 FROM information_schema.tables WHERE table_name = $1)",
+            &[&TASKS_TABLE],
+        )
+        .await
+        .context("Failed to check for _seren_replication_tasks control table")?;
+
+    Ok(row.get(0))
+}
+
+/// Summarize task counts by status, for `status` to surface queue backlog/failures
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn summarize(client: &Client) -> Result<QueueSummary> {
+    let rows = client
+        .query(
+            &format!("SELECT status, count(*) FROM {} GROUP BY status", TASKS_TABLE),
+            &[],
+        )
+        .await
+        .context("Failed to summarize replication task queue")?;
+
+    let mut summary = QueueSummary::default();
+    for row in rows {
+        let status: String = row.get(0);
+        let count: i64 = row.get(1);
+        match status.as_str() {
+            "pending" => summary.pending = count,
+            "in_progress" => summary.in_progress = count,
+            "done" => summary.done = count,
+            "failed" => summary.failed = count,
+            other => tracing::warn!("Unrecognized replication task status '{}'", other),
+        }
+    }
+
+    Ok(summary)
+}
+
+/// List failed tasks (database, table, last error), for `status` to print alongside
+/// the summary counts
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn list_failed_tasks(client: &Client) -> Result<Vec<(String, Option<String>, String)>> {
+    let rows = client
+        .query(
+            &format!(
+                "SELECT database, table_name, last_error FROM {}
+                 WHERE status = 'failed'
+                 ORDER BY id",
+                TASKS_TABLE
+            ),
+            &[],
+        )
+        .await
+        .context("Failed to list failed replication tasks")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let database: String = row.get(0);
+            let table_name: String = row.get(1);
+            let last_error: Option<String> = row.get(2);
+            (
+                database,
+                (table_name != WHOLE_DATABASE_SENTINEL).then_some(table_name),
+                last_error.unwrap_or_else(|| "unknown error".to_string()),
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_seconds_doubles_and_caps() {
+        assert_eq!(backoff_seconds(1), 2);
+        assert_eq!(backoff_seconds(2), 4);
+        assert_eq!(backoff_seconds(3), 8);
+        assert_eq!(backoff_seconds(10), 300);
+    }
+
+    #[test]
+    fn test_queue_summary_total() {
+        let summary = QueueSummary {
+            pending: 2,
+            in_progress: 1,
+            done: 5,
+            failed: 1,
+        };
+        assert_eq!(summary.total(), 9);
+    }
+}
@@ -0,0 +1,210 @@
+// ABOUTME: Client for Seren's remote replication API (`init --remote`, `jobs ...`)
+// ABOUTME: Submits jobs, polls status, and streams logs for replication run on managed infrastructure
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Database/table filter for a remote job, mirroring [`crate::filters::ReplicationFilter`]
+/// in the shape the remote API expects
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FilterSpec {
+    pub include_databases: Option<Vec<String>>,
+    pub exclude_tables: Option<Vec<String>>,
+}
+
+/// A replication job submitted to the remote API
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JobSpec {
+    pub version: String,
+    pub command: String,
+    pub source_url: String,
+    pub target_url: String,
+    pub filter: Option<FilterSpec>,
+    pub options: HashMap<String, serde_json::Value>,
+    /// Per-database checkpoint to resume from, rather than starting each database's
+    /// snapshot/replication stream from scratch - see [`JobProgress::checkpoints`].
+    /// `None` for a fresh job.
+    pub resume_from: Option<HashMap<String, String>>,
+}
+
+/// Per-database progress of a running or finished job
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JobProgress {
+    pub databases_completed: u32,
+    pub databases_total: u32,
+    pub current_database: Option<String>,
+    /// Last confirmed WAL position per database (`confirmed_flush_lsn` for a database
+    /// already streaming, or the last committed key range for a database still on its
+    /// initial snapshot copy), checkpointed as the job makes progress so a dropped
+    /// connection can resume from here instead of restarting. Populated incrementally -
+    /// a database with no entry yet hasn't reached its first checkpoint.
+    #[serde(default)]
+    pub checkpoints: HashMap<String, String>,
+}
+
+/// Current state of a submitted job, as returned by `GET /jobs/:id`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JobStatus {
+    pub job_id: String,
+    pub status: String,
+    pub progress: Option<JobProgress>,
+    pub error: Option<String>,
+}
+
+/// One row of `GET /jobs`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JobSummary {
+    pub job_id: String,
+    pub status: String,
+}
+
+/// Response to submitting a job
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SubmitResponse {
+    pub job_id: String,
+}
+
+/// How often [`RemoteClient::poll_until_complete`] checks job status
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Client for the remote replication API backing `init --remote` and `jobs`
+///
+/// A thin wrapper over the API's HTTP endpoints - job submission and lifecycle
+/// management happen entirely on Seren's infrastructure; this client only submits
+/// [`JobSpec`]s and polls/displays [`JobStatus`].
+pub struct RemoteClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl RemoteClient {
+    /// Build a client targeting `base_url` (e.g. `https://api.seren.cloud/replication`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying HTTP client fails to build.
+    pub fn new(base_url: String) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .context("Failed to build HTTP client for remote API")?;
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http,
+        })
+    }
+
+    /// Submit a new replication job
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the API returns a non-success status.
+    pub async fn submit_job(&self, spec: &JobSpec) -> Result<SubmitResponse> {
+        self.http
+            .post(format!("{}/jobs", self.base_url))
+            .json(spec)
+            .send()
+            .await
+            .context("Failed to submit job to remote API")?
+            .error_for_status()
+            .context("Remote API rejected job submission")?
+            .json()
+            .await
+            .context("Failed to parse job submission response")
+    }
+
+    /// List jobs known to the remote API
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the API returns a non-success status.
+    pub async fn list_jobs(&self) -> Result<Vec<JobSummary>> {
+        self.http
+            .get(format!("{}/jobs", self.base_url))
+            .send()
+            .await
+            .context("Failed to list remote jobs")?
+            .error_for_status()
+            .context("Remote API rejected job listing request")?
+            .json()
+            .await
+            .context("Failed to parse job listing response")
+    }
+
+    /// Fetch a job's current status
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the API returns a non-success status.
+    pub async fn get_job_status(&self, job_id: &str) -> Result<JobStatus> {
+        self.http
+            .get(format!("{}/jobs/{}", self.base_url, job_id))
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch status for job '{}'", job_id))?
+            .error_for_status()
+            .with_context(|| format!("Remote API rejected status request for job '{}'", job_id))?
+            .json()
+            .await
+            .context("Failed to parse job status response")
+    }
+
+    /// Fetch the full log so far for a job
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the API returns a non-success status.
+    pub async fn get_job_logs(&self, job_id: &str) -> Result<Vec<String>> {
+        self.http
+            .get(format!("{}/jobs/{}/logs", self.base_url, job_id))
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch logs for job '{}'", job_id))?
+            .error_for_status()
+            .with_context(|| format!("Remote API rejected log request for job '{}'", job_id))?
+            .json()
+            .await
+            .context("Failed to parse job logs response")
+    }
+
+    /// Request cancellation of a running job
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the API returns a non-success status.
+    pub async fn cancel_job(&self, job_id: &str) -> Result<()> {
+        self.http
+            .post(format!("{}/jobs/{}/cancel", self.base_url, job_id))
+            .send()
+            .await
+            .with_context(|| format!("Failed to cancel job '{}'", job_id))?
+            .error_for_status()
+            .with_context(|| format!("Remote API rejected cancellation for job '{}'", job_id))?;
+        Ok(())
+    }
+
+    /// Poll a job's status every [`POLL_INTERVAL`] until it reaches a terminal state
+    /// (`completed` or `failed`), calling `on_update` with each status seen along the
+    /// way (including the final one)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any status request fails.
+    pub async fn poll_until_complete(
+        &self,
+        job_id: &str,
+        mut on_update: impl FnMut(&JobStatus),
+    ) -> Result<JobStatus> {
+        loop {
+            let status = self.get_job_status(job_id).await?;
+            on_update(&status);
+
+            if matches!(status.status.as_str(), "completed" | "failed") {
+                return Ok(status);
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
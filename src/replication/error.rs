@@ -0,0 +1,157 @@
+// ABOUTME: Typed error for logical replication operations (publications/subscriptions)
+// ABOUTME: Classifies tokio_postgres errors by SqlState instead of matching message text
+
+use std::fmt;
+
+use tokio_postgres::error::SqlState;
+
+/// Failure modes for publication/subscription management
+///
+/// Unlike the `anyhow`-string errors used elsewhere in this crate, callers that need to
+/// branch on *why* a replication operation failed (e.g. treating "already exists" as
+/// idempotent success) can match on these variants instead of grepping the error text,
+/// which varies across PostgreSQL versions and server locales.
+#[derive(Debug)]
+pub enum ReplicationError {
+    /// The publication or subscription already exists (`42710` / `duplicate_object`)
+    AlreadyExists,
+    /// The connected role lacks the privilege to perform the operation
+    /// (`42501` / `insufficient_privilege`)
+    PermissionDenied,
+    /// The named publication does not exist on the source database
+    /// (`42704` / `undefined_object`)
+    PublicationNotFound { publication: String },
+    /// `--include-tables`/`--exclude-tables` filtered out every table in the database,
+    /// so there is nothing left to build a publication from
+    NoMatchingTables { database: String },
+    /// The target could not connect to the publisher to create the subscription
+    PublisherUnreachable,
+    /// The source has reached `max_replication_slots` or a similar configured limit
+    /// (`53400` / `configuration_limit_exceeded`)
+    SlotLimitReached,
+    /// `wait_for_sync` ran out of time before every relation reached `'r'`/`'s'`
+    SyncTimeout {
+        subscription: String,
+        timeout_secs: u64,
+        synced: i64,
+        total: i64,
+    },
+    /// Any other `tokio_postgres` error, passed through unchanged
+    Other(tokio_postgres::Error),
+    /// A failure in surrounding logic (e.g. listing tables to build a filtered
+    /// publication) rather than in the `CREATE`/`DROP` statement itself
+    Internal(anyhow::Error),
+}
+
+impl fmt::Display for ReplicationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplicationError::AlreadyExists => {
+                write!(f, "already exists")
+            }
+            ReplicationError::PermissionDenied => write!(
+                f,
+                "Permission denied.\n\
+                 Only superusers (or the publication/database owner) can perform this operation.\n\
+                 Contact your database administrator to:\n\
+                 1. Grant superuser: ALTER ROLE <user> WITH SUPERUSER;\n\
+                 2. Or perform the operation on your behalf"
+            ),
+            ReplicationError::PublicationNotFound { publication } => write!(
+                f,
+                "Publication '{}' does not exist on the source database.\n\
+                 Make sure the publication exists before creating the subscription.",
+                publication
+            ),
+            ReplicationError::NoMatchingTables { database } => write!(
+                f,
+                "No tables match the filter criteria for database '{}'.\n\
+                 Cannot create a publication with an empty table list.\n\
+                 Check your --include-tables or --exclude-tables settings.",
+                database
+            ),
+            ReplicationError::PublisherUnreachable => write!(
+                f,
+                "Could not connect to the publisher.\n\
+                 Please verify:\n\
+                 - The source database is accessible from the target\n\
+                 - The connection string is correct\n\
+                 - Firewall rules allow connections\n\
+                 - The source user has REPLICATION privilege"
+            ),
+            ReplicationError::SlotLimitReached => write!(
+                f,
+                "The source database has reached the maximum number of replication slots.\n\
+                 Check 'max_replication_slots' on the source database."
+            ),
+            ReplicationError::SyncTimeout {
+                subscription,
+                timeout_secs,
+                synced,
+                total,
+            } => write!(
+                f,
+                "Timeout waiting for subscription '{}' to sync after {} seconds.\n\
+                 {}/{} tables are synced; the remaining tables have not reached\n\
+                 'ready' or 'synchronized' state.\n\
+                 \n\
+                 Possible causes:\n\
+                 - Large database taking longer than expected to copy\n\
+                 - Network issues slowing down data transfer\n\
+                 - Source database under heavy load\n\
+                 \n\
+                 Suggestions:\n\
+                 - Increase the timeout value and try again\n\
+                 - Check replication status with 'status' command\n\
+                 - Monitor source database load and network connectivity",
+                subscription, timeout_secs, synced, total
+            ),
+            ReplicationError::Other(e) => write!(f, "{}", e),
+            ReplicationError::Internal(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ReplicationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReplicationError::Other(e) => Some(e),
+            ReplicationError::Internal(e) => e.source(),
+            _ => None,
+        }
+    }
+}
+
+impl From<anyhow::Error> for ReplicationError {
+    fn from(err: anyhow::Error) -> Self {
+        ReplicationError::Internal(err)
+    }
+}
+
+impl From<tokio_postgres::Error> for ReplicationError {
+    fn from(err: tokio_postgres::Error) -> Self {
+        let Some(db_error) = err.as_db_error() else {
+            return ReplicationError::Other(err);
+        };
+
+        match *db_error.code() {
+            SqlState::DUPLICATE_OBJECT => ReplicationError::AlreadyExists,
+            SqlState::INSUFFICIENT_PRIVILEGE => ReplicationError::PermissionDenied,
+            SqlState::UNDEFINED_OBJECT => {
+                // `db_error.message()` names the missing object, e.g. `publication "foo" does not exist`
+                let publication = db_error
+                    .message()
+                    .split('"')
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+                ReplicationError::PublicationNotFound { publication }
+            }
+            SqlState::CONFIGURATION_LIMIT_EXCEEDED => ReplicationError::SlotLimitReached,
+            _ if db_error.message().contains("could not connect to the publisher") => {
+                ReplicationError::PublisherUnreachable
+            }
+            _ => ReplicationError::Other(err),
+        }
+    }
+}
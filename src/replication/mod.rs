@@ -1,13 +1,23 @@
 // ABOUTME: Replication utilities module
 // ABOUTME: Handles PostgreSQL logical replication setup and monitoring
 
+pub mod error;
 pub mod monitor;
 pub mod publication;
+pub mod quoting;
 pub mod subscription;
 
+pub use error::ReplicationError;
 pub use monitor::{
-    get_replication_lag, get_subscription_status, is_replication_caught_up, SourceReplicationStats,
-    SubscriptionStats,
+    get_replication_lag, get_subscription_status, is_replication_caught_up, parse_lsn,
+    wait_for_catchup, wait_for_lsn, SourceReplicationStats, SubscriptionStats,
+};
+pub use publication::{
+    add_tables_to_publication, create_publication, drop_publication, drop_tables_from_publication,
+    list_publication_tables, list_publications, PublicationTable,
+};
+pub use quoting::{quote_identifier, quote_literal, quote_qualified};
+pub use subscription::{
+    create_subscription, drop_subscription, list_subscriptions, wait_for_sync, StreamingMode,
+    SubscriptionOptions,
 };
-pub use publication::{create_publication, drop_publication, list_publications};
-pub use subscription::{create_subscription, drop_subscription, list_subscriptions, wait_for_sync};
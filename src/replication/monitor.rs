@@ -2,10 +2,12 @@
 // ABOUTME: Queries replication status and lag from source and target databases
 
 use anyhow::{Context, Result};
+use serde::Serialize;
+use std::time::{Duration, Instant};
 use tokio_postgres::Client;
 
 /// Replication statistics from the source database (publisher)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SourceReplicationStats {
     pub application_name: String,
     pub state: String,
@@ -19,7 +21,7 @@ pub struct SourceReplicationStats {
 }
 
 /// Subscription statistics from the target database (subscriber)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SubscriptionStats {
     pub subscription_name: String,
     pub pid: Option<i32>,
@@ -164,11 +166,197 @@ pub async fn is_replication_caught_up(
     Ok(true)
 }
 
+/// Parse a Postgres LSN string (`pg_lsn`'s text form, `"H/L"` - two hex halves)
+/// into a linear byte offset, so callers can do throughput/ETA arithmetic on
+/// LSNs client-side across polls instead of a single `pg_wal_lsn_diff` call
+pub fn parse_lsn(lsn: &str) -> Result<u64> {
+    let (high, low) = lsn
+        .split_once('/')
+        .with_context(|| format!("Invalid LSN '{}': expected \"H/L\" format", lsn))?;
+    let high = u64::from_str_radix(high, 16)
+        .with_context(|| format!("Invalid LSN '{}': bad high half '{}'", lsn, high))?;
+    let low = u64::from_str_radix(low, 16)
+        .with_context(|| format!("Invalid LSN '{}': bad low half '{}'", lsn, low))?;
+    Ok((high << 32) | low)
+}
+
+/// Wait until the subscriber has durably applied every change committed on the
+/// source up to the instant this function is called - a precise cutover signal,
+/// as opposed to [`is_replication_caught_up`]'s `replay_lag_ms < 1000` heuristic,
+/// which only tells you lag is *currently* small and gives no guarantee about any
+/// particular commit.
+///
+/// Captures `pg_current_wal_lsn()` on the source as the cutover target, then
+/// delegates to [`wait_for_lsn`] to poll for it. Use this immediately before
+/// flipping traffic, once writes to the source have stopped.
+pub async fn wait_for_catchup(
+    source_client: &Client,
+    target_client: &Client,
+    subscription_name: &str,
+    timeout_secs: u64,
+) -> Result<()> {
+    let target_lsn: String = source_client
+        .query_one("SELECT pg_current_wal_lsn()::text", &[])
+        .await
+        .context("Failed to capture cutover target LSN on source")?
+        .get(0);
+
+    tracing::info!(
+        "Waiting for subscription '{}' to catch up to cutover LSN {}...",
+        subscription_name,
+        target_lsn
+    );
+
+    wait_for_lsn(
+        source_client,
+        target_client,
+        subscription_name,
+        &target_lsn,
+        timeout_secs,
+    )
+    .await
+}
+
+/// Wait until the subscriber has durably applied every change up to `target_lsn`
+///
+/// Implements the standard LSN-target protocol: poll `pg_stat_replication` for the
+/// subscriber's `flush_lsn` (matched by `application_name`, which a subscription's
+/// apply worker sets to the subscription name) until it has flushed at or past
+/// `target_lsn`, per `pg_wal_lsn_diff(flush_lsn, target_lsn) >= 0`. [`wait_for_catchup`]
+/// is a thin wrapper around this for the common case of waiting for "right now";
+/// call this directly when the target LSN was captured somewhere other than this
+/// call (e.g. alongside an exported snapshot).
+///
+/// # Errors
+///
+/// Returns an error if the subscription doesn't exist or isn't enabled on the
+/// target, or if it doesn't catch up to `target_lsn` within `timeout_secs`.
+pub async fn wait_for_lsn(
+    source_client: &Client,
+    target_client: &Client,
+    subscription_name: &str,
+    target_lsn: &str,
+    timeout_secs: u64,
+) -> Result<()> {
+    let subscription_enabled: Option<bool> = target_client
+        .query_opt(
+            "SELECT subenabled FROM pg_subscription WHERE subname = $1",
+            &[&subscription_name],
+        )
+        .await
+        .context(format!(
+            "Failed to look up subscription '{}' on target",
+            subscription_name
+        ))?
+        .map(|row| row.get(0));
+
+    if subscription_enabled != Some(true) {
+        anyhow::bail!(
+            "Subscription '{}' does not exist or is disabled on the target; \
+             cannot wait for catchup",
+            subscription_name
+        );
+    }
+
+    let target_lsn = target_lsn.to_string();
+    let start = Instant::now();
+    let timeout = Duration::from_secs(timeout_secs);
+    let mut last_remaining_bytes: Option<i64> = None;
+
+    loop {
+        let row = source_client
+            .query_opt(
+                "SELECT flush_lsn::text, pg_wal_lsn_diff($2::pg_lsn, flush_lsn) \
+                 FROM pg_stat_replication WHERE application_name = $1",
+                &[&subscription_name, &target_lsn],
+            )
+            .await
+            .context(format!(
+                "Failed to query replication status for subscription '{}'",
+                subscription_name
+            ))?;
+
+        match row {
+            Some(row) => {
+                let flush_lsn: String = row.get(0);
+                let remaining_bytes: i64 = row.get(1);
+                last_remaining_bytes = Some(remaining_bytes);
+
+                if remaining_bytes <= 0 {
+                    tracing::info!(
+                        "✓ Subscription '{}' caught up to target LSN {} (flush_lsn {})",
+                        subscription_name,
+                        target_lsn,
+                        flush_lsn
+                    );
+                    return Ok(());
+                }
+
+                tracing::info!(
+                    "Subscription '{}': {} bytes behind target LSN {} (flush_lsn {})...",
+                    subscription_name,
+                    remaining_bytes,
+                    target_lsn,
+                    flush_lsn
+                );
+            }
+            None => {
+                tracing::warn!(
+                    "Subscription '{}' not yet visible in pg_stat_replication...",
+                    subscription_name
+                );
+            }
+        }
+
+        if start.elapsed() > timeout {
+            anyhow::bail!(
+                "Timeout waiting for subscription '{}' to catch up to target LSN {} \
+                 after {} seconds.\n\
+                 {}\n\
+                 \n\
+                 Possible causes:\n\
+                 - Large volume of writes occurred before the target position\n\
+                 - Network issues slowing down data transfer\n\
+                 - Target database under heavy load applying changes\n\
+                 \n\
+                 Suggestions:\n\
+                 - Increase the timeout value and try again\n\
+                 - Check replication status with 'status' command\n\
+                 - Ensure writes to the source have actually stopped, if waiting for a cutover point",
+                subscription_name,
+                target_lsn,
+                timeout_secs,
+                match last_remaining_bytes {
+                    Some(bytes) => format!("Still {} bytes behind target.", bytes),
+                    None => "Subscription never appeared in pg_stat_replication.".to_string(),
+                }
+            );
+        }
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::postgres::connect;
 
+    #[test]
+    fn test_parse_lsn() {
+        assert_eq!(parse_lsn("0/0").unwrap(), 0);
+        assert_eq!(parse_lsn("0/16B3748").unwrap(), 0x16B3748);
+        assert_eq!(parse_lsn("1/0").unwrap(), 1u64 << 32);
+        assert_eq!(parse_lsn("16/B374800").unwrap(), (0x16u64 << 32) | 0xB374800);
+    }
+
+    #[test]
+    fn test_parse_lsn_rejects_malformed_input() {
+        assert!(parse_lsn("not-an-lsn").is_err());
+        assert!(parse_lsn("ZZ/0").is_err());
+        assert!(parse_lsn("0/ZZ").is_err());
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_get_replication_lag() {
@@ -270,4 +458,45 @@ mod tests {
         }
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_wait_for_catchup() {
+        // This test requires an active subscription between source and target
+        let source_url = std::env::var("TEST_SOURCE_URL").unwrap();
+        let target_url = std::env::var("TEST_TARGET_URL").unwrap();
+
+        let source_client = connect(&source_url).await.unwrap();
+        let target_client = connect(&target_url).await.unwrap();
+
+        let result = wait_for_catchup(&source_client, &target_client, "seren_migration_sub", 30).await;
+        println!("Catchup result: {:?}", result);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_wait_for_lsn() {
+        // This test requires an active subscription between source and target
+        let source_url = std::env::var("TEST_SOURCE_URL").unwrap();
+        let target_url = std::env::var("TEST_TARGET_URL").unwrap();
+
+        let source_client = connect(&source_url).await.unwrap();
+        let target_client = connect(&target_url).await.unwrap();
+
+        let target_lsn: String = source_client
+            .query_one("SELECT pg_current_wal_lsn()::text", &[])
+            .await
+            .unwrap()
+            .get(0);
+
+        let result = wait_for_lsn(
+            &source_client,
+            &target_client,
+            "seren_migration_sub",
+            &target_lsn,
+            30,
+        )
+        .await;
+        println!("Catchup result: {:?}", result);
+    }
 }
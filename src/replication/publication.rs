@@ -6,27 +6,53 @@ use tokio_postgres::Client;
 
 use crate::filters::ReplicationFilter;
 
+use super::quoting::{quote_identifier, quote_qualified};
+use super::ReplicationError;
+
+/// Minimum `server_version_num` that supports row filters (`WHERE`) and column lists
+/// on `CREATE PUBLICATION ... FOR TABLE`, introduced in PostgreSQL 15
+const MIN_SERVER_VERSION_NUM_FOR_ROW_FILTERS: i32 = 150000;
+
 /// Create a publication for tables with optional filtering
 ///
 /// When table filters are specified, creates a publication for only the filtered tables.
 /// Without filters, creates a publication for all tables.
 ///
+/// If `filter` carries a [`ReplicationFilter::row_filter`] predicate for a table, it is
+/// appended as `WHERE (<predicate>)` after that table's qualified name, so only rows
+/// matching the predicate are replicated. If it carries a [`ReplicationFilter::column_list`],
+/// that table's entry instead becomes `"schema"."table" (col1, col2, ...)` so only those
+/// columns are replicated - the list is validated to include every replica-identity
+/// column first, since the server rejects a column list missing one whenever an
+/// `UPDATE`/`DELETE` needs to identify the row. Both features require PostgreSQL 15+ and
+/// are checked via `SHOW server_version_num` before the `CREATE PUBLICATION` is attempted.
+/// Note that a row filter's predicate columns must also be part of the table's replica
+/// identity for `UPDATE`/`DELETE` on matching rows to replicate correctly; this function
+/// does not verify that for row filters, only for column lists.
+///
 /// # Arguments
 ///
 /// * `client` - Connected client to the database
 /// * `db_name` - Name of the database (for filtering context)
 /// * `publication_name` - Name of the publication to create
-/// * `filter` - Replication filter for table inclusion/exclusion
+/// * `filter` - Replication filter for table inclusion/exclusion, with optional
+///   per-table row filters and column lists
 ///
 /// # Returns
 ///
 /// Returns `Ok(())` if publication is created or already exists
+///
+/// # Errors
+///
+/// Returns an error if `filter` carries a row filter or column list but the server is
+/// older than PostgreSQL 15, if one is attached to a table that isn't actually included
+/// in the publication, or if a column list omits a replica-identity column.
 pub async fn create_publication(
     client: &Client,
     db_name: &str,
     publication_name: &str,
     filter: &ReplicationFilter,
-) -> Result<()> {
+) -> Result<(), ReplicationError> {
     tracing::info!("Creating publication '{}'...", publication_name);
 
     // Check if table filtering is active
@@ -54,13 +80,9 @@ pub async fn create_publication(
             .collect();
 
         if filtered_tables.is_empty() {
-            anyhow::bail!(
-                "No tables match the filter criteria for database '{}'.\n\
-                 Cannot create publication '{}' with empty table list.\n\
-                 Check your --include-tables or --exclude-tables settings.",
-                db_name,
-                publication_name
-            );
+            return Err(ReplicationError::NoMatchingTables {
+                database: db_name.to_string(),
+            });
         }
 
         tracing::info!(
@@ -68,20 +90,64 @@ pub async fn create_publication(
             filtered_tables.len()
         );
 
-        // Build FOR TABLE clause with schema-qualified table names
-        let table_list: Vec<String> = filtered_tables
-            .iter()
-            .map(|t| format!("\"{}\".\"{}\"", t.schema, t.name))
-            .collect();
+        if filter.has_row_filters() || filter.has_column_lists() {
+            check_pg15_filter_requirements(client, db_name, filter, &filtered_tables).await?;
+        }
+
+        // Build FOR TABLE clause with schema-qualified table names, plus an optional
+        // column list and row filter WHERE clause per table
+        let mut table_list = Vec::with_capacity(filtered_tables.len());
+        for t in &filtered_tables {
+            let qualified_name = t.qualified_name();
+            let mut entry = quote_qualified(&t.schema, &t.name);
+
+            if let Some(columns) = filter.column_list(db_name, &qualified_name) {
+                let replica_identity_columns =
+                    replica_identity_columns(client, &t.schema, &t.name).await?;
+                for required in &replica_identity_columns {
+                    if !columns.iter().any(|c| c == required) {
+                        return Err(ReplicationError::Internal(anyhow::anyhow!(
+                            "Column list for '{}' omits '{}', which is part of the table's \
+                             replica identity; the server rejects publication column lists \
+                             missing a replica identity column because UPDATE/DELETE couldn't \
+                             be replicated",
+                            qualified_name,
+                            required
+                        )));
+                    }
+                }
+                let column_list = columns
+                    .iter()
+                    .map(|c| quote_identifier(c))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                entry.push_str(&format!(" ({})", column_list));
+            }
+
+            if let Some(predicate) = filter.row_filter(db_name, &qualified_name) {
+                entry.push_str(&format!(" WHERE ({})", predicate));
+            }
+
+            table_list.push(entry);
+        }
 
         format!(
-            "CREATE PUBLICATION \"{}\" FOR TABLE {}",
-            publication_name,
+            "CREATE PUBLICATION {} FOR TABLE {}",
+            quote_identifier(publication_name),
             table_list.join(", ")
         )
     } else {
+        if filter.has_row_filters() || filter.has_column_lists() {
+            return Err(ReplicationError::Internal(anyhow::anyhow!(
+                "Row filters/column lists were specified but no tables are selected for publication '{}'",
+                publication_name
+            )));
+        }
         // No filtering - use FOR ALL TABLES (fast path)
-        format!("CREATE PUBLICATION \"{}\" FOR ALL TABLES", publication_name)
+        format!(
+            "CREATE PUBLICATION {} FOR ALL TABLES",
+            quote_identifier(publication_name)
+        )
     };
 
     match client.execute(&query, &[]).await {
@@ -89,45 +155,89 @@ pub async fn create_publication(
             tracing::info!("✓ Publication '{}' created successfully", publication_name);
             Ok(())
         }
-        Err(e) => {
-            let err_str = e.to_string();
+        Err(e) => match ReplicationError::from(e) {
             // Publication might already exist - that's okay
-            if err_str.contains("already exists") {
+            ReplicationError::AlreadyExists => {
                 tracing::info!("✓ Publication '{}' already exists", publication_name);
                 Ok(())
-            } else if err_str.contains("permission denied") || err_str.contains("must be owner") {
-                anyhow::bail!(
-                    "Permission denied: Cannot create publication '{}'.\n\
-                     You need superuser or owner privileges on the database.\n\
-                     Grant with: GRANT CREATE ON DATABASE <dbname> TO <user>;\n\
-                     Error: {}",
-                    publication_name,
-                    err_str
-                )
-            } else if err_str.contains("wal_level") || err_str.contains("logical replication") {
-                anyhow::bail!(
-                    "Logical replication not enabled: Cannot create publication '{}'.\n\
-                     The database parameter 'wal_level' must be set to 'logical'.\n\
-                     Contact your database administrator to update postgresql.conf:\n\
-                     wal_level = logical\n\
-                     Error: {}",
-                    publication_name,
-                    err_str
-                )
-            } else {
-                anyhow::bail!(
-                    "Failed to create publication '{}': {}\n\
-                     \n\
-                     Common causes:\n\
-                     - Insufficient privileges (need CREATE privilege on database)\n\
-                     - Logical replication not enabled (wal_level must be 'logical')\n\
-                     - Database does not support publications",
-                    publication_name,
-                    err_str
-                )
             }
+            other => Err(other),
+        },
+    }
+}
+
+/// Check that row filters and column lists can actually be applied: the server is
+/// PostgreSQL 15+, and every table carrying a row filter or column list is one of the
+/// tables actually being published
+async fn check_pg15_filter_requirements(
+    client: &Client,
+    db_name: &str,
+    filter: &ReplicationFilter,
+    filtered_tables: &[crate::migration::TableInfo],
+) -> Result<(), ReplicationError> {
+    let version_num = server_version_num(client).await?;
+    if version_num < MIN_SERVER_VERSION_NUM_FOR_ROW_FILTERS {
+        return Err(ReplicationError::Internal(anyhow::anyhow!(
+            "Row filters and column lists on CREATE PUBLICATION require PostgreSQL 15 or \
+             newer; this server reports server_version_num = {}",
+            version_num
+        )));
+    }
+
+    for table_with_filter in filter.row_filter_tables().chain(filter.column_list_tables()) {
+        let matches_published = filtered_tables
+            .iter()
+            .any(|t| t.qualified_name() == table_with_filter);
+        if !matches_published {
+            return Err(ReplicationError::Internal(anyhow::anyhow!(
+                "Row filter or column list attached to table '{}' in database '{}', but that \
+                 table is not included in the publication (check --include-tables/--exclude-tables)",
+                table_with_filter,
+                db_name
+            )));
         }
     }
+
+    Ok(())
+}
+
+/// `SHOW server_version_num` parsed as an integer (e.g. `160003` for 16.3)
+async fn server_version_num(client: &Client) -> Result<i32, ReplicationError> {
+    let row = client
+        .query_one("SHOW server_version_num", &[])
+        .await
+        .map_err(anyhow::Error::from)?;
+    let raw: String = row.get(0);
+    raw.parse()
+        .map_err(|_| anyhow::anyhow!("Unexpected non-numeric server_version_num: '{}'", raw))
+        .map_err(ReplicationError::from)
+}
+
+/// Columns that make up a table's replica identity: the primary key's columns, or
+/// (if set) the columns of the unique index used as `REPLICA IDENTITY USING INDEX`.
+/// Returns an empty `Vec` if the table has no usable replica identity - callers that
+/// care should pair this with [`crate::migration::check_replication_eligibility`].
+async fn replica_identity_columns(
+    client: &Client,
+    schema: &str,
+    table: &str,
+) -> Result<Vec<String>, ReplicationError> {
+    let rows = client
+        .query(
+            "SELECT a.attname
+             FROM pg_index i
+             JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = ANY(i.indkey)
+             WHERE i.indrelid = format('%I.%I', $1::text, $2::text)::regclass
+               AND (i.indisprimary OR i.indisreplident)
+             ORDER BY array_position(i.indkey, a.attnum)",
+            &[&schema, &table],
+        )
+        .await
+        .map_err(anyhow::Error::from)
+        .with_context(|| format!("Failed to look up replica identity columns for {}.{}", schema, table))
+        .map_err(ReplicationError::from)?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
 }
 
 /// List all publications in the database
@@ -146,7 +256,10 @@ pub async fn list_publications(client: &Client) -> Result<Vec<String>> {
 pub async fn drop_publication(client: &Client, publication_name: &str) -> Result<()> {
     tracing::info!("Dropping publication '{}'...", publication_name);
 
-    let query = format!("DROP PUBLICATION IF EXISTS \"{}\"", publication_name);
+    let query = format!(
+        "DROP PUBLICATION IF EXISTS {}",
+        quote_identifier(publication_name)
+    );
 
     client
         .execute(&query, &[])
@@ -157,6 +270,146 @@ pub async fn drop_publication(client: &Client, publication_name: &str) -> Result
     Ok(())
 }
 
+/// A single table entry reported by [`list_publication_tables`]
+#[derive(Debug, Clone)]
+pub struct PublicationTable {
+    pub schema: String,
+    pub table: String,
+    /// The table's row filter, if one is set (PostgreSQL 15+ `pg_publication_tables.rowfilter`)
+    pub row_filter: Option<String>,
+    /// The table's column list, if one is set (PostgreSQL 15+ `pg_publication_tables.attnames`)
+    pub columns: Option<Vec<String>>,
+}
+
+/// List the tables currently in a publication, along with any row filter/column list
+///
+/// Queries `pg_publication_tables`, which already resolves partitioned-table
+/// membership and per-table row filters/column lists, rather than re-deriving
+/// membership from `pg_publication_rel`.
+///
+/// # Errors
+///
+/// Returns an error if the catalog query fails.
+pub async fn list_publication_tables(
+    client: &Client,
+    publication_name: &str,
+) -> Result<Vec<PublicationTable>> {
+    let rows = client
+        .query(
+            "SELECT schemaname, tablename, rowfilter, attnames
+             FROM pg_publication_tables
+             WHERE pubname = $1
+             ORDER BY schemaname, tablename",
+            &[&publication_name],
+        )
+        .await
+        .with_context(|| format!("Failed to list tables in publication '{}'", publication_name))?;
+
+    Ok(rows
+        .iter()
+        .map(|row| PublicationTable {
+            schema: row.get(0),
+            table: row.get(1),
+            row_filter: row.get(2),
+            columns: row.get(3),
+        })
+        .collect())
+}
+
+/// Add tables to an existing publication via `ALTER PUBLICATION ... ADD TABLE ...`
+///
+/// Lets a long-running migration widen a publication's table set without recreating
+/// it, which would drop and recreate the associated replication slot and force a
+/// fresh initial sync. Tolerates "already a member" the same way [`create_publication`]
+/// tolerates "already exists", so this is safe to re-run.
+///
+/// # Errors
+///
+/// Returns an error if `table_names` is empty or the `ALTER PUBLICATION` fails for any
+/// reason other than the table already being a member.
+pub async fn add_tables_to_publication(
+    client: &Client,
+    publication_name: &str,
+    table_names: &[(String, String)],
+) -> Result<(), ReplicationError> {
+    alter_publication_tables(client, publication_name, table_names, "ADD").await
+}
+
+/// Remove tables from an existing publication via `ALTER PUBLICATION ... DROP TABLE ...`
+///
+/// Tolerates "not a member" the same way [`create_publication`] tolerates "already
+/// exists", so this is safe to re-run.
+///
+/// # Errors
+///
+/// Returns an error if `table_names` is empty or the `ALTER PUBLICATION` fails for any
+/// reason other than the table not being a member.
+pub async fn drop_tables_from_publication(
+    client: &Client,
+    publication_name: &str,
+    table_names: &[(String, String)],
+) -> Result<(), ReplicationError> {
+    alter_publication_tables(client, publication_name, table_names, "DROP").await
+}
+
+async fn alter_publication_tables(
+    client: &Client,
+    publication_name: &str,
+    table_names: &[(String, String)],
+    verb: &str,
+) -> Result<(), ReplicationError> {
+    if table_names.is_empty() {
+        return Err(ReplicationError::Internal(anyhow::anyhow!(
+            "No tables given to {} {} publication '{}'",
+            verb,
+            if verb == "ADD" { "to" } else { "from" },
+            publication_name
+        )));
+    }
+
+    tracing::info!(
+        "{} {} table(s) {} publication '{}'...",
+        if verb == "ADD" { "Adding" } else { "Dropping" },
+        table_names.len(),
+        if verb == "ADD" { "to" } else { "from" },
+        publication_name
+    );
+
+    let table_list: Vec<String> = table_names
+        .iter()
+        .map(|(schema, table)| quote_qualified(schema, table))
+        .collect();
+
+    let query = format!(
+        "ALTER PUBLICATION {} {} TABLE {}",
+        quote_identifier(publication_name),
+        verb,
+        table_list.join(", ")
+    );
+
+    match client.execute(&query, &[]).await {
+        Ok(_) => {
+            tracing::info!("✓ Publication '{}' updated", publication_name);
+            Ok(())
+        }
+        Err(e) => {
+            let message = e.as_db_error().map(|dbe| dbe.message().to_string());
+            let idempotent = message.as_deref().is_some_and(|m| {
+                m.contains("is already a member of publication")
+                    || m.contains("is not part of publication")
+            });
+            if idempotent {
+                tracing::info!(
+                    "✓ Publication '{}' already reflects the requested table membership",
+                    publication_name
+                );
+                return Ok(());
+            }
+            Err(ReplicationError::from(e))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
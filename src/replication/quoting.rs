@@ -0,0 +1,80 @@
+// ABOUTME: SQL identifier/literal quoting helpers for publication and subscription DDL
+// ABOUTME: Replaces ad-hoc format!("\"{}\"", ...) interpolation with proper escaping
+
+/// Quote `name` as a PostgreSQL identifier, doubling any embedded `"`.
+///
+/// Use this for every table/schema/publication/subscription name interpolated into DDL;
+/// `format!("\"{}\"", name)` alone is wrong (and injectable) for a name containing a
+/// `"`, e.g. a table named `foo"; DROP TABLE bar; --`.
+///
+/// # Examples
+///
+/// ```
+/// # use postgres_seren_replicator::replication::quote_identifier;
+/// assert_eq!(quote_identifier("orders"), "\"orders\"");
+/// assert_eq!(quote_identifier("weird\"name"), "\"weird\"\"name\"");
+/// ```
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Quote `value` as a PostgreSQL string literal, doubling any embedded `'` and
+/// switching to an `E'...'` escape string (with `\` doubled) if `value` contains a
+/// backslash, so the result is safe regardless of `standard_conforming_strings`.
+///
+/// # Examples
+///
+/// ```
+/// # use postgres_seren_replicator::replication::quote_literal;
+/// assert_eq!(quote_literal("EU"), "'EU'");
+/// assert_eq!(quote_literal("O'Brien"), "'O''Brien'");
+/// assert_eq!(quote_literal("a\\b"), "E'a\\\\b'");
+/// ```
+pub fn quote_literal(value: &str) -> String {
+    let escaped = value.replace('\'', "''");
+    if value.contains('\\') {
+        format!("E'{}'", escaped.replace('\\', "\\\\"))
+    } else {
+        format!("'{}'", escaped)
+    }
+}
+
+/// Quote a schema-qualified name as `"schema"."name"`
+pub fn quote_qualified(schema: &str, name: &str) -> String {
+    format!("{}.{}", quote_identifier(schema), quote_identifier(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_identifier_plain() {
+        assert_eq!(quote_identifier("users"), "\"users\"");
+    }
+
+    #[test]
+    fn test_quote_identifier_embedded_quote() {
+        assert_eq!(quote_identifier("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn test_quote_literal_plain() {
+        assert_eq!(quote_literal("EU"), "'EU'");
+    }
+
+    #[test]
+    fn test_quote_literal_embedded_quote() {
+        assert_eq!(quote_literal("O'Brien"), "'O''Brien'");
+    }
+
+    #[test]
+    fn test_quote_literal_backslash() {
+        assert_eq!(quote_literal("a\\b"), "E'a\\\\b'");
+    }
+
+    #[test]
+    fn test_quote_qualified() {
+        assert_eq!(quote_qualified("public", "orders"), "\"public\".\"orders\"");
+    }
+}
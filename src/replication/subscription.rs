@@ -5,18 +5,136 @@ use anyhow::{Context, Result};
 use std::time::Duration;
 use tokio_postgres::Client;
 
+use super::ReplicationError;
+
+/// In-progress-transaction streaming mode for a subscription's `streaming` option
+///
+/// Corresponds directly to PostgreSQL's `CREATE SUBSCRIPTION ... WITH (streaming = ...)`
+/// values: streaming large transactions to the target as they happen (rather than
+/// buffering them until commit) cuts replication lag for bulk loads, and `Parallel`
+/// additionally applies them using multiple worker processes on the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamingMode {
+    Off,
+    On,
+    Parallel,
+}
+
+impl StreamingMode {
+    fn as_sql(self) -> &'static str {
+        match self {
+            StreamingMode::Off => "off",
+            StreamingMode::On => "on",
+            StreamingMode::Parallel => "parallel",
+        }
+    }
+}
+
+impl Default for StreamingMode {
+    fn default() -> Self {
+        StreamingMode::Off
+    }
+}
+
+/// `WITH (...)` options for [`create_subscription`]
+///
+/// Defaults mirror PostgreSQL's own `CREATE SUBSCRIPTION` defaults, so
+/// `SubscriptionOptions::default()` produces the same bare
+/// `CREATE SUBSCRIPTION ... CONNECTION ... PUBLICATION ...` statement this crate used
+/// before these options existed.
+#[derive(Debug, Clone)]
+pub struct SubscriptionOptions {
+    /// Copy the publication's existing table data during initial sync. Set to `false`
+    /// to attach to a target that was already seeded out-of-band, pairing with a
+    /// pre-created `slot_name`.
+    pub copy_data: bool,
+    /// Create a new replication slot on the source for this subscription
+    pub create_slot: bool,
+    /// Name of a pre-existing replication slot to attach to instead of creating one;
+    /// only meaningful when `create_slot` is `false`
+    pub slot_name: Option<String>,
+    /// In-progress transaction streaming mode; `Parallel` plus `binary` gives the best
+    /// throughput for large initial loads
+    pub streaming: StreamingMode,
+    /// Send replicated data in PostgreSQL's binary format instead of text
+    pub binary: bool,
+    /// Support replication of prepared (two-phase commit) transactions
+    pub two_phase: bool,
+    /// Origin to replicate changes from. Set to `Some("none".to_string())` to only
+    /// replicate changes that originate locally on the source, which avoids
+    /// replication loops in bidirectional/failback topologies.
+    pub origin: Option<String>,
+    /// Whether the subscription starts replicating immediately after creation
+    pub enabled: bool,
+}
+
+impl SubscriptionOptions {
+    fn with_clause(&self) -> String {
+        let mut opts = Vec::new();
+        if !self.copy_data {
+            opts.push("copy_data = false".to_string());
+        }
+        if !self.create_slot {
+            opts.push("create_slot = false".to_string());
+        }
+        if let Some(slot_name) = &self.slot_name {
+            opts.push(format!("slot_name = '{}'", slot_name));
+        }
+        if self.streaming != StreamingMode::Off {
+            opts.push(format!("streaming = {}", self.streaming.as_sql()));
+        }
+        if self.binary {
+            opts.push("binary = true".to_string());
+        }
+        if self.two_phase {
+            opts.push("two_phase = true".to_string());
+        }
+        if let Some(origin) = &self.origin {
+            opts.push(format!("origin = {}", origin));
+        }
+        if !self.enabled {
+            opts.push("enabled = false".to_string());
+        }
+
+        if opts.is_empty() {
+            String::new()
+        } else {
+            format!(" WITH ({})", opts.join(", "))
+        }
+    }
+}
+
+impl Default for SubscriptionOptions {
+    fn default() -> Self {
+        Self {
+            copy_data: true,
+            create_slot: true,
+            slot_name: None,
+            streaming: StreamingMode::default(),
+            binary: false,
+            two_phase: false,
+            origin: None,
+            enabled: true,
+        }
+    }
+}
+
 /// Create a subscription to a publication on the source database
 pub async fn create_subscription(
     client: &Client,
     subscription_name: &str,
     source_connection_string: &str,
     publication_name: &str,
-) -> Result<()> {
+    options: &SubscriptionOptions,
+) -> Result<(), ReplicationError> {
     tracing::info!("Creating subscription '{}'...", subscription_name);
 
     let query = format!(
-        "CREATE SUBSCRIPTION \"{}\" CONNECTION '{}' PUBLICATION \"{}\"",
-        subscription_name, source_connection_string, publication_name
+        "CREATE SUBSCRIPTION \"{}\" CONNECTION '{}' PUBLICATION \"{}\"{}",
+        subscription_name,
+        source_connection_string,
+        publication_name,
+        options.with_clause()
     );
 
     match client.execute(&query, &[]).await {
@@ -27,71 +145,19 @@ pub async fn create_subscription(
             );
             Ok(())
         }
-        Err(e) => {
-            let err_str = e.to_string();
+        Err(e) => match ReplicationError::from(e) {
             // Subscription might already exist - that's okay
-            if err_str.contains("already exists") {
+            ReplicationError::AlreadyExists => {
                 tracing::info!("✓ Subscription '{}' already exists", subscription_name);
                 Ok(())
-            } else if err_str.contains("permission denied") || err_str.contains("must be superuser")
-            {
-                anyhow::bail!(
-                    "Permission denied: Cannot create subscription '{}'.\n\
-                     Only superusers can create subscriptions in PostgreSQL.\n\
-                     Contact your database administrator to:\n\
-                     1. Grant superuser: ALTER ROLE <user> WITH SUPERUSER;\n\
-                     2. Or create the subscription on your behalf\n\
-                     Error: {}",
-                    subscription_name,
-                    err_str
-                )
-            } else if err_str.contains("publication") && err_str.contains("does not exist") {
-                anyhow::bail!(
-                    "Publication does not exist: Cannot create subscription '{}'.\n\
-                     The publication '{}' was not found on the source database.\n\
-                     Make sure the publication exists before creating the subscription.\n\
-                     Error: {}",
-                    subscription_name,
-                    publication_name,
-                    err_str
-                )
-            } else if err_str.contains("could not connect to the publisher")
-                || err_str.contains("connection")
-            {
-                anyhow::bail!(
-                    "Connection failed: Cannot connect to source database for subscription '{}'.\n\
-                     Please verify:\n\
-                     - The source database is accessible from the target\n\
-                     - The connection string is correct\n\
-                     - Firewall rules allow connections\n\
-                     - The source user has REPLICATION privilege\n\
-                     Error: {}",
-                    subscription_name,
-                    err_str
-                )
-            } else if err_str.contains("replication slot") {
-                anyhow::bail!(
-                    "Replication slot error: Cannot create subscription '{}'.\n\
-                     The source database may have reached the maximum number of replication slots.\n\
-                     Check 'max_replication_slots' on the source database.\n\
-                     Error: {}",
-                    subscription_name,
-                    err_str
-                )
-            } else {
-                anyhow::bail!(
-                    "Failed to create subscription '{}': {}\n\
-                     \n\
-                     Common causes:\n\
-                     - Insufficient privileges (need SUPERUSER on target)\n\
-                     - Publication does not exist on source\n\
-                     - Cannot connect to source database\n\
-                     - max_replication_slots limit reached on source",
-                    subscription_name,
-                    err_str
-                )
             }
-        }
+            ReplicationError::PublicationNotFound { .. } => {
+                Err(ReplicationError::PublicationNotFound {
+                    publication: publication_name.to_string(),
+                })
+            }
+            other => Err(other),
+        },
     }
 }
 
@@ -108,93 +174,101 @@ pub async fn list_subscriptions(client: &Client) -> Result<Vec<String>> {
 }
 
 /// Drop a subscription
-pub async fn drop_subscription(client: &Client, subscription_name: &str) -> Result<()> {
+pub async fn drop_subscription(
+    client: &Client,
+    subscription_name: &str,
+) -> Result<(), ReplicationError> {
     tracing::info!("Dropping subscription '{}'...", subscription_name);
 
     let query = format!("DROP SUBSCRIPTION IF EXISTS \"{}\"", subscription_name);
 
-    client.execute(&query, &[]).await.context(format!(
-        "Failed to drop subscription '{}'",
-        subscription_name
-    ))?;
+    client.execute(&query, &[]).await?;
 
     tracing::info!("✓ Subscription '{}' dropped", subscription_name);
     Ok(())
 }
 
-/// Wait for subscription to complete initial sync and enter streaming state
-/// Returns when subscription reaches 'r' (ready/streaming) state
+/// Wait for subscription to complete initial sync on every replicated table
+///
+/// `srsubstate` is per-relation state that lives in `pg_subscription_rel`, not a
+/// single subscription-wide value - a subscription with many tables is only fully
+/// synced once *every* table's row reaches `'r'` (ready) or `'s'` (synchronized,
+/// i.e. caught up to the sync point and streaming along with the rest). Polling
+/// `pg_stat_subscription` for a single state, as earlier versions of this function
+/// did, is wrong: that view has nothing to do with per-relation sync and only
+/// happens to exist once the apply worker is already running.
 pub async fn wait_for_sync(
     client: &Client,
     subscription_name: &str,
     timeout_secs: u64,
-) -> Result<()> {
+) -> Result<(), ReplicationError> {
     tracing::info!(
         "Waiting for subscription '{}' to sync...",
         subscription_name
     );
 
+    let subscription_oid: u32 = client
+        .query_one(
+            "SELECT oid FROM pg_subscription WHERE subname = $1",
+            &[&subscription_name],
+        )
+        .await?
+        .get(0);
+
     let start = std::time::Instant::now();
     let timeout = Duration::from_secs(timeout_secs);
 
     loop {
-        let row = client
+        let total: i64 = client
             .query_one(
-                "SELECT srsubstate FROM pg_stat_subscription WHERE subname = $1",
-                &[&subscription_name],
+                "SELECT count(1) FROM pg_subscription_rel WHERE srsubid = $1",
+                &[&subscription_oid],
             )
-            .await
-            .context(format!(
-                "Failed to query subscription status for '{}'",
-                subscription_name
-            ))?;
+            .await?
+            .get(0);
 
-        let state: String = row.get(0);
+        let pending: i64 = client
+            .query_one(
+                "SELECT count(1) FROM pg_subscription_rel \
+                 WHERE srsubid = $1 AND srsubstate NOT IN ('r', 's')",
+                &[&subscription_oid],
+            )
+            .await?
+            .get(0);
 
-        match state.as_str() {
-            "r" => {
-                tracing::info!(
-                    "✓ Subscription '{}' is ready and streaming",
-                    subscription_name
-                );
-                return Ok(());
-            }
-            "i" => {
-                tracing::info!("Subscription '{}' is initializing...", subscription_name);
-            }
-            "d" => {
-                tracing::info!("Subscription '{}' is copying data...", subscription_name);
-            }
-            "s" => {
-                tracing::info!("Subscription '{}' is syncing...", subscription_name);
-            }
-            _ => {
-                tracing::warn!(
-                    "Subscription '{}' in unexpected state: {}",
-                    subscription_name,
-                    state
-                );
-            }
+        if total == 0 {
+            tracing::info!(
+                "✓ Subscription '{}' has no replicated tables, nothing to sync",
+                subscription_name
+            );
+            return Ok(());
         }
 
-        if start.elapsed() > timeout {
-            anyhow::bail!(
-                "Timeout waiting for subscription '{}' to sync after {} seconds.\n\
-                 The subscription is in state '{}' and has not reached 'ready' (streaming) state.\n\
-                 \n\
-                 Possible causes:\n\
-                 - Large database taking longer than expected to copy\n\
-                 - Network issues slowing down data transfer\n\
-                 - Source database under heavy load\n\
-                 \n\
-                 Suggestions:\n\
-                 - Increase the timeout value and try again\n\
-                 - Check replication status with 'status' command\n\
-                 - Monitor source database load and network connectivity",
+        let synced = total - pending;
+        if pending == 0 {
+            tracing::info!(
+                "✓ Subscription '{}' is fully synced ({}/{} tables)",
                 subscription_name,
-                timeout_secs,
-                state
+                synced,
+                total
             );
+            return Ok(());
+        }
+
+        tracing::info!(
+            "Subscription '{}': {}/{} tables synced...",
+            subscription_name,
+            synced,
+            total
+        );
+
+        if start.elapsed() > timeout {
+            return Err(ReplicationError::SyncTimeout {
+                subscription: subscription_name.to_string(),
+                timeout_secs,
+                synced,
+                total,
+            });
         }
 
         tokio::time::sleep(Duration::from_secs(2)).await;
@@ -230,7 +304,14 @@ mod tests {
         let _ = drop_subscription(&target_client, sub_name).await;
 
         // Create subscription on target
-        let result = create_subscription(&target_client, sub_name, &source_url, pub_name).await;
+        let result = create_subscription(
+            &target_client,
+            sub_name,
+            &source_url,
+            pub_name,
+            &SubscriptionOptions::default(),
+        )
+        .await;
         match &result {
             Ok(_) => println!("✓ Subscription created successfully"),
             Err(e) => {
@@ -276,9 +357,15 @@ mod tests {
             .unwrap();
 
         // Create subscription on target
-        create_subscription(&target_client, sub_name, &source_url, pub_name)
-            .await
-            .unwrap();
+        create_subscription(
+            &target_client,
+            sub_name,
+            &source_url,
+            pub_name,
+            &SubscriptionOptions::default(),
+        )
+        .await
+        .unwrap();
 
         // Drop it
         let result = drop_subscription(&target_client, sub_name).await;
@@ -317,9 +404,15 @@ mod tests {
         let _ = drop_subscription(&target_client, sub_name).await;
 
         // Create subscription on target
-        create_subscription(&target_client, sub_name, &source_url, pub_name)
-            .await
-            .unwrap();
+        create_subscription(
+            &target_client,
+            sub_name,
+            &source_url,
+            pub_name,
+            &SubscriptionOptions::default(),
+        )
+        .await
+        .unwrap();
 
         // Wait for sync (30 second timeout)
         let result = wait_for_sync(&target_client, sub_name, 30).await;
@@ -0,0 +1,144 @@
+// ABOUTME: Structured per-check result events for CI/pipeline integration
+// ABOUTME: NDJSON records (one per check) plus a final summary, for validate/status/verify
+
+use anyhow::Result;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// Outcome of a single check, with a reason attached for non-`Ok` outcomes
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CheckOutcome {
+    Ok,
+    Skipped { reason: String },
+    Failed { reason: String },
+}
+
+/// A single structured result record, NDJSON-serialized for machine consumption
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckEvent {
+    /// Name of the check performed (e.g. "table_checksum", "replication_lag")
+    pub check: String,
+    /// The database, table, or other target the check ran against, if any
+    pub target: Option<String>,
+    #[serde(flatten)]
+    pub outcome: CheckOutcome,
+    pub duration_ms: u64,
+}
+
+/// Final summary emitted after all check events for one command invocation
+#[derive(Debug, Clone, Serialize)]
+pub struct ResultSummary {
+    pub total: usize,
+    pub ok: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub duration_ms: u64,
+}
+
+/// Collects [`CheckEvent`]s and streams them as NDJSON for CI consumption
+///
+/// Every command that supports `--emit-results` creates one of these up front and
+/// calls [`ResultRecorder::record`] as each check completes, then
+/// [`ResultRecorder::finish`] at the end to print the summary line and learn
+/// whether anything failed. When `emit_results` is false, `record`/`finish` still
+/// track pass/fail counts but print nothing, so call sites don't need to branch.
+pub struct ResultRecorder {
+    emit: bool,
+    started_at: Instant,
+    ok: usize,
+    skipped: usize,
+    failed: usize,
+}
+
+impl ResultRecorder {
+    pub fn new(emit_results: bool) -> Self {
+        Self {
+            emit: emit_results,
+            started_at: Instant::now(),
+            ok: 0,
+            skipped: 0,
+            failed: 0,
+        }
+    }
+
+    /// Record a check's outcome, printing it as an NDJSON line if enabled
+    pub fn record(
+        &mut self,
+        check: impl Into<String>,
+        target: Option<String>,
+        outcome: CheckOutcome,
+        duration: Duration,
+    ) {
+        match &outcome {
+            CheckOutcome::Ok => self.ok += 1,
+            CheckOutcome::Skipped { .. } => self.skipped += 1,
+            CheckOutcome::Failed { .. } => self.failed += 1,
+        }
+
+        if self.emit {
+            let event = CheckEvent {
+                check: check.into(),
+                target,
+                outcome,
+                duration_ms: duration.as_millis() as u64,
+            };
+            match serde_json::to_string(&event) {
+                Ok(line) => println!("{}", line),
+                Err(e) => tracing::warn!("Failed to serialize result event: {}", e),
+            }
+        }
+    }
+
+    /// Print the final summary line (if enabled) and report whether any check failed
+    pub fn finish(self) -> bool {
+        let summary = ResultSummary {
+            total: self.ok + self.skipped + self.failed,
+            ok: self.ok,
+            skipped: self.skipped,
+            failed: self.failed,
+            duration_ms: self.started_at.elapsed().as_millis() as u64,
+        };
+
+        if self.emit {
+            match serde_json::to_string(&summary) {
+                Ok(line) => println!("{}", line),
+                Err(e) => tracing::warn!("Failed to serialize result summary: {}", e),
+            }
+        }
+
+        summary.failed == 0
+    }
+}
+
+/// Record the outcome of a fallible check against `result` without consuming it,
+/// so call sites can keep using `?` on the original `Result`
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::time::Instant;
+/// # use postgres_seren_replicator::results::{track, ResultRecorder};
+/// # async fn example(recorder: &mut ResultRecorder) -> anyhow::Result<()> {
+/// let start = Instant::now();
+/// let result: anyhow::Result<()> = Ok(());
+/// track(recorder, "example_check", None, start, result)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn track<T>(
+    recorder: &mut ResultRecorder,
+    check: impl Into<String>,
+    target: Option<String>,
+    start: Instant,
+    result: Result<T>,
+) -> Result<T> {
+    let outcome = match &result {
+        Ok(_) => CheckOutcome::Ok,
+        Err(e) => CheckOutcome::Failed {
+            reason: e.to_string(),
+        },
+    };
+    recorder.record(check, target, outcome, start.elapsed());
+    result
+}
@@ -0,0 +1,82 @@
+// ABOUTME: Async-signal-safe SIGINT/SIGTERM handling for emergency cleanup
+// ABOUTME: Drains the cleanup registry on a background thread, then re-raises the signal
+
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::Once;
+use std::time::Duration;
+
+static SIGNAL_RECEIVED: AtomicBool = AtomicBool::new(false);
+static LAST_SIGNAL: AtomicI32 = AtomicI32::new(0);
+static INSTALL_ONCE: Once = Once::new();
+
+/// Install SIGINT/SIGTERM handlers that trigger an immediate cleanup of
+/// registered temp directories and `.pgpass` files, instead of leaving them
+/// for a future process startup's
+/// [`cleanup_stale_temp_dirs`](crate::utils::cleanup_stale_temp_dirs) - which
+/// is no help for credentials on disk if the process is never relaunched.
+///
+/// SIGKILL can't be intercepted at all, so this only covers SIGINT (Ctrl-C)
+/// and SIGTERM, which are the signals a normal interrupted run - or an
+/// orchestrator doing a graceful shutdown - actually sends.
+///
+/// Idempotent: safe to call more than once; only the first call installs
+/// anything. Call once near the top of `main`.
+pub fn install_signal_handlers() {
+    INSTALL_ONCE.call_once(|| {
+        unsafe {
+            install_handler(libc::SIGINT);
+            install_handler(libc::SIGTERM);
+        }
+        std::thread::spawn(drain_loop);
+    });
+}
+
+unsafe fn install_handler(signum: libc::c_int) {
+    let mut action: libc::sigaction = std::mem::zeroed();
+    action.sa_sigaction = handle_signal as usize;
+    libc::sigemptyset(&mut action.sa_mask);
+    action.sa_flags = 0;
+    libc::sigaction(signum, &action, std::ptr::null_mut());
+}
+
+/// The actual signal handler. Async-signal-safe by construction: it only
+/// stores to two atomics, with no allocation, locking, or I/O - the registry
+/// drain itself happens on [`drain_loop`], a regular thread that is free to
+/// take locks and touch the filesystem.
+extern "C" fn handle_signal(signum: libc::c_int) {
+    LAST_SIGNAL.store(signum, Ordering::SeqCst);
+    SIGNAL_RECEIVED.store(true, Ordering::SeqCst);
+}
+
+/// Polls the flag set by [`handle_signal`]; once it fires, drains the cleanup
+/// registry and re-raises the same signal with its default disposition so the
+/// process actually terminates the way a caller (shell job control, an
+/// orchestrator) expects.
+fn drain_loop() {
+    loop {
+        if SIGNAL_RECEIVED.swap(false, Ordering::SeqCst) {
+            let signum = LAST_SIGNAL.load(Ordering::SeqCst);
+            tracing::warn!(
+                "Received signal {}, cleaning up temp directories and pgpass files",
+                signum
+            );
+            crate::utils::drain_cleanup_registry();
+            reraise_with_default_disposition(signum);
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Reset `signum`'s disposition to `SIG_DFL` and re-raise it, so the process
+/// dies with the same signal it originally received (e.g. so a parent shell
+/// reports the expected exit status) rather than just calling `process::exit`
+fn reraise_with_default_disposition(signum: libc::c_int) {
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = libc::SIG_DFL;
+        libc::sigemptyset(&mut action.sa_mask);
+        libc::sigaction(signum, &action, std::ptr::null_mut());
+        libc::raise(signum);
+    }
+}
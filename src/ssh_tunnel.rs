@@ -0,0 +1,194 @@
+// ABOUTME: SSH local-port-forward tunnel for reaching Postgres instances behind a bastion
+// ABOUTME: Lets dump_*/restore_* point pg_dump/psql/pg_restore at a forwarded local port
+
+use crate::utils::{PostgresConnectTarget, PostgresUrlParts};
+use anyhow::{bail, Context, Result};
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How to reach the bastion host that fronts a Postgres instance not directly
+/// reachable on the network (e.g. a production cluster only exposed through a
+/// jump box)
+#[derive(Debug, Clone)]
+pub struct SshTunnelConfig {
+    /// `user@host` passed to `ssh` as the destination to forward through
+    pub user_host: String,
+    /// Port `sshd` listens on at `user_host` (default: 22)
+    pub ssh_port: u16,
+    /// Private key passed to `ssh -i`, if the target doesn't accept the
+    /// default identity
+    pub identity_file: Option<std::path::PathBuf>,
+    /// `-J` jump host, for a bastion that's itself only reachable through
+    /// another bastion
+    pub jump_host: Option<String>,
+}
+
+impl SshTunnelConfig {
+    /// Construct a config for tunnelling through `user_host`, with SSH's
+    /// usual defaults (port 22, default identity, no jump host) unless
+    /// overridden
+    pub fn new(
+        user_host: String,
+        ssh_port: Option<u16>,
+        identity_file: Option<std::path::PathBuf>,
+        jump_host: Option<String>,
+    ) -> Self {
+        Self {
+            user_host,
+            ssh_port: ssh_port.unwrap_or(22),
+            identity_file,
+            jump_host,
+        }
+    }
+}
+
+/// A running `ssh -L <local-port>:<db-host>:<db-port> -N` child process
+///
+/// The tunnel is torn down (the `ssh` process killed) when this value is
+/// dropped, so it should be kept alive for exactly as long as the forwarded
+/// connection is in use.
+pub struct SshTunnel {
+    child: Child,
+    local_port: u16,
+}
+
+impl SshTunnel {
+    /// Spawn `ssh -L` to forward a local port to `db_host:db_port` through
+    /// `config`, and block until the local port accepts connections
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a free local port can't be found, `ssh` isn't on
+    /// `PATH`, or the tunnel doesn't come up within a few seconds (most
+    /// commonly a bad bastion host, port, or identity file).
+    pub fn open(config: &SshTunnelConfig, db_host: &str, db_port: u16) -> Result<Self> {
+        let local_port = find_free_port().context("Failed to find a free local port for the SSH tunnel")?;
+        let forward_spec = format!("{}:{}:{}", local_port, db_host, db_port);
+
+        let mut cmd = Command::new("ssh");
+        cmd.arg("-N") // don't execute a remote command, just forward
+            .arg("-L")
+            .arg(&forward_spec)
+            .arg("-o")
+            .arg("ExitOnForwardFailure=yes")
+            .arg("-o")
+            .arg("StrictHostKeyChecking=accept-new")
+            .arg("-p")
+            .arg(config.ssh_port.to_string());
+
+        if let Some(identity_file) = &config.identity_file {
+            cmd.arg("-i").arg(identity_file);
+        }
+        if let Some(jump_host) = &config.jump_host {
+            cmd.arg("-J").arg(jump_host);
+        }
+
+        cmd.arg(&config.user_host)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit());
+
+        let child = cmd.spawn().context(
+            "Failed to execute ssh. Is an OpenSSH client installed?\n\
+             Install with:\n\
+             - Ubuntu/Debian: sudo apt-get install openssh-client\n\
+             - macOS: ssh ships with the OS",
+        )?;
+
+        let mut tunnel = Self { child, local_port };
+        if let Err(e) = tunnel.wait_until_ready(Duration::from_secs(15)) {
+            // wait_until_ready failed, so the tunnel never came up; Drop will
+            // still reap the child, but surface the real error to the caller
+            return Err(e);
+        }
+
+        tracing::info!(
+            "✓ SSH tunnel established: 127.0.0.1:{} -> {} -> {}:{}",
+            local_port,
+            config.user_host,
+            db_host,
+            db_port
+        );
+
+        Ok(tunnel)
+    }
+
+    /// Poll the forwarded local port until it accepts a connection or
+    /// `timeout` elapses
+    fn wait_until_ready(&mut self, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if TcpStream::connect(("127.0.0.1", self.local_port)).is_ok() {
+                return Ok(());
+            }
+            if let Some(status) = self.child.try_wait().context("Failed to poll ssh process")? {
+                bail!("ssh exited before the tunnel came up (status: {})", status);
+            }
+            if Instant::now() >= deadline {
+                bail!(
+                    "Timed out waiting for SSH tunnel's local port {} to accept connections",
+                    self.local_port
+                );
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    /// Local port that forwards to the remote database
+    pub fn local_port(&self) -> u16 {
+        self.local_port
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        if let Err(e) = self.child.kill() {
+            tracing::warn!("Failed to kill SSH tunnel process: {}", e);
+        }
+        let _ = self.child.wait();
+    }
+}
+
+/// Bind an ephemeral TCP listener to have the OS assign a free port, then drop
+/// it so `ssh -L` can bind the same port; there's an inherent (and in
+/// practice negligible) race between the two binds
+fn find_free_port() -> Result<u16> {
+    let listener = TcpListener::bind("127.0.0.1:0").context("Failed to bind to an OS-assigned port")?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// Resolve the `--host`/`--port` a `pg_dump`/`psql`/`pg_restore` invocation
+/// should actually use: `parts`' own host/port, or a freshly-opened tunnel's
+/// forwarded local port when `config` is set
+///
+/// The returned [`SshTunnel`] (when present) must be kept alive for the
+/// duration of the command it was opened for - dropping it tears the tunnel
+/// down. `parts` itself is untouched, so callers should keep using it as-is
+/// for [`crate::utils::PgPassFile::new`], which needs the real host to match
+/// what Postgres authenticates against.
+///
+/// # Errors
+///
+/// Returns an error if `config` is set but `parts.target` isn't a TCP target
+/// (a Unix-domain socket can't be forwarded over SSH), or if opening the
+/// tunnel fails.
+pub fn resolve_connect_target(
+    config: Option<&SshTunnelConfig>,
+    parts: &PostgresUrlParts,
+) -> Result<(String, u16, Option<SshTunnel>)> {
+    let Some(config) = config else {
+        return Ok((parts.host.clone(), parts.port, None));
+    };
+
+    let (db_host, db_port) = match &parts.target {
+        PostgresConnectTarget::Tcp { host, port } => (host.clone(), *port),
+        PostgresConnectTarget::UnixSocket { .. } => {
+            bail!("An SSH tunnel requires a TCP connection URL, not a Unix-domain socket target")
+        }
+    };
+
+    let tunnel = SshTunnel::open(config, &db_host, db_port)?;
+    let local_port = tunnel.local_port();
+    Ok(("127.0.0.1".to_string(), local_port, Some(tunnel)))
+}
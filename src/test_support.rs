@@ -0,0 +1,304 @@
+// ABOUTME: Ephemeral embedded-Postgres harness for end-to-end migration tests
+// ABOUTME: Provisions a throwaway `initdb`/`pg_ctl`-managed instance per test
+
+use crate::utils::{
+    cleanup_stale_temp_dirs, create_managed_temp_dir, remove_managed_temp_dir,
+    PostgresConnectTarget, PostgresUrlParts,
+};
+use anyhow::{bail, Context, Result};
+use std::net::TcpListener;
+use std::process::{Command, Stdio};
+
+/// A throwaway PostgreSQL instance backed by its own `PGDATA` directory and
+/// listening on an OS-assigned port over a Unix-domain socket, for exercising
+/// a real dump → apply round trip in tests instead of unit-testing string
+/// helpers in isolation.
+///
+/// Started with `initdb` + `pg_ctl start` in [`Self::start`] and torn down
+/// (server stopped, `PGDATA` removed) on [`Drop`], so a test instance never
+/// outlives its test even on panic.
+pub struct EphemeralPostgres {
+    data_dir: std::path::PathBuf,
+    port: u16,
+}
+
+impl EphemeralPostgres {
+    /// Provision and start a fresh instance
+    ///
+    /// Also opportunistically reaps `PGDATA` directories left behind by
+    /// crashed test runs via [`cleanup_stale_temp_dirs`], so a SIGKILL'd test
+    /// process doesn't leak data directories indefinitely.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `initdb`/`pg_ctl` aren't on `PATH`, a free port
+    /// can't be found, or the server doesn't report ready within `pg_ctl`'s
+    /// startup timeout.
+    pub fn start() -> Result<Self> {
+        Self::start_with_options(&[])
+    }
+
+    /// Like [`Self::start`], but with `wal_level=logical` and enough
+    /// replication slots/senders for a publication+subscription pair -
+    /// provision both sides of a [`ReplicationPair`] with this instead of
+    /// [`Self::start`]
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::start`].
+    pub fn start_for_replication() -> Result<Self> {
+        Self::start_with_options(&[
+            "wal_level=logical",
+            "max_replication_slots=10",
+            "max_wal_senders=10",
+        ])
+    }
+
+    fn start_with_options(extra_options: &[&str]) -> Result<Self> {
+        if let Err(e) = cleanup_stale_temp_dirs(86400) {
+            tracing::warn!("Failed to clean up stale temp directories: {}", e);
+        }
+
+        let data_dir = create_managed_temp_dir().context("Failed to create PGDATA directory")?;
+        let port = find_free_port().context("Failed to find a free port for ephemeral Postgres")?;
+
+        let instance = Self { data_dir, port };
+        instance.initdb()?;
+        instance.pg_ctl_start(extra_options)?;
+        Ok(instance)
+    }
+
+    fn initdb(&self) -> Result<()> {
+        let status = Command::new("initdb")
+            .arg("--auth=trust")
+            .arg("--no-sync")
+            .arg("-D")
+            .arg(&self.data_dir)
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .status()
+            .context(
+                "Failed to execute initdb. Is the PostgreSQL server package installed?\n\
+                 Install with:\n\
+                 - Ubuntu/Debian: sudo apt-get install postgresql\n\
+                 - macOS: brew install postgresql",
+            )?;
+
+        if !status.success() {
+            bail!("initdb failed for PGDATA at {}", self.data_dir.display());
+        }
+
+        Ok(())
+    }
+
+    fn pg_ctl_start(&self, extra_options: &[&str]) -> Result<()> {
+        let log_path = self.data_dir.join("postgres.log");
+        let mut server_options = format!("-p {} -k {} -h ''", self.port, self.data_dir.display());
+        for option in extra_options {
+            server_options.push_str(" -c ");
+            server_options.push_str(option);
+        }
+        let status = Command::new("pg_ctl")
+            .arg("-D")
+            .arg(&self.data_dir)
+            .arg("-l")
+            .arg(&log_path)
+            .arg("-w") // wait for the server to report ready
+            .arg("-o")
+            .arg(server_options)
+            .arg("start")
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .context("Failed to execute pg_ctl. Is the PostgreSQL server package installed?")?;
+
+        if !status.success() {
+            bail!(
+                "pg_ctl failed to start the ephemeral Postgres instance; see {}",
+                log_path.display()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn pg_ctl_stop(&self) {
+        let status = Command::new("pg_ctl")
+            .arg("-D")
+            .arg(&self.data_dir)
+            .arg("-m")
+            .arg("fast")
+            .arg("stop")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+
+        match status {
+            Ok(status) if !status.success() => {
+                tracing::warn!(
+                    "pg_ctl stop exited non-zero for {}",
+                    self.data_dir.display()
+                );
+            }
+            Err(e) => tracing::warn!("Failed to execute pg_ctl stop: {}", e),
+            Ok(_) => {}
+        }
+    }
+
+    /// Connection parts for the `postgres` database on this instance, reachable
+    /// only via its Unix-domain socket (no TCP listener is started)
+    pub fn url_parts(&self) -> PostgresUrlParts {
+        PostgresUrlParts {
+            host: self.data_dir.display().to_string(),
+            port: self.port,
+            database: "postgres".to_string(),
+            user: None,
+            password: None,
+            query_params: std::collections::HashMap::new(),
+            target: PostgresConnectTarget::UnixSocket {
+                dir: self.data_dir.clone(),
+            },
+            hosts: Vec::new(),
+        }
+    }
+
+    /// `postgresql://` URL form of [`Self::url_parts`], for APIs that take a
+    /// connection string rather than already-parsed parts
+    pub fn url(&self) -> String {
+        format!(
+            "postgresql:///postgres?host={}&port={}",
+            self.data_dir.display(),
+            self.port
+        )
+    }
+}
+
+impl Drop for EphemeralPostgres {
+    fn drop(&mut self) {
+        self.pg_ctl_stop();
+        if let Err(e) = remove_managed_temp_dir(&self.data_dir) {
+            tracing::warn!(
+                "Failed to remove PGDATA directory {}: {}",
+                self.data_dir.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Bind an ephemeral TCP listener to have the OS assign a free port, then drop
+/// it so Postgres can bind the same port; there's an inherent (and in
+/// practice negligible) race between the two binds
+fn find_free_port() -> Result<u16> {
+    let listener = TcpListener::bind("127.0.0.1:0").context("Failed to bind to an OS-assigned port")?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// A source/target [`EphemeralPostgres`] pair already wired up for logical
+/// replication - publication on `source`, subscription on `target`, a couple
+/// of seeded tables, initial sync complete - so `commands::status` can be
+/// exercised against real `pg_stat_replication`/`pg_stat_subscription` output
+/// instead of mocking it.
+pub struct ReplicationPair {
+    pub source: EphemeralPostgres,
+    pub target: EphemeralPostgres,
+}
+
+impl ReplicationPair {
+    /// Name of the publication created on `source` by [`Self::start`]
+    pub const PUBLICATION_NAME: &'static str = "seren_test_pub";
+
+    /// Provision both instances, seed `public.widgets`/`public.gadgets` on
+    /// the source with a couple of rows each, publish all tables under
+    /// [`Self::PUBLICATION_NAME`], subscribe to them on the target as
+    /// `sub_name`, and wait up to `timeout_secs` for initial sync to finish.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either instance fails to start, the publication or
+    /// subscription can't be created, or initial sync doesn't complete within
+    /// `timeout_secs`.
+    pub async fn start(sub_name: &str, timeout_secs: u64) -> Result<Self> {
+        let source =
+            EphemeralPostgres::start_for_replication().context("Failed to start source instance")?;
+        let target =
+            EphemeralPostgres::start_for_replication().context("Failed to start target instance")?;
+
+        let source_client = crate::postgres::connect(&source.url())
+            .await
+            .context("Failed to connect to source instance")?;
+        let target_client = crate::postgres::connect(&target.url())
+            .await
+            .context("Failed to connect to target instance")?;
+
+        source_client
+            .batch_execute(
+                "CREATE TABLE widgets (id serial PRIMARY KEY, name text);
+                 CREATE TABLE gadgets (id serial PRIMARY KEY, label text);
+                 INSERT INTO widgets (name) VALUES ('left-widget'), ('right-widget');
+                 INSERT INTO gadgets (label) VALUES ('gizmo');",
+            )
+            .await
+            .context("Failed to seed source tables")?;
+
+        // A subscription's initial copy needs matching tables already present
+        // on the target; it doesn't create schema itself.
+        target_client
+            .batch_execute(
+                "CREATE TABLE widgets (id serial PRIMARY KEY, name text);
+                 CREATE TABLE gadgets (id serial PRIMARY KEY, label text);",
+            )
+            .await
+            .context("Failed to create target schema")?;
+
+        let filter = crate::filters::ReplicationFilter::empty();
+        crate::replication::create_publication(
+            &source_client,
+            "postgres",
+            Self::PUBLICATION_NAME,
+            &filter,
+        )
+        .await
+        .context("Failed to create publication")?;
+
+        crate::replication::create_subscription(
+            &target_client,
+            sub_name,
+            &source.url(),
+            Self::PUBLICATION_NAME,
+            &crate::replication::SubscriptionOptions::default(),
+        )
+        .await
+        .context("Failed to create subscription")?;
+
+        crate::replication::wait_for_sync(&target_client, sub_name, timeout_secs)
+            .await
+            .context("Subscription did not reach initial sync in time")?;
+
+        Ok(Self { source, target })
+    }
+
+    /// `postgresql://` URL of the source instance
+    pub fn source_url(&self) -> String {
+        self.source.url()
+    }
+
+    /// `postgresql://` URL of the target instance
+    pub fn target_url(&self) -> String {
+        self.target.url()
+    }
+}
+
+/// Start a source and target [`EphemeralPostgres`] instance for an end-to-end
+/// migration test, binding them to `$source` and `$target`
+///
+/// # Errors
+///
+/// Propagates the first instance's startup error via `?`.
+#[macro_export]
+macro_rules! seren_test_db {
+    ($source:ident, $target:ident) => {
+        let $source = $crate::test_support::EphemeralPostgres::start()?;
+        let $target = $crate::test_support::EphemeralPostgres::start()?;
+    };
+}